@@ -1,9 +1,39 @@
+use anyhow::anyhow;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 
-pub async fn account_info(address: &Pubkey, rpc_client: &RpcClient) -> anyhow::Result<()> {
-    let account_info = rpc_client.get_account(&address).await?;
-    println!("{}: {:#?}", address, account_info);
+use crate::command::address::{DerivationArgs, identify_derivation};
+use crate::error::{CliError, CliResult};
+use crate::output::{CliAccount, CliAccountEncoding};
+use crate::parser::parse_account;
 
-    Ok(())
+pub async fn account_info(
+    address: &Pubkey,
+    encoding: CliAccountEncoding,
+    output_file: Option<&str>,
+    derivation: &DerivationArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliAccount> {
+    let account_info = rpc_client.get_account(address).await?;
+
+    if let Some(output_file) = output_file {
+        std::fs::write(output_file, &account_info.data).map_err(|err| {
+            CliError::Other(anyhow!("写入账户数据到 {} 失败: {}", output_file, err))
+        })?;
+    }
+
+    Ok(CliAccount {
+        parsed: parse_account(&account_info.owner, &account_info.data),
+        output_file: output_file.map(str::to_string),
+        // 未指定 --program 时按账户的 owner 派生
+        derivation: (!derivation.seeds.is_empty()).then(|| {
+            identify_derivation(
+                address,
+                derivation.program.as_ref().unwrap_or(&account_info.owner),
+                &derivation.seeds,
+                derivation.base.as_ref(),
+            )
+        }),
+        ..CliAccount::new(address, &account_info, encoding)
+    })
 }
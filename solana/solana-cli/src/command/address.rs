@@ -0,0 +1,336 @@
+use std::fmt;
+use std::str::FromStr;
+
+use clap::Args;
+use solana_sdk::pubkey::{MAX_SEED_LEN, MAX_SEEDS, Pubkey};
+
+use crate::error::{CliError, CliResult};
+use crate::output::{CliDerivation, CliPda, CliSeed, CliSeedAddress};
+
+/// find-pda / account --seed 的一个 seed：原始写法与编码后的字节
+#[derive(Clone, Debug)]
+pub struct Seed {
+    pub spec: String,
+    pub bytes: Vec<u8>,
+}
+
+/// account 检查地址派生方式的参数
+#[derive(Args)]
+pub struct DerivationArgs {
+    /// 检查地址是否由这些 seed 派生 (PDA 或 create_with_seed)，格式同 find-pda --seed，可重复
+    #[arg(long = "seed", value_name = "TYPE:VALUE", value_parser = parse_seed)]
+    pub seeds: Vec<Seed>,
+    /// 派生地址的程序，默认为账户的 owner
+    #[arg(long, value_name = "PROGRAM_ID", requires = "seeds")]
+    pub program: Option<Pubkey>,
+    /// 同时检查以该地址为 base 的 create_with_seed 派生 (此时只能有一个 seed)
+    #[arg(long, value_name = "PUBKEY", requires = "seeds")]
+    pub base: Option<Pubkey>,
+}
+
+/// 解析 `TYPE:VALUE` 形式的 seed；整数按指定的字节序编码
+pub fn parse_seed(value: &str) -> Result<Seed, String> {
+    let (kind, data) = value
+        .split_once(':')
+        .ok_or_else(|| format!("格式应为 TYPE:VALUE: {}", value))?;
+    let bytes = match kind {
+        "str" => Ok(data.as_bytes().to_vec()),
+        "pubkey" => data
+            .parse::<Pubkey>()
+            .map(|pubkey| pubkey.to_bytes().to_vec())
+            .map_err(|err| err.to_string()),
+        "hex" => decode_hex(data).ok_or_else(|| "不是有效的十六进制".to_string()),
+        "base58" => bs58::decode(data)
+            .into_vec()
+            .map_err(|err| err.to_string()),
+        "u8" => int(data, |n: u8| vec![n]),
+        "u16le" => int(data, |n: u16| n.to_le_bytes().to_vec()),
+        "u16be" => int(data, |n: u16| n.to_be_bytes().to_vec()),
+        "u32le" => int(data, |n: u32| n.to_le_bytes().to_vec()),
+        "u32be" => int(data, |n: u32| n.to_be_bytes().to_vec()),
+        "u64le" => int(data, |n: u64| n.to_le_bytes().to_vec()),
+        "u64be" => int(data, |n: u64| n.to_be_bytes().to_vec()),
+        "i64le" => int(data, |n: i64| n.to_le_bytes().to_vec()),
+        "i64be" => int(data, |n: i64| n.to_be_bytes().to_vec()),
+        "u128le" => int(data, |n: u128| n.to_le_bytes().to_vec()),
+        "u128be" => int(data, |n: u128| n.to_be_bytes().to_vec()),
+        _ => {
+            return Err(format!(
+                "未知的 seed 类型 {} (支持 str、pubkey、hex、base58、u8、u16le/be、u32le/be、u64le/be、i64le/be、u128le/be)",
+                kind
+            ));
+        }
+    }
+    .map_err(|err| format!("无效的 {} seed {}: {}", kind, data, err))?;
+    if bytes.len() > MAX_SEED_LEN {
+        return Err(format!(
+            "seed {} 编码后为 {} 字节，超过上限 {}",
+            value,
+            bytes.len(),
+            MAX_SEED_LEN
+        ));
+    }
+    Ok(Seed {
+        spec: value.to_string(),
+        bytes,
+    })
+}
+
+/// 按 seed 与程序计算 PDA，bump 为 find_program_address 找到的规范 bump
+pub fn find_pda(program_id: &Pubkey, seeds: &[Seed]) -> CliResult<CliPda> {
+    // 规范 bump 本身占用最后一个 seed
+    if seeds.len() >= MAX_SEEDS {
+        return Err(CliError::InvalidInput(format!(
+            "最多只能指定 {} 个 seed",
+            MAX_SEEDS - 1
+        )));
+    }
+    let seed_bytes = seed_bytes(seeds);
+    let (address, bump) = Pubkey::try_find_program_address(&seed_bytes, program_id)
+        .ok_or_else(|| CliError::InvalidInput("找不到可用的 bump".to_string()))?;
+    Ok(CliPda {
+        address: address.to_string(),
+        bump,
+        program_id: program_id.to_string(),
+        seeds: seeds
+            .iter()
+            .map(|seed| CliSeed {
+                seed: seed.spec.clone(),
+                hex: encode_hex(&seed.bytes),
+            })
+            .collect(),
+    })
+}
+
+/// create_with_seed(base, seed, owner) 派生的地址，与 create-stake-account --seed 等使用的规则相同
+pub fn create_with_seed(base: &Pubkey, seed: &str, owner: &Pubkey) -> CliResult<CliSeedAddress> {
+    let address = Pubkey::create_with_seed(base, seed, owner)
+        .map_err(|err| CliError::InvalidInput(format!("无效的 seed {}: {}", seed, err)))?;
+    Ok(CliSeedAddress {
+        address: address.to_string(),
+        base: base.to_string(),
+        seed: seed.to_string(),
+        owner: owner.to_string(),
+    })
+}
+
+/// 判断 `address` 是否由给出的 seed 派生：先检查 create_with_seed (指定了 base 时)，
+/// 再逐个尝试 bump 检查 PDA，最后检查 seed 中已包含 bump 的情况
+pub fn identify_derivation(
+    address: &Pubkey,
+    program_id: &Pubkey,
+    seeds: &[Seed],
+    base: Option<&Pubkey>,
+) -> CliDerivation {
+    let mut derivation = CliDerivation {
+        method: None,
+        program_id: program_id.to_string(),
+        base: None,
+        seeds: seeds.iter().map(|seed| seed.spec.clone()).collect(),
+        bump: None,
+        canonical_bump: None,
+    };
+
+    if let Some(base) = base
+        && let [seed] = seeds
+        && let Ok(seed) = std::str::from_utf8(&seed.bytes)
+        && Pubkey::create_with_seed(base, seed, program_id).is_ok_and(|derived| derived == *address)
+    {
+        derivation.method = Some("create-with-seed".to_string());
+        derivation.base = Some(base.to_string());
+        return derivation;
+    }
+
+    let seed_bytes = seed_bytes(seeds);
+    if seeds.len() < MAX_SEEDS {
+        for bump in (0..=u8::MAX).rev() {
+            let bump_seed = [bump];
+            let mut with_bump = seed_bytes.clone();
+            with_bump.push(&bump_seed);
+            if Pubkey::create_program_address(&with_bump, program_id)
+                .is_ok_and(|derived| derived == *address)
+            {
+                derivation.method = Some("program-address".to_string());
+                derivation.bump = Some(bump);
+                derivation.canonical_bump =
+                    Pubkey::try_find_program_address(&seed_bytes, program_id).map(|(_, bump)| bump);
+                return derivation;
+            }
+        }
+    }
+    if Pubkey::create_program_address(&seed_bytes, program_id)
+        .is_ok_and(|derived| derived == *address)
+    {
+        derivation.method = Some("program-address".to_string());
+    }
+    derivation
+}
+
+fn seed_bytes(seeds: &[Seed]) -> Vec<&[u8]> {
+    seeds.iter().map(|seed| seed.bytes.as_slice()).collect()
+}
+
+fn int<T>(data: &str, encode: impl Fn(T) -> Vec<u8>) -> Result<Vec<u8>, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    data.parse::<T>().map(encode).map_err(|err| err.to_string())
+}
+
+fn decode_hex(data: &str) -> Option<Vec<u8>> {
+    let data = data.strip_prefix("0x").unwrap_or(data);
+    if !data.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..data.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(data.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(value: &str) -> Vec<u8> {
+        parse_seed(value).unwrap().bytes
+    }
+
+    #[test]
+    fn parse_seed_types() {
+        let pubkey = Pubkey::new_unique();
+        assert_eq!(bytes("str:vault"), b"vault");
+        assert_eq!(bytes("str:"), b"");
+        // 只按第一个冒号拆分
+        assert_eq!(bytes("str:a:b"), b"a:b");
+        assert_eq!(bytes(&format!("pubkey:{}", pubkey)), pubkey.to_bytes());
+        assert_eq!(bytes("hex:00ff10"), [0x00, 0xff, 0x10]);
+        assert_eq!(bytes("hex:0xABcd"), [0xab, 0xcd]);
+        assert_eq!(bytes("base58:2g"), [0x61]);
+        assert_eq!(bytes("u8:255"), [0xff]);
+        assert_eq!(bytes("u16le:258"), [0x02, 0x01]);
+        assert_eq!(bytes("u16be:258"), [0x01, 0x02]);
+        assert_eq!(bytes("u32le:16909060"), [0x04, 0x03, 0x02, 0x01]);
+        assert_eq!(bytes("u32be:16909060"), [0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(bytes("u64le:1"), [1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(bytes("u64be:1"), [0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(bytes("i64le:-1"), [0xff; 8]);
+        assert_eq!(
+            bytes("i64be:-2"),
+            [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]
+        );
+        assert_eq!(bytes("u128le:1").len(), 16);
+        assert_eq!(bytes("u128be:1")[15], 1);
+        assert_eq!(parse_seed("u8:7").unwrap().spec, "u8:7");
+    }
+
+    #[test]
+    fn parse_seed_rejects_invalid_values() {
+        for value in [
+            "vault",
+            "bytes:00",
+            "pubkey:not-a-pubkey",
+            "hex:abc",
+            "hex:zz",
+            "base58:0OIl",
+            "u8:256",
+            "u8:-1",
+            "u16le:65536",
+            "u32be:x",
+            "u64le:",
+        ] {
+            assert!(parse_seed(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn parse_seed_enforces_max_seed_len() {
+        assert_eq!(
+            bytes(&format!("str:{}", "a".repeat(MAX_SEED_LEN))).len(),
+            MAX_SEED_LEN
+        );
+        assert!(parse_seed(&format!("str:{}", "a".repeat(MAX_SEED_LEN + 1))).is_err());
+        assert!(parse_seed(&format!("hex:{}", "00".repeat(MAX_SEED_LEN + 1))).is_err());
+    }
+
+    #[test]
+    fn decode_hex_values() {
+        assert_eq!(decode_hex(""), Some(vec![]));
+        assert_eq!(decode_hex("0x"), Some(vec![]));
+        assert_eq!(decode_hex("0aFf"), Some(vec![0x0a, 0xff]));
+        assert_eq!(decode_hex("0x0aff"), Some(vec![0x0a, 0xff]));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("0xabc"), None);
+        assert_eq!(decode_hex("0g"), None);
+        // 多字节字符不能按两个字符一组切分
+        assert_eq!(decode_hex("é"), None);
+    }
+
+    #[test]
+    fn find_pda_matches_find_program_address() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let seeds = [
+            parse_seed("str:vault").unwrap(),
+            parse_seed(&format!("pubkey:{}", owner)).unwrap(),
+            parse_seed("u64le:42").unwrap(),
+        ];
+        let (address, bump) = Pubkey::find_program_address(
+            &[b"vault", owner.as_ref(), &42u64.to_le_bytes()],
+            &program_id,
+        );
+
+        let pda = find_pda(&program_id, &seeds).unwrap();
+        assert_eq!(pda.address, address.to_string());
+        assert_eq!(pda.bump, bump);
+        assert_eq!(pda.seeds[2].hex, "2a00000000000000");
+
+        let too_many = vec![parse_seed("u8:0").unwrap(); MAX_SEEDS];
+        assert!(find_pda(&program_id, &too_many).is_err());
+    }
+
+    #[test]
+    fn identify_program_address() {
+        let program_id = Pubkey::new_unique();
+        let seeds = [parse_seed("str:vault").unwrap()];
+        let (address, bump) = Pubkey::find_program_address(&[b"vault"], &program_id);
+
+        let derivation = identify_derivation(&address, &program_id, &seeds, None);
+        assert_eq!(derivation.method.as_deref(), Some("program-address"));
+        assert_eq!(derivation.bump, Some(bump));
+        assert_eq!(derivation.canonical_bump, Some(bump));
+
+        // seed 中已包含 bump
+        let with_bump = [
+            parse_seed("str:vault").unwrap(),
+            parse_seed(&format!("u8:{}", bump)).unwrap(),
+        ];
+        let derivation = identify_derivation(&address, &program_id, &with_bump, None);
+        assert_eq!(derivation.method.as_deref(), Some("program-address"));
+        assert_eq!(derivation.bump, None);
+
+        let other = identify_derivation(&address, &Pubkey::new_unique(), &seeds, None);
+        assert_eq!(other.method, None);
+    }
+
+    #[test]
+    fn identify_create_with_seed() {
+        let base = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let address = Pubkey::create_with_seed(&base, "stake:0", &owner).unwrap();
+        let seeds = [parse_seed("str:stake:0").unwrap()];
+
+        let derivation = identify_derivation(&address, &owner, &seeds, Some(&base));
+        assert_eq!(derivation.method.as_deref(), Some("create-with-seed"));
+        assert_eq!(derivation.base, Some(base.to_string()));
+
+        let derivation = identify_derivation(&address, &owner, &seeds, Some(&Pubkey::new_unique()));
+        assert_eq!(derivation.method, None);
+        assert_eq!(derivation.base, None);
+    }
+}
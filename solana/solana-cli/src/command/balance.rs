@@ -1,9 +1,210 @@
+use std::collections::{HashMap, HashSet};
+
+use solana_account::Account;
+use solana_account_decoder_client_types::UiAccountEncoding;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{native_token::Sol, pubkey::Pubkey};
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcTokenAccountsFilter};
+use solana_client::rpc_request::{MAX_MULTIPLE_ACCOUNTS, RpcRequest};
+use solana_client::rpc_response::{Response, RpcKeyedAccount};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::error::{CliError, CliResult};
+use crate::output::{
+    CliAddressBalance, CliBalance, CliBalances, CliParsedTokenAccount, CliTokenBalance,
+    CliTokenTotal, lamports_to_sol_string,
+};
+use crate::parser::token::{
+    metaplex_metadata_address, mint_symbol, parse_metaplex_symbol, parse_token_account,
+    parse_token_mint,
+};
+
+pub async fn balance(address: &Pubkey, rpc_client: &RpcClient) -> CliResult<CliBalance> {
+    let balance = rpc_client.get_balance(address).await?;
+
+    Ok(CliBalance::new(address, balance))
+}
+
+/// 查询多个地址的 SOL 余额，include_tokens 时同时列出各地址持有的代币账户并按 mint 合计
+pub async fn balances(
+    addresses: &[Pubkey],
+    include_tokens: bool,
+    rpc_client: &RpcClient,
+) -> CliResult<CliBalances> {
+    let accounts = get_multiple_accounts(rpc_client, addresses).await?;
+    let mut balances: Vec<CliAddressBalance> = addresses
+        .iter()
+        .zip(&accounts)
+        .map(|(address, account)| CliAddressBalance {
+            balance: CliBalance::new(address, account.as_ref().map_or(0, |a| a.lamports)),
+            tokens: None,
+        })
+        .collect();
+    let total_lamports = balances.iter().map(|b| b.balance.lamports).sum();
+
+    let mut token_totals = Vec::new();
+    if include_tokens {
+        let mut owned = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            owned.push(get_token_accounts(rpc_client, address).await?);
+        }
+
+        let mut mints = Vec::new();
+        let mut seen = HashSet::new();
+        for (_, token_account) in owned.iter().flatten() {
+            if seen.insert(token_account.mint.clone()) {
+                mints.push(token_account.mint.clone());
+            }
+        }
+        let mint_infos = get_mint_infos(rpc_client, &mints).await?;
+
+        let mut totals: HashMap<&str, (u64, usize)> = HashMap::new();
+        for (balance, token_accounts) in balances.iter_mut().zip(&owned) {
+            let tokens = token_accounts
+                .iter()
+                .map(|(address, token_account)| {
+                    let amount = token_account.amount.parse::<u64>().unwrap_or_default();
+                    let (decimals, symbol) = mint_infos
+                        .get(&token_account.mint)
+                        .cloned()
+                        .unwrap_or_default();
+                    let total = totals.entry(&token_account.mint).or_default();
+                    total.0 = total.0.saturating_add(amount);
+                    total.1 += 1;
+                    CliTokenBalance {
+                        address: address.to_string(),
+                        mint: token_account.mint.clone(),
+                        program: token_account.program.clone(),
+                        symbol,
+                        amount: token_account.amount.clone(),
+                        decimals,
+                        ui_amount: spl_token_2022::amount_to_ui_amount_string_trimmed(
+                            amount, decimals,
+                        ),
+                    }
+                })
+                .collect();
+            balance.tokens = Some(tokens);
+        }
+
+        token_totals = mints
+            .iter()
+            .map(|mint| {
+                let (amount, accounts) = totals.get(mint.as_str()).copied().unwrap_or_default();
+                let (decimals, symbol) = mint_infos.get(mint).cloned().unwrap_or_default();
+                CliTokenTotal {
+                    mint: mint.clone(),
+                    symbol,
+                    amount: amount.to_string(),
+                    decimals,
+                    ui_amount: spl_token_2022::amount_to_ui_amount_string_trimmed(amount, decimals),
+                    accounts,
+                }
+            })
+            .collect();
+    }
+
+    Ok(CliBalances {
+        balances,
+        total_lamports,
+        total_sol: lamports_to_sol_string(total_lamports),
+        token_totals,
+    })
+}
+
+/// 读取地址列表文件：每行一个地址，忽略空行与 # 开头的注释
+pub fn read_addresses_file(path: &str) -> CliResult<Vec<Pubkey>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| CliError::InvalidInput(format!("读取地址文件 {} 失败: {}", path, err)))?;
+    content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            line.parse::<Pubkey>().map_err(|_| {
+                CliError::InvalidInput(format!(
+                    "{} 第 {} 行不是有效的地址: {}",
+                    path, line_number, line
+                ))
+            })
+        })
+        .collect()
+}
+
+/// 按 getMultipleAccounts 的单次上限分批获取账户，结果与 pubkeys 一一对应
+pub async fn get_multiple_accounts(
+    rpc_client: &RpcClient,
+    pubkeys: &[Pubkey],
+) -> CliResult<Vec<Option<Account>>> {
+    let mut accounts = Vec::with_capacity(pubkeys.len());
+    for chunk in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        accounts.extend(rpc_client.get_multiple_accounts(chunk).await?);
+    }
+    Ok(accounts)
+}
+
+/// 获取 owner 在 spl-token 与 Token-2022 下的全部代币账户
+async fn get_token_accounts(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+) -> CliResult<Vec<(Pubkey, CliParsedTokenAccount)>> {
+    let config = RpcAccountInfoConfig {
+        // 使用 base64 以便复用本地的代币账户解码器
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(rpc_client.commitment()),
+        ..RpcAccountInfoConfig::default()
+    };
+    let mut token_accounts = Vec::new();
+    for program_id in [spl_token::ID.to_string(), spl_token_2022::ID.to_string()] {
+        let response: Response<Vec<RpcKeyedAccount>> = rpc_client
+            .send(
+                RpcRequest::GetTokenAccountsByOwner,
+                serde_json::json!([
+                    owner.to_string(),
+                    RpcTokenAccountsFilter::ProgramId(program_id),
+                    config
+                ]),
+            )
+            .await?;
+        for keyed_account in response.value {
+            let Ok(pubkey) = keyed_account.pubkey.parse::<Pubkey>() else {
+                continue;
+            };
+            let Some(account) = keyed_account.account.decode::<Account>() else {
+                continue;
+            };
+            if let Some(token_account) = parse_token_account(&account.owner, &account.data) {
+                token_accounts.push((pubkey, token_account));
+            }
+        }
+    }
+    Ok(token_accounts)
+}
 
-pub async fn balance(address: &Pubkey, rpc_client: &RpcClient) -> anyhow::Result<()> {
-    let balance = rpc_client.get_balance(&address).await?;
-    println!("{}: {}", address, Sol(balance));
+/// 获取各 mint 的精度与符号；符号优先取 Token-2022 元数据扩展，其次为 Metaplex metadata
+async fn get_mint_infos(
+    rpc_client: &RpcClient,
+    mints: &[String],
+) -> CliResult<HashMap<String, (u8, Option<String>)>> {
+    let mint_pubkeys: Vec<Pubkey> = mints.iter().filter_map(|mint| mint.parse().ok()).collect();
+    let mint_accounts = get_multiple_accounts(rpc_client, &mint_pubkeys).await?;
+    let metadata_addresses: Vec<Pubkey> =
+        mint_pubkeys.iter().map(metaplex_metadata_address).collect();
+    let metadata_accounts = get_multiple_accounts(rpc_client, &metadata_addresses).await?;
 
-    Ok(())
+    Ok(mint_pubkeys
+        .iter()
+        .zip(mint_accounts)
+        .zip(metadata_accounts)
+        .filter_map(|((mint, account), metadata)| {
+            let account = account?;
+            let parsed = parse_token_mint(&account.owner, &account.data)?;
+            let symbol = mint_symbol(&parsed).or_else(|| {
+                metadata
+                    .as_ref()
+                    .and_then(|metadata| parse_metaplex_symbol(&metadata.data))
+            });
+            Some((mint.to_string(), (parsed.decimals, symbol)))
+        })
+        .collect())
 }
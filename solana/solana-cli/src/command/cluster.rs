@@ -0,0 +1,296 @@
+use std::cmp::Ordering;
+
+use clap::ValueEnum;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcBlockProductionConfig, RpcBlockProductionConfigRange, RpcGetVoteAccountsConfig,
+    RpcLeaderScheduleConfig,
+};
+use solana_client::rpc_response::RpcVoteAccountInfo;
+use solana_sdk::clock::DEFAULT_MS_PER_SLOT;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::error::{CliError, CliResult};
+use crate::output::{
+    CliBlockProduction, CliBlockProductionEntry, CliClusterVersion, CliEpochInfo,
+    CliLeaderSchedule, CliLeaderSlot, CliValidator, CliValidators,
+};
+
+/// 估算 slot 时长时采样的性能样本数 (每个样本约 60 秒)
+const PERFORMANCE_SAMPLES: usize = 60;
+
+/// validators 的排序字段
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ValidatorSort {
+    /// 按活跃质押从大到小
+    Stake,
+    /// 按佣金从低到高
+    Commission,
+    /// 按当前 epoch 的跳块率从低到高 (无出块记录的排在最后)
+    SkipRate,
+    /// 按当前 epoch 获得的投票积分从高到低
+    Credits,
+    /// 按最近投票的 slot 从新到旧
+    LastVote,
+}
+
+/// 节点的软件版本与特性集
+pub async fn cluster_version(rpc_client: &RpcClient) -> CliResult<CliClusterVersion> {
+    let version = rpc_client.get_version().await?;
+    Ok(CliClusterVersion {
+        solana_core: version.solana_core,
+        feature_set: version.feature_set,
+    })
+}
+
+/// 当前 epoch 的进度，剩余时间按最近的性能样本估算 slot 时长
+pub async fn epoch_info(rpc_client: &RpcClient) -> CliResult<CliEpochInfo> {
+    let epoch_info = rpc_client.get_epoch_info().await?;
+    let samples = rpc_client
+        .get_recent_performance_samples(Some(PERFORMANCE_SAMPLES))
+        .await?;
+    let (slots, secs) = samples.iter().fold((0u64, 0u64), |(slots, secs), sample| {
+        (
+            slots + sample.num_slots,
+            secs + u64::from(sample.sample_period_secs),
+        )
+    });
+    let ms_per_slot = (secs * 1000)
+        .checked_div(slots)
+        .unwrap_or(DEFAULT_MS_PER_SLOT);
+
+    let remaining_slots = epoch_info
+        .slots_in_epoch
+        .saturating_sub(epoch_info.slot_index);
+    Ok(CliEpochInfo {
+        epoch: epoch_info.epoch,
+        absolute_slot: epoch_info.absolute_slot,
+        block_height: epoch_info.block_height,
+        slot_index: epoch_info.slot_index,
+        slots_in_epoch: epoch_info.slots_in_epoch,
+        first_slot: epoch_info.absolute_slot - epoch_info.slot_index,
+        transaction_count: epoch_info.transaction_count,
+        completed_percent: epoch_info.slot_index as f64 * 100.0
+            / epoch_info.slots_in_epoch.max(1) as f64,
+        ms_per_slot,
+        remaining_secs: remaining_slots * ms_per_slot / 1000,
+    })
+}
+
+/// 列出投票账户；跳块率来自当前 epoch 的出块统计
+pub async fn validators(
+    sort: ValidatorSort,
+    reverse: bool,
+    delinquent_only: bool,
+    keep_unstaked_delinquents: bool,
+    rpc_client: &RpcClient,
+) -> CliResult<CliValidators> {
+    let vote_accounts = rpc_client
+        .get_vote_accounts_with_config(RpcGetVoteAccountsConfig {
+            commitment: Some(rpc_client.commitment()),
+            keep_unstaked_delinquents: Some(keep_unstaked_delinquents),
+            ..RpcGetVoteAccountsConfig::default()
+        })
+        .await?;
+    let production = rpc_client.get_block_production().await?.value;
+
+    let current_stake: u64 = vote_accounts
+        .current
+        .iter()
+        .map(|vote| vote.activated_stake)
+        .sum();
+    let delinquent_stake: u64 = vote_accounts
+        .delinquent
+        .iter()
+        .map(|vote| vote.activated_stake)
+        .sum();
+    let total_stake = current_stake + delinquent_stake;
+
+    let to_validator = |vote: &RpcVoteAccountInfo, delinquent: bool| CliValidator {
+        identity: vote.node_pubkey.clone(),
+        vote_account: vote.vote_pubkey.clone(),
+        commission: vote.commission,
+        last_vote: vote.last_vote,
+        root_slot: vote.root_slot,
+        activated_stake: vote.activated_stake,
+        stake_percent: percent(vote.activated_stake, total_stake),
+        epoch_credits: vote
+            .epoch_credits
+            .last()
+            .map_or(0, |(_, credits, previous)| {
+                credits.saturating_sub(*previous)
+            }),
+        skip_rate: production
+            .by_identity
+            .get(&vote.node_pubkey)
+            .and_then(|(slots, produced)| skip_rate(*slots as u64, *produced as u64)),
+        delinquent,
+    };
+    let mut validators: Vec<CliValidator> = vote_accounts
+        .delinquent
+        .iter()
+        .map(|vote| to_validator(vote, true))
+        .collect();
+    if !delinquent_only {
+        validators.extend(
+            vote_accounts
+                .current
+                .iter()
+                .map(|vote| to_validator(vote, false)),
+        );
+    }
+
+    validators.sort_by(|a, b| {
+        let ordering = match sort {
+            ValidatorSort::Stake => b.activated_stake.cmp(&a.activated_stake),
+            ValidatorSort::Commission => a.commission.cmp(&b.commission),
+            ValidatorSort::SkipRate => match (a.skip_rate, b.skip_rate) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            ValidatorSort::Credits => b.epoch_credits.cmp(&a.epoch_credits),
+            ValidatorSort::LastVote => b.last_vote.cmp(&a.last_vote),
+        };
+        // 相同时按质押从大到小，保证输出稳定
+        ordering.then_with(|| b.activated_stake.cmp(&a.activated_stake))
+    });
+    if reverse {
+        validators.reverse();
+    }
+
+    Ok(CliValidators {
+        total_active_stake: total_stake,
+        total_current_stake: current_stake,
+        total_delinquent_stake: delinquent_stake,
+        current_count: vote_accounts.current.len(),
+        delinquent_count: vote_accounts.delinquent.len(),
+        validators,
+    })
+}
+
+/// 指定 epoch (默认当前 epoch) 的 leader 排班，按 slot 排序
+pub async fn leader_schedule(
+    epoch: Option<u64>,
+    identity: Option<&Pubkey>,
+    rpc_client: &RpcClient,
+) -> CliResult<CliLeaderSchedule> {
+    let epoch = match epoch {
+        Some(epoch) => epoch,
+        None => rpc_client.get_epoch_info().await?.epoch,
+    };
+    let first_slot = rpc_client
+        .get_epoch_schedule()
+        .await?
+        .get_first_slot_in_epoch(epoch);
+    let schedule = rpc_client
+        .get_leader_schedule_with_config(
+            Some(first_slot),
+            RpcLeaderScheduleConfig {
+                identity: identity.map(Pubkey::to_string),
+                commitment: Some(rpc_client.commitment()),
+            },
+        )
+        .await?
+        .ok_or_else(|| {
+            CliError::InvalidInput(format!("节点没有 epoch {} 的 leader schedule", epoch))
+        })?;
+
+    let mut leaders: Vec<CliLeaderSlot> = schedule
+        .into_iter()
+        .flat_map(|(leader, slot_indexes)| {
+            slot_indexes.into_iter().map(move |index| CliLeaderSlot {
+                slot: first_slot + index as u64,
+                leader: leader.clone(),
+            })
+        })
+        .collect();
+    leaders.sort_by_key(|leader| leader.slot);
+    Ok(CliLeaderSchedule {
+        epoch,
+        first_slot,
+        leaders,
+    })
+}
+
+/// 指定 epoch (默认当前 epoch，截至最新 slot) 各 leader 的出块与跳块统计
+pub async fn block_production(
+    epoch: Option<u64>,
+    identity: Option<&Pubkey>,
+    rpc_client: &RpcClient,
+) -> CliResult<CliBlockProduction> {
+    let epoch_info = rpc_client.get_epoch_info().await?;
+    let epoch = epoch.unwrap_or(epoch_info.epoch);
+    if epoch > epoch_info.epoch {
+        return Err(CliError::InvalidInput(format!(
+            "epoch {} 尚未开始 (当前 epoch 为 {})",
+            epoch, epoch_info.epoch
+        )));
+    }
+    let epoch_schedule = rpc_client.get_epoch_schedule().await?;
+    let first_slot = epoch_schedule.get_first_slot_in_epoch(epoch);
+    let last_slot = if epoch == epoch_info.epoch {
+        epoch_info.absolute_slot
+    } else {
+        epoch_schedule.get_last_slot_in_epoch(epoch)
+    };
+
+    let production = rpc_client
+        .get_block_production_with_config(RpcBlockProductionConfig {
+            identity: identity.map(Pubkey::to_string),
+            range: Some(RpcBlockProductionConfigRange {
+                first_slot,
+                last_slot: Some(last_slot),
+            }),
+            commitment: Some(rpc_client.commitment()),
+        })
+        .await?
+        .value;
+
+    let mut leaders: Vec<CliBlockProductionEntry> = production
+        .by_identity
+        .into_iter()
+        .map(|(identity, (slots, produced))| {
+            let (slots, produced) = (slots as u64, produced as u64);
+            CliBlockProductionEntry {
+                identity,
+                leader_slots: slots,
+                blocks_produced: produced,
+                skipped_slots: slots.saturating_sub(produced),
+                skip_rate: skip_rate(slots, produced).unwrap_or_default(),
+            }
+        })
+        .collect();
+    leaders.sort_by(|a, b| {
+        b.leader_slots
+            .cmp(&a.leader_slots)
+            .then_with(|| a.identity.cmp(&b.identity))
+    });
+
+    let total_slots: u64 = leaders.iter().map(|leader| leader.leader_slots).sum();
+    let total_produced: u64 = leaders.iter().map(|leader| leader.blocks_produced).sum();
+    Ok(CliBlockProduction {
+        epoch,
+        first_slot: production.range.first_slot,
+        last_slot: production.range.last_slot,
+        total_slots,
+        total_blocks_produced: total_produced,
+        total_slots_skipped: total_slots.saturating_sub(total_produced),
+        skip_rate: skip_rate(total_slots, total_produced).unwrap_or_default(),
+        leaders,
+    })
+}
+
+/// 跳块率 (百分比)，没有 leader slot 时为 None
+fn skip_rate(leader_slots: u64, blocks_produced: u64) -> Option<f64> {
+    (leader_slots > 0).then(|| percent(leader_slots.saturating_sub(blocks_produced), leader_slots))
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
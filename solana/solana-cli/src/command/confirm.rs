@@ -0,0 +1,261 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_request::RpcRequest;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, UiInstruction, UiLoadedAddresses,
+    UiTransactionEncoding, UiTransactionTokenBalance,
+};
+
+use crate::error::{CliError, CliResult, describe_transaction_error};
+use crate::output::{
+    CliAccountMeta, CliBalanceChange, CliConfirmedInstruction, CliConfirmedTransaction,
+    CliTokenBalanceChange,
+};
+use crate::parser::instruction::{parse_instruction, program_name};
+use crate::transaction::confirmation_status_str;
+
+/// 查询交易的确认状态，获取已上链的交易并解码其中的指令、日志、手续费与余额变化 (支持 v0 交易)
+pub async fn confirm(
+    signature: &Signature,
+    rpc_client: &RpcClient,
+) -> CliResult<CliConfirmedTransaction> {
+    let confirmation_status = rpc_client
+        .get_signature_statuses_with_history(&[*signature])
+        .await?
+        .value
+        .pop()
+        .flatten()
+        .map(|status| status.confirmation_status());
+    match get_transaction(signature, rpc_client).await? {
+        Some(mut transaction) => {
+            transaction.confirmation_status = confirmation_status
+                .as_ref()
+                .map(|status| confirmation_status_str(status).to_string());
+            Ok(transaction)
+        }
+        None => Err(CliError::InvalidInput(match confirmation_status {
+            Some(_) => format!(
+                "交易 {} 已被处理，但尚未达到 {:?} 提交级别，请稍后再试",
+                signature,
+                transaction_commitment(rpc_client).commitment
+            ),
+            None => format!("未找到交易 {}", signature),
+        })),
+    }
+}
+
+/// getTransaction / getSignaturesForAddress 不支持 processed，至少使用 confirmed
+pub fn transaction_commitment(rpc_client: &RpcClient) -> CommitmentConfig {
+    if rpc_client.commitment().is_at_least_confirmed() {
+        rpc_client.commitment()
+    } else {
+        CommitmentConfig::confirmed()
+    }
+}
+
+/// 调用 getTransaction 并解码交易，交易不存在 (或尚未达到提交级别) 时返回 None
+pub async fn get_transaction(
+    signature: &Signature,
+    rpc_client: &RpcClient,
+) -> CliResult<Option<CliConfirmedTransaction>> {
+    let config = RpcTransactionConfig {
+        // 使用 base64 以便在本地解码交易并复用指令解码器
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(transaction_commitment(rpc_client)),
+        max_supported_transaction_version: Some(0),
+    };
+    let transaction: Option<EncodedConfirmedTransactionWithStatusMeta> = rpc_client
+        .send(
+            RpcRequest::GetTransaction,
+            serde_json::json!([signature.to_string(), config]),
+        )
+        .await?;
+    let Some(transaction) = transaction else {
+        return Ok(None);
+    };
+
+    let versioned = transaction
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| CliError::InvalidInput(format!("无法解码交易 {}", signature)))?;
+    let meta = transaction
+        .transaction
+        .meta
+        .ok_or_else(|| CliError::InvalidInput(format!("交易 {} 缺少执行结果", signature)))?;
+    let message = &versioned.message;
+
+    // v0 交易通过地址查找表加载的账户排在静态账户之后：先可写，后只读
+    let mut account_keys: Vec<Pubkey> = message.static_account_keys().to_vec();
+    let static_len = account_keys.len();
+    let mut loaded_writable = 0;
+    if let Some(loaded) = Option::<UiLoadedAddresses>::from(meta.loaded_addresses) {
+        loaded_writable = loaded.writable.len();
+        for address in loaded.writable.iter().chain(&loaded.readonly) {
+            account_keys.push(
+                address.parse().map_err(|_| {
+                    CliError::InvalidInput(format!("无效的查找表地址: {}", address))
+                })?,
+            );
+        }
+    }
+    let account_meta = |index: usize| CliAccountMeta {
+        pubkey: account_keys
+            .get(index)
+            .map_or_else(|| format!("#{}", index), ToString::to_string),
+        is_signer: message.is_signer(index),
+        is_writable: if index < static_len {
+            message.is_maybe_writable(index, None)
+        } else {
+            index - static_len < loaded_writable
+        },
+    };
+    let decode_instruction = |program_id_index: u8, accounts: &[u8], data: &[u8]| {
+        let program_id = account_keys
+            .get(program_id_index as usize)
+            .copied()
+            .unwrap_or_default();
+        let instruction_accounts: Vec<Pubkey> = accounts
+            .iter()
+            .filter_map(|&index| account_keys.get(index as usize).copied())
+            .collect();
+        CliConfirmedInstruction {
+            program_id: program_id.to_string(),
+            program: program_name(&program_id).map(str::to_string),
+            parsed: parse_instruction(&program_id, &instruction_accounts, data),
+            accounts: accounts
+                .iter()
+                .map(|&index| account_meta(index as usize))
+                .collect(),
+            data: bs58::encode(data).into_string(),
+            inner_instructions: Vec::new(),
+        }
+    };
+
+    let mut instructions: Vec<CliConfirmedInstruction> = message
+        .instructions()
+        .iter()
+        .map(|instruction| {
+            decode_instruction(
+                instruction.program_id_index,
+                &instruction.accounts,
+                &instruction.data,
+            )
+        })
+        .collect();
+    for inner in Option::<Vec<_>>::from(meta.inner_instructions).unwrap_or_default() {
+        let Some(parent) = instructions.get_mut(inner.index as usize) else {
+            continue;
+        };
+        for instruction in inner.instructions {
+            if let UiInstruction::Compiled(compiled) = instruction {
+                let data = bs58::decode(&compiled.data).into_vec().unwrap_or_default();
+                parent.inner_instructions.push(decode_instruction(
+                    compiled.program_id_index,
+                    &compiled.accounts,
+                    &data,
+                ));
+            }
+        }
+    }
+
+    let balance_changes = account_keys
+        .iter()
+        .zip(meta.pre_balances.iter().zip(&meta.post_balances))
+        .filter(|(_, (pre, post))| pre != post)
+        .map(|(address, (&pre, &post))| CliBalanceChange {
+            address: address.to_string(),
+            pre,
+            post,
+            delta: post as i128 - pre as i128,
+        })
+        .collect();
+    let token_balance_changes = token_balance_changes(
+        &account_keys,
+        Option::from(meta.pre_token_balances).unwrap_or_default(),
+        Option::from(meta.post_token_balances).unwrap_or_default(),
+    );
+
+    Ok(Some(CliConfirmedTransaction {
+        signature: signature.to_string(),
+        slot: transaction.slot,
+        block_time: transaction.block_time,
+        confirmation_status: None,
+        version: match message {
+            VersionedMessage::Legacy(_) => "legacy".to_string(),
+            VersionedMessage::V0(_) => "v0".to_string(),
+        },
+        error: meta.err.map(|err| describe_transaction_error(&err.into())),
+        fee: meta.fee,
+        compute_units_consumed: meta.compute_units_consumed.into(),
+        recent_blockhash: message.recent_blockhash().to_string(),
+        signers: account_keys
+            .iter()
+            .take(message.header().num_required_signatures as usize)
+            .map(ToString::to_string)
+            .collect(),
+        instructions,
+        logs: Option::from(meta.log_messages).unwrap_or_default(),
+        balance_changes,
+        token_balance_changes,
+    }))
+}
+
+/// 按账户与 mint 对齐交易前后的代币余额，只保留发生变化的账户 (含新建与关闭的账户)
+fn token_balance_changes(
+    account_keys: &[Pubkey],
+    pre: Vec<UiTransactionTokenBalance>,
+    post: Vec<UiTransactionTokenBalance>,
+) -> Vec<CliTokenBalanceChange> {
+    let raw = |balance: Option<&UiTransactionTokenBalance>| {
+        balance.map_or(0, |balance| {
+            balance
+                .ui_token_amount
+                .amount
+                .parse::<u64>()
+                .unwrap_or_default()
+        })
+    };
+    let mut keys: Vec<(u8, String)> = Vec::new();
+    for balance in pre.iter().chain(&post) {
+        let key = (balance.account_index, balance.mint.clone());
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys.into_iter()
+        .filter_map(|(account_index, mint)| {
+            let find = |balances: &[UiTransactionTokenBalance]| {
+                balances
+                    .iter()
+                    .find(|balance| balance.account_index == account_index && balance.mint == mint)
+                    .cloned()
+            };
+            let (pre, post) = (find(&pre), find(&post));
+            let (pre_amount, post_amount) = (raw(pre.as_ref()), raw(post.as_ref()));
+            if pre_amount == post_amount {
+                return None;
+            }
+            let known = post.as_ref().or(pre.as_ref())?;
+            let decimals = known.ui_token_amount.decimals;
+            let ui =
+                |amount: u64| spl_token_2022::amount_to_ui_amount_string_trimmed(amount, decimals);
+            let sign = if post_amount < pre_amount { "-" } else { "+" };
+            Some(CliTokenBalanceChange {
+                account: account_keys
+                    .get(account_index as usize)
+                    .map_or_else(|| format!("#{}", account_index), ToString::to_string),
+                owner: Option::from(known.owner.clone()),
+                decimals,
+                pre: ui(pre_amount),
+                post: ui(post_amount),
+                delta: format!("{}{}", sign, ui(pre_amount.abs_diff(post_amount))),
+                mint,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,1045 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::AddressLookupTableAccount;
+use solana_sdk::native_token::Sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+use solana_system_interface::instruction as system_instruction;
+use solana_transaction_status_client_types::TransactionStatus;
+use spl_token_2022::instruction;
+
+use crate::command::token::{
+    MintArgs, TokenAmount, associated_token_address, checked_instruction,
+    create_associated_token_account_instruction, get_mint, get_token_balance, spl_pubkey,
+};
+use crate::command::transfer::SpendAmount;
+use crate::error::{CliError, CliResult, describe_transaction_error};
+use crate::output::{CliDistribute, CliDistributeEntry, CliDistributeTransaction};
+use crate::transaction::{
+    ComputeBudget, MAX_COMPUTE_UNIT_LIMIT, MAX_SIGNATURE_STATUSES, PACKET_DATA_SIZE,
+    TransactionArgs, build_transaction, send_and_confirm, sign_transaction, simulate_transaction,
+};
+
+/// 单笔交易最多引用的账户数 (含从地址查找表加载的账户)，与运行时的 MAX_TX_ACCOUNT_LOCKS 相同
+const MAX_TX_ACCOUNT_LOCKS: usize = 64;
+
+/// 发放的资产：SOL 或某个 SPL 代币
+enum Asset {
+    Sol,
+    Token {
+        mint: Pubkey,
+        program_id: Pubkey,
+        decimals: u8,
+    },
+}
+
+impl Asset {
+    fn label(&self) -> String {
+        match self {
+            Asset::Sol => "SOL".to_string(),
+            Asset::Token { mint, .. } => mint.to_string(),
+        }
+    }
+
+    fn ui_amount(&self, amount: u64) -> String {
+        match self {
+            Asset::Sol => format!("{}", Sol(amount)),
+            Asset::Token { decimals, .. } => {
+                spl_token_2022::amount_to_ui_amount_string_trimmed(amount, *decimals)
+            }
+        }
+    }
+
+    fn parse_amount(&self, amount: &str) -> CliResult<u64> {
+        match self {
+            Asset::Sol => match SpendAmount::parse(amount, false)? {
+                SpendAmount::Lamports(lamports) => Ok(lamports),
+                SpendAmount::All => Err(CliError::InvalidInput(
+                    "distribute 不支持 ALL，请填写具体数量".to_string(),
+                )),
+            },
+            Asset::Token { decimals, .. } => {
+                TokenAmount::Ui(amount.to_string()).to_raw(*decimals, None)
+            }
+        }
+    }
+}
+
+/// CSV 中的一行发放记录，amount 为最小单位 (lamports 或代币原始数量)
+struct Payout {
+    line: usize,
+    recipient: Pubkey,
+    amount: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum PayoutStatus {
+    /// 尚未发送，或已确认未上链 (区块哈希过期) 可以安全重发
+    Pending,
+    /// 已签名并记录签名，是否上链需要核对
+    Sent,
+    Confirmed,
+    /// 模拟或链上执行失败，未发生转账，可以重发
+    Failed,
+}
+
+impl PayoutStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PayoutStatus::Pending => "pending",
+            PayoutStatus::Sent => "sent",
+            PayoutStatus::Confirmed => "confirmed",
+            PayoutStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LedgerEntry {
+    line: usize,
+    recipient: String,
+    amount: u64,
+    status: PayoutStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+    /// 交易所用区块哈希的最后有效区块高度，超过该高度仍未上链的交易不会再被处理
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_valid_block_height: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// 本地进度账本：每一行的状态与交易签名，崩溃后重新运行时据此继续而不会重复发放
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ledger {
+    /// SOL 或代币 mint 地址
+    asset: String,
+    source: String,
+    entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    fn new(asset: &Asset, source: &Pubkey, payouts: &[Payout]) -> Self {
+        Self {
+            asset: asset.label(),
+            source: source.to_string(),
+            entries: payouts
+                .iter()
+                .map(|payout| LedgerEntry {
+                    line: payout.line,
+                    recipient: payout.recipient.to_string(),
+                    amount: payout.amount,
+                    status: PayoutStatus::Pending,
+                    signature: None,
+                    last_valid_block_height: None,
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// 读取已有的账本并确认它与本次的 CSV、资产和转出账户一致
+    fn load(path: &str, asset: &Asset, source: &Pubkey, payouts: &[Payout]) -> CliResult<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| CliError::InvalidInput(format!("读取账本 {} 失败: {}", path, err)))?;
+        let ledger: Ledger = serde_json::from_str(&content)
+            .map_err(|err| CliError::InvalidInput(format!("无法解析账本 {}: {}", path, err)))?;
+        if ledger.asset != asset.label() || ledger.source != source.to_string() {
+            return Err(CliError::InvalidInput(format!(
+                "账本 {} 记录的是 {} 从 {} 发放，与本次参数不一致",
+                path, ledger.asset, ledger.source
+            )));
+        }
+        let matches = ledger.entries.len() == payouts.len()
+            && ledger.entries.iter().zip(payouts).all(|(entry, payout)| {
+                entry.line == payout.line
+                    && entry.recipient == payout.recipient.to_string()
+                    && entry.amount == payout.amount
+            });
+        if !matches {
+            return Err(CliError::InvalidInput(format!(
+                "账本 {} 与 CSV 内容不一致；CSV 在上次运行后被修改过，请恢复原文件或改用新的 --ledger",
+                path
+            )));
+        }
+        Ok(ledger)
+    }
+
+    /// 先写临时文件再重命名，避免中途崩溃留下损坏的账本
+    fn save(&self, path: &str) -> CliResult<()> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|err| CliError::Other(anyhow!("序列化账本失败: {}", err)))?;
+        let tmp_path = format!("{}.tmp", path);
+        std::fs::write(&tmp_path, content)
+            .and_then(|()| std::fs::rename(&tmp_path, path))
+            .map_err(|err| CliError::Other(anyhow!("写入账本 {} 失败: {}", path, err)))
+    }
+
+    fn set_status(
+        &mut self,
+        indexes: &[usize],
+        status: PayoutStatus,
+        signature: Option<&Signature>,
+        last_valid_block_height: Option<u64>,
+        error: Option<&str>,
+    ) {
+        for &index in indexes {
+            let entry = &mut self.entries[index];
+            entry.status = status;
+            entry.signature = signature.map(ToString::to_string);
+            entry.last_valid_block_height = last_valid_block_height;
+            entry.error = error.map(str::to_string);
+        }
+    }
+}
+
+/// 按 CSV 向多个地址发放 SOL 或代币
+///
+/// 多笔转账在交易大小与 `batch_size` 允许的范围内打包进同一笔交易。每笔交易在发送前把签名写入账本，
+/// 重新运行时先核对这些签名的状态 (必要时等待区块哈希过期)，只重发确认未到账的行。
+#[allow(clippy::too_many_arguments)]
+pub async fn distribute(
+    fee_payer: &dyn Signer,
+    from: &dyn Signer,
+    csv_path: &str,
+    mint: Option<Pubkey>,
+    ledger_path: Option<String>,
+    batch_size: usize,
+    fund_recipient: bool,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliDistribute> {
+    if tx_args.sign_only || tx_args.blockhash.is_some() || !tx_args.signers.is_empty() {
+        return Err(CliError::InvalidInput(
+            "distribute 会发送多笔交易，不支持 --sign-only、--blockhash 与 --signer".to_string(),
+        ));
+    }
+    if batch_size == 0 {
+        return Err(CliError::InvalidInput(
+            "--batch-size 必须大于 0".to_string(),
+        ));
+    }
+
+    let asset = match mint {
+        None => Asset::Sol,
+        Some(mint) => {
+            let mint_info = get_mint(&mint, &MintArgs::default(), tx_args, rpc_client).await?;
+            Asset::Token {
+                mint,
+                program_id: mint_info.program_id,
+                decimals: mint_info.decimals()?,
+            }
+        }
+    };
+    let payouts = read_payouts(csv_path, &asset)?;
+    let ledger_path = ledger_path.unwrap_or_else(|| format!("{}.ledger.json", csv_path));
+    let mut ledger = if Path::new(&ledger_path).exists() {
+        Ledger::load(&ledger_path, &asset, &from.pubkey(), &payouts)?
+    } else {
+        Ledger::new(&asset, &from.pubkey(), &payouts)
+    };
+
+    resolve_sent_entries(&mut ledger, rpc_client).await?;
+    if !tx_args.dry_run {
+        ledger.save(&ledger_path)?;
+    }
+    let previously_confirmed = ledger
+        .entries
+        .iter()
+        .filter(|entry| entry.status == PayoutStatus::Confirmed)
+        .count();
+
+    let todo: Vec<usize> = ledger
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| matches!(entry.status, PayoutStatus::Pending | PayoutStatus::Failed))
+        .map(|(index, _)| index)
+        .collect();
+    let remaining: u64 = todo.iter().map(|&index| payouts[index].amount).sum();
+    check_source_balance(&asset, from, remaining, rpc_client).await?;
+
+    let lookup_tables = tx_args.lookup_tables(rpc_client).await?;
+    let batches = pack_batches(
+        &todo,
+        &payouts,
+        &asset,
+        fee_payer,
+        from,
+        batch_size,
+        fund_recipient,
+        &lookup_tables,
+    )?;
+    eprintln!(
+        "Distributing {} to {} recipients in {} transactions ({} already confirmed)",
+        asset.ui_amount(remaining),
+        todo.len(),
+        batches.len(),
+        previously_confirmed
+    );
+
+    let mut transactions = Vec::with_capacity(batches.len());
+    let mut fee = 0;
+    for (indexes, instructions) in batches {
+        let amount = asset.ui_amount(indexes.iter().map(|&index| payouts[index].amount).sum());
+        let mut report = CliDistributeTransaction {
+            signature: None,
+            recipients: indexes.len(),
+            amount,
+            status: String::new(),
+            error: None,
+        };
+
+        let (blockhash, last_valid_block_height) = rpc_client
+            .get_latest_blockhash_with_commitment(rpc_client.commitment())
+            .await?;
+        let prepared = async {
+            let budget = tx_args
+                .compute_budget(
+                    &instructions,
+                    &fee_payer.pubkey(),
+                    &lookup_tables,
+                    rpc_client,
+                )
+                .await?;
+            let mut transaction = build_transaction(
+                instructions,
+                &fee_payer.pubkey(),
+                None,
+                &budget,
+                &lookup_tables,
+            )?;
+            sign_transaction(&mut transaction, &[fee_payer, from], blockhash, tx_args)?;
+            let simulation = simulate_transaction(rpc_client, &transaction).await?;
+            Ok::<_, CliError>((transaction, simulation.fee))
+        }
+        .await;
+        let transaction = match prepared {
+            Ok((transaction, simulation_fee)) => {
+                fee += simulation_fee;
+                transaction
+            }
+            Err(err @ CliError::SimulationFailed { .. }) => {
+                let error = err.to_string();
+                ledger.set_status(&indexes, PayoutStatus::Failed, None, None, Some(&error));
+                report.status = PayoutStatus::Failed.as_str().to_string();
+                report.error = Some(error);
+                transactions.push(report);
+                continue;
+            }
+            Err(err) => return Err(save_before_error(&ledger, &ledger_path, tx_args, err)),
+        };
+        let signature = transaction.signatures[0];
+        if tx_args.dry_run {
+            report.status = "simulated".to_string();
+            transactions.push(report);
+            continue;
+        }
+
+        // 发送前先记下签名，崩溃后可据此核对该交易是否已经上链
+        ledger.set_status(
+            &indexes,
+            PayoutStatus::Sent,
+            Some(&signature),
+            Some(last_valid_block_height),
+            None,
+        );
+        ledger.save(&ledger_path)?;
+        report.signature = Some(signature.to_string());
+
+        match send_and_confirm(rpc_client, &transaction, tx_args).await {
+            Ok(_) => {
+                ledger.set_status(
+                    &indexes,
+                    PayoutStatus::Confirmed,
+                    Some(&signature),
+                    None,
+                    None,
+                );
+                report.status = PayoutStatus::Confirmed.as_str().to_string();
+            }
+            Err(err) => match err {
+                // 确定未上链：退回 pending，下次运行时重新发送
+                CliError::BlockhashExpired(_) => {
+                    ledger.set_status(&indexes, PayoutStatus::Pending, None, None, None);
+                    report.status = "expired".to_string();
+                }
+                err @ (CliError::TransactionFailed { .. }
+                | CliError::SimulationFailed { .. }
+                | CliError::InsufficientFunds(_)) => {
+                    let error = err.to_string();
+                    ledger.set_status(
+                        &indexes,
+                        PayoutStatus::Failed,
+                        Some(&signature),
+                        None,
+                        Some(&error),
+                    );
+                    report.status = PayoutStatus::Failed.as_str().to_string();
+                    report.error = Some(error);
+                }
+                // 交易状态未知：保留 sent 状态，下次运行时核对
+                err => return Err(save_before_error(&ledger, &ledger_path, tx_args, err)),
+            },
+        }
+        ledger.save(&ledger_path)?;
+        eprintln!(
+            "{} ({} recipients, {}): {}",
+            signature, report.recipients, report.amount, report.status
+        );
+        transactions.push(report);
+    }
+
+    Ok(reconcile(
+        &ledger,
+        &asset,
+        ledger_path,
+        tx_args.dry_run,
+        previously_confirmed,
+        fee,
+        transactions,
+    ))
+}
+
+/// 读取 `recipient,amount` 格式的 CSV，忽略空行、# 开头的注释以及表头
+fn read_payouts(path: &str, asset: &Asset) -> CliResult<Vec<Payout>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| CliError::InvalidInput(format!("读取 CSV 文件 {} 失败: {}", path, err)))?;
+    let mut payouts = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [recipient, amount] = fields.as_slice() else {
+            return Err(CliError::InvalidInput(format!(
+                "{} 第 {} 行应为 recipient,amount: {}",
+                path, line_number, line
+            )));
+        };
+        let Ok(recipient) = recipient.parse::<Pubkey>() else {
+            if payouts.is_empty() && recipient.eq_ignore_ascii_case("recipient") {
+                continue;
+            }
+            return Err(CliError::InvalidInput(format!(
+                "{} 第 {} 行不是有效的地址: {}",
+                path, line_number, recipient
+            )));
+        };
+        let amount = asset.parse_amount(amount).map_err(|err| {
+            CliError::InvalidInput(format!("{} 第 {} 行: {}", path, line_number, err))
+        })?;
+        payouts.push(Payout {
+            line: line_number,
+            recipient,
+            amount,
+        });
+    }
+    if payouts.is_empty() {
+        return Err(CliError::InvalidInput(format!(
+            "{} 中没有任何发放记录",
+            path
+        )));
+    }
+    Ok(payouts)
+}
+
+/// 核对账本中 sent 状态的交易：已上链的标记为 confirmed / failed，区块哈希过期仍未上链的改回 pending
+async fn resolve_sent_entries(ledger: &mut Ledger, rpc_client: &RpcClient) -> CliResult<()> {
+    loop {
+        let mut signatures: Vec<Signature> = Vec::new();
+        for entry in &ledger.entries {
+            if entry.status == PayoutStatus::Sent
+                && let Some(signature) = entry.signature.as_deref().and_then(|s| s.parse().ok())
+                && !signatures.contains(&signature)
+            {
+                signatures.push(signature);
+            }
+        }
+        if signatures.is_empty() {
+            return Ok(());
+        }
+
+        // 上次运行的交易可能早已移出节点的近期状态缓存，必须查询完整历史，
+        // 否则已上链的交易会被当作未上链而重复发放
+        let mut statuses = Vec::with_capacity(signatures.len());
+        for chunk in signatures.chunks(MAX_SIGNATURE_STATUSES) {
+            statuses.extend(
+                rpc_client
+                    .get_signature_statuses_with_history(chunk)
+                    .await?
+                    .value,
+            );
+        }
+        let block_height = rpc_client.get_block_height().await?;
+
+        let waiting = signatures
+            .iter()
+            .zip(statuses)
+            .filter(|(signature, status)| {
+                !apply_signature_status(
+                    ledger,
+                    signature,
+                    status.as_ref(),
+                    block_height,
+                    rpc_client.commitment(),
+                )
+            })
+            .count();
+        if waiting == 0 {
+            return Ok(());
+        }
+        eprintln!("等待上次运行中 {} 笔已发送交易确认或过期...", waiting);
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+/// 按签名的链上状态更新账本中对应的 sent 行；返回 false 表示交易既未确认也未过期，需要继续等待
+///
+/// `status` 必须来自查询完整历史的 getSignatureStatuses，只有历史中也找不到且区块哈希已过期时才能确定交易未上链
+fn apply_signature_status(
+    ledger: &mut Ledger,
+    signature: &Signature,
+    status: Option<&TransactionStatus>,
+    block_height: u64,
+    commitment: CommitmentConfig,
+) -> bool {
+    let signature_str = signature.to_string();
+    let indexes: Vec<usize> = ledger
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| {
+            entry.status == PayoutStatus::Sent
+                && entry.signature.as_deref() == Some(signature_str.as_str())
+        })
+        .map(|(index, _)| index)
+        .collect();
+    let last_valid_block_height = indexes
+        .first()
+        .and_then(|&index| ledger.entries[index].last_valid_block_height)
+        .unwrap_or_default();
+    match status {
+        Some(status) if status.err.is_some() => {
+            let error = status.err.as_ref().map(describe_transaction_error);
+            ledger.set_status(
+                &indexes,
+                PayoutStatus::Failed,
+                Some(signature),
+                None,
+                error.as_deref(),
+            );
+        }
+        Some(status) if status.satisfies_commitment(commitment) => {
+            ledger.set_status(
+                &indexes,
+                PayoutStatus::Confirmed,
+                Some(signature),
+                None,
+                None,
+            );
+        }
+        None if block_height > last_valid_block_height => {
+            eprintln!("交易 {} 的区块哈希已过期且未上链，将重新发送", signature);
+            ledger.set_status(&indexes, PayoutStatus::Pending, None, None, None);
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// 发放前确认转出账户余额足以覆盖剩余的发放总额
+async fn check_source_balance(
+    asset: &Asset,
+    from: &dyn Signer,
+    remaining: u64,
+    rpc_client: &RpcClient,
+) -> CliResult<()> {
+    if remaining == 0 {
+        return Ok(());
+    }
+    let (account, balance) = match asset {
+        Asset::Sol => (from.pubkey(), rpc_client.get_balance(&from.pubkey()).await?),
+        Asset::Token {
+            mint, program_id, ..
+        } => {
+            let source = associated_token_address(&from.pubkey(), mint, program_id);
+            let balance = get_token_balance(&source, &TransactionArgs::default(), rpc_client)
+                .await?
+                .unwrap_or_default();
+            (source, balance)
+        }
+    };
+    if balance < remaining {
+        return Err(CliError::InsufficientFunds(format!(
+            "账户 {} 需要 {}，当前余额 {}",
+            account,
+            asset.ui_amount(remaining),
+            asset.ui_amount(balance)
+        )));
+    }
+    Ok(())
+}
+
+/// 按交易大小与账户数上限以及 batch_size 把待发放的行打包成多笔交易
+#[allow(clippy::too_many_arguments)]
+fn pack_batches(
+    todo: &[usize],
+    payouts: &[Payout],
+    asset: &Asset,
+    fee_payer: &dyn Signer,
+    from: &dyn Signer,
+    batch_size: usize,
+    fund_recipient: bool,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> CliResult<Vec<(Vec<usize>, Vec<Instruction>)>> {
+    let mut batches = Vec::new();
+    let mut indexes: Vec<usize> = Vec::new();
+    let mut instructions: Vec<Instruction> = Vec::new();
+    for &index in todo {
+        let payout_instructions =
+            payout_instructions(&payouts[index], asset, fee_payer, from, fund_recipient)?;
+        let mut candidate = instructions.clone();
+        candidate.extend(payout_instructions.iter().cloned());
+        if !indexes.is_empty()
+            && (indexes.len() >= batch_size
+                || !fits_in_transaction(&candidate, fee_payer, lookup_tables)?)
+        {
+            batches.push((
+                std::mem::take(&mut indexes),
+                std::mem::take(&mut instructions),
+            ));
+            candidate = payout_instructions;
+        }
+        if !fits_in_transaction(&candidate, fee_payer, lookup_tables)? {
+            return Err(CliError::InvalidInput(format!(
+                "第 {} 行的转账无法放入单笔交易",
+                payouts[index].line
+            )));
+        }
+        indexes.push(index);
+        instructions = candidate;
+    }
+    if !indexes.is_empty() {
+        batches.push((indexes, instructions));
+    }
+    Ok(batches)
+}
+
+fn payout_instructions(
+    payout: &Payout,
+    asset: &Asset,
+    fee_payer: &dyn Signer,
+    from: &dyn Signer,
+    fund_recipient: bool,
+) -> CliResult<Vec<Instruction>> {
+    match asset {
+        Asset::Sol => Ok(vec![system_instruction::transfer(
+            &from.pubkey(),
+            &payout.recipient,
+            payout.amount,
+        )]),
+        Asset::Token {
+            mint,
+            program_id,
+            decimals,
+        } => {
+            let source = associated_token_address(&from.pubkey(), mint, program_id);
+            let destination = associated_token_address(&payout.recipient, mint, program_id);
+            let mut instructions = Vec::new();
+            if fund_recipient {
+                instructions.push(create_associated_token_account_instruction(
+                    &fee_payer.pubkey(),
+                    &payout.recipient,
+                    mint,
+                    program_id,
+                ));
+            }
+            instructions.push(checked_instruction(instruction::transfer_checked(
+                &spl_pubkey(program_id),
+                &spl_pubkey(&source),
+                &spl_pubkey(mint),
+                &spl_pubkey(&destination),
+                &spl_pubkey(&from.pubkey()),
+                &[],
+                payout.amount,
+                *decimals,
+            ))?);
+            Ok(instructions)
+        }
+    }
+}
+
+/// 交易 (含签名占位，并为计算预算指令预留空间) 的序列化大小与引用的账户数是否都在上限之内
+fn fits_in_transaction(
+    instructions: &[Instruction],
+    fee_payer: &dyn Signer,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> CliResult<bool> {
+    let reserved_budget = ComputeBudget {
+        unit_limit: Some(MAX_COMPUTE_UNIT_LIMIT),
+        unit_price: Some(1),
+    };
+    let transaction = build_transaction(
+        instructions.to_vec(),
+        &fee_payer.pubkey(),
+        None,
+        &reserved_budget,
+        lookup_tables,
+    )?;
+    let size = bincode::serialized_size(&transaction)
+        .map_err(|err| CliError::Other(anyhow!("序列化交易失败: {}", err)))?;
+    let loaded_accounts: usize = transaction
+        .message
+        .address_table_lookups()
+        .unwrap_or_default()
+        .iter()
+        .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+        .sum();
+    let account_count = transaction.message.static_account_keys().len() + loaded_accounts;
+    Ok(size as usize <= PACKET_DATA_SIZE && account_count <= MAX_TX_ACCOUNT_LOCKS)
+}
+
+/// 交易状态未知时先保存账本，并提示重新运行即可继续
+fn save_before_error(
+    ledger: &Ledger,
+    ledger_path: &str,
+    tx_args: &TransactionArgs,
+    err: CliError,
+) -> CliError {
+    if !tx_args.dry_run {
+        if let Err(save_err) = ledger.save(ledger_path) {
+            return save_err;
+        }
+        eprintln!(
+            "进度已记录在 {}，重新运行相同的命令即可继续 (不会重复发放)",
+            ledger_path
+        );
+    }
+    err
+}
+
+/// 汇总账本生成对账报告
+fn reconcile(
+    ledger: &Ledger,
+    asset: &Asset,
+    ledger_path: String,
+    dry_run: bool,
+    previously_confirmed: usize,
+    fee: u64,
+    transactions: Vec<CliDistributeTransaction>,
+) -> CliDistribute {
+    let count = |status: PayoutStatus| {
+        ledger
+            .entries
+            .iter()
+            .filter(|entry| entry.status == status)
+            .count()
+    };
+    let sum = |confirmed: bool| -> u64 {
+        ledger
+            .entries
+            .iter()
+            .filter(|entry| (entry.status == PayoutStatus::Confirmed) == confirmed)
+            .map(|entry| entry.amount)
+            .sum()
+    };
+    let total: u64 = ledger.entries.iter().map(|entry| entry.amount).sum();
+
+    CliDistribute {
+        asset: asset.label(),
+        ledger: ledger_path,
+        dry_run,
+        recipients: ledger.entries.len(),
+        total_amount: asset.ui_amount(total),
+        confirmed: count(PayoutStatus::Confirmed),
+        confirmed_amount: asset.ui_amount(sum(true)),
+        previously_confirmed,
+        failed: count(PayoutStatus::Failed),
+        pending: count(PayoutStatus::Pending) + count(PayoutStatus::Sent),
+        remaining_amount: asset.ui_amount(sum(false)),
+        fee,
+        transactions,
+        unpaid: ledger
+            .entries
+            .iter()
+            .filter(|entry| entry.status != PayoutStatus::Confirmed)
+            .map(|entry| CliDistributeEntry {
+                line: entry.line,
+                recipient: entry.recipient.clone(),
+                amount: asset.ui_amount(entry.amount),
+                status: entry.status.as_str().to_string(),
+                signature: entry.signature.clone(),
+                error: entry.error.clone(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::transaction::TransactionError;
+    use solana_transaction_status_client_types::TransactionConfirmationStatus;
+
+    use super::*;
+
+    /// 在临时目录写入测试文件，返回其路径
+    fn write_temp(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!(
+            "solana-cli-distribute-{}-{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn payout(line: usize, amount: u64) -> Payout {
+        Payout {
+            line,
+            recipient: Pubkey::new_unique(),
+            amount,
+        }
+    }
+
+    fn sent_ledger(signature: &Signature, last_valid_block_height: u64) -> Ledger {
+        let mut ledger = Ledger::new(
+            &Asset::Sol,
+            &Pubkey::new_unique(),
+            &[payout(1, 1), payout(2, 2), payout(3, 3)],
+        );
+        ledger.set_status(
+            &[0, 1],
+            PayoutStatus::Sent,
+            Some(signature),
+            Some(last_valid_block_height),
+            None,
+        );
+        ledger
+    }
+
+    fn status(
+        err: Option<TransactionError>,
+        confirmation_status: TransactionConfirmationStatus,
+    ) -> TransactionStatus {
+        TransactionStatus {
+            slot: 1,
+            confirmations: None,
+            status: err.clone().map_or(Ok(()), Err),
+            err,
+            confirmation_status: Some(confirmation_status),
+        }
+    }
+
+    #[test]
+    fn read_payouts_skips_header_comments_and_blank_lines() {
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let path = write_temp(
+            "header.csv",
+            &format!(
+                "Recipient,Amount\n# 第一批\n\n{},1.5\n  {} , 0.000000001 \n",
+                first, second
+            ),
+        );
+        let payouts = read_payouts(&path, &Asset::Sol).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(payouts.len(), 2);
+        assert_eq!(
+            (payouts[0].line, payouts[0].recipient, payouts[0].amount),
+            (4, first, 1_500_000_000)
+        );
+        assert_eq!(
+            (payouts[1].line, payouts[1].recipient, payouts[1].amount),
+            (5, second, 1)
+        );
+    }
+
+    #[test]
+    fn read_payouts_rejects_malformed_lines() {
+        let recipient = Pubkey::new_unique();
+        let cases = [
+            ("fields.csv", format!("{},1,extra\n", recipient)),
+            ("address.csv", "not-an-address,1\n".to_string()),
+            ("amount.csv", format!("{},abc\n", recipient)),
+            ("all.csv", format!("{},ALL\n", recipient)),
+            // 表头只能出现在第一条记录之前
+            (
+                "late-header.csv",
+                format!("{},1\nrecipient,amount\n", recipient),
+            ),
+            ("empty.csv", "recipient,amount\n# 无记录\n".to_string()),
+        ];
+        for (name, content) in cases {
+            let path = write_temp(name, &content);
+            let result = read_payouts(&path, &Asset::Sol);
+            std::fs::remove_file(&path).unwrap();
+            assert!(
+                matches!(result, Err(CliError::InvalidInput(_))),
+                "{}: {}",
+                name,
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn ledger_load_rejects_ledger_from_different_input() {
+        let source = Pubkey::new_unique();
+        let payouts = [payout(1, 10), payout(2, 20)];
+        let path = write_temp("ledger.json", "");
+        Ledger::new(&Asset::Sol, &source, &payouts)
+            .save(&path)
+            .unwrap();
+
+        assert!(Ledger::load(&path, &Asset::Sol, &source, &payouts).is_ok());
+
+        let changed_amount = [
+            Payout { ..payouts[0] },
+            Payout {
+                amount: 21,
+                ..payouts[1]
+            },
+        ];
+        let changed_recipient = [Payout { ..payouts[0] }, payout(2, 20)];
+        let token = Asset::Token {
+            mint: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            decimals: 6,
+        };
+        let results = [
+            Ledger::load(&path, &Asset::Sol, &source, &changed_amount),
+            Ledger::load(&path, &Asset::Sol, &source, &changed_recipient),
+            Ledger::load(&path, &Asset::Sol, &source, &payouts[..1]),
+            Ledger::load(&path, &Asset::Sol, &Pubkey::new_unique(), &payouts),
+            Ledger::load(&path, &token, &source, &payouts),
+        ];
+        std::fs::remove_file(&path).unwrap();
+        for result in results {
+            assert!(matches!(result, Err(CliError::InvalidInput(_))));
+        }
+    }
+
+    #[test]
+    fn confirmed_signature_marks_sent_entries_confirmed() {
+        let signature = Signature::new_unique();
+        let mut ledger = sent_ledger(&signature, 100);
+        let status = status(None, TransactionConfirmationStatus::Finalized);
+
+        assert!(apply_signature_status(
+            &mut ledger,
+            &signature,
+            Some(&status),
+            50,
+            CommitmentConfig::confirmed(),
+        ));
+        for entry in &ledger.entries[..2] {
+            assert_eq!(entry.status, PayoutStatus::Confirmed);
+            assert_eq!(entry.signature, Some(signature.to_string()));
+        }
+        assert_eq!(ledger.entries[2].status, PayoutStatus::Pending);
+    }
+
+    #[test]
+    fn failed_signature_marks_sent_entries_failed() {
+        let signature = Signature::new_unique();
+        let mut ledger = sent_ledger(&signature, 100);
+        let status = status(
+            Some(TransactionError::InsufficientFundsForFee),
+            TransactionConfirmationStatus::Processed,
+        );
+
+        assert!(apply_signature_status(
+            &mut ledger,
+            &signature,
+            Some(&status),
+            50,
+            CommitmentConfig::confirmed(),
+        ));
+        for entry in &ledger.entries[..2] {
+            assert_eq!(entry.status, PayoutStatus::Failed);
+            assert!(entry.error.is_some());
+        }
+    }
+
+    #[test]
+    fn expired_unlanded_signature_returns_entries_to_pending() {
+        let signature = Signature::new_unique();
+        let mut ledger = sent_ledger(&signature, 100);
+
+        // 完整历史中也没有该签名，且区块哈希已过期：交易不可能再上链，可以安全重发
+        assert!(apply_signature_status(
+            &mut ledger,
+            &signature,
+            None,
+            101,
+            CommitmentConfig::confirmed(),
+        ));
+        for entry in &ledger.entries[..2] {
+            assert_eq!(entry.status, PayoutStatus::Pending);
+            assert_eq!(entry.signature, None);
+            assert_eq!(entry.last_valid_block_height, None);
+        }
+    }
+
+    #[test]
+    fn old_landed_signature_stays_confirmed() {
+        let signature = Signature::new_unique();
+        let mut ledger = sent_ledger(&signature, 100);
+        // 早已上链并最终确认：近期状态缓存中已查不到，只有历史查询能返回状态
+        let status = status(None, TransactionConfirmationStatus::Finalized);
+
+        assert!(apply_signature_status(
+            &mut ledger,
+            &signature,
+            Some(&status),
+            1_000_000,
+            CommitmentConfig::confirmed(),
+        ));
+        for entry in &ledger.entries[..2] {
+            assert_eq!(entry.status, PayoutStatus::Confirmed);
+            assert_eq!(entry.signature, Some(signature.to_string()));
+        }
+    }
+
+    #[test]
+    fn unconfirmed_or_unexpired_signature_keeps_waiting() {
+        let signature = Signature::new_unique();
+        let mut ledger = sent_ledger(&signature, 100);
+        let processed = status(None, TransactionConfirmationStatus::Processed);
+
+        // 未上链但区块哈希仍有效：交易可能还会被处理，不能重发
+        assert!(!apply_signature_status(
+            &mut ledger,
+            &signature,
+            None,
+            100,
+            CommitmentConfig::confirmed(),
+        ));
+        // 已上链但尚未达到要求的确认级别
+        assert!(!apply_signature_status(
+            &mut ledger,
+            &signature,
+            Some(&processed),
+            101,
+            CommitmentConfig::confirmed(),
+        ));
+        for entry in &ledger.entries[..2] {
+            assert_eq!(entry.status, PayoutStatus::Sent);
+            assert_eq!(entry.signature, Some(signature.to_string()));
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::anyhow;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::command::confirm::{get_transaction, transaction_commitment};
+use crate::error::{CliError, CliResult, describe_transaction_error};
+use crate::output::{CliConfirmedTransaction, CliHistory, CliHistoryEntry, CliHistorySummary};
+use crate::transaction::confirmation_status_str;
+
+/// getSignaturesForAddress 单次最多返回的签名数
+const MAX_SIGNATURES_PER_PAGE: usize = 1000;
+
+/// 列出地址的历史交易签名，从新到旧自动分页直到 `limit` 条
+///
+/// `summary` 时逐笔获取交易，汇总该地址的 SOL / 代币余额变化与调用的指令；
+/// `export` 按扩展名 (.csv / .json) 将结果写入文件。
+pub async fn history(
+    address: &Pubkey,
+    before: Option<Signature>,
+    until: Option<Signature>,
+    limit: usize,
+    summary: bool,
+    export: Option<&str>,
+    rpc_client: &RpcClient,
+) -> CliResult<CliHistory> {
+    let export_format = export.map(ExportFormat::from_path).transpose()?;
+
+    let mut entries = Vec::new();
+    let mut before = before;
+    while entries.len() < limit {
+        let page_limit = (limit - entries.len()).min(MAX_SIGNATURES_PER_PAGE);
+        let page = rpc_client
+            .get_signatures_for_address_with_config(
+                address,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until,
+                    limit: Some(page_limit),
+                    commitment: Some(transaction_commitment(rpc_client)),
+                },
+            )
+            .await?;
+        let last_page = page.len() < page_limit;
+        for status in page {
+            let signature = status.signature.parse::<Signature>().map_err(|_| {
+                CliError::Other(anyhow!("RPC 返回了无效的签名: {}", status.signature))
+            })?;
+            before = Some(signature);
+            entries.push(CliHistoryEntry {
+                signature: status.signature,
+                slot: status.slot,
+                block_time: status.block_time,
+                confirmation_status: status
+                    .confirmation_status
+                    .as_ref()
+                    .map(|status| confirmation_status_str(status).to_string()),
+                error: status
+                    .err
+                    .map(|err| describe_transaction_error(&err.into())),
+                memo: status.memo,
+                summary: None,
+            });
+        }
+        if last_page {
+            break;
+        }
+    }
+
+    if summary {
+        for entry in &mut entries {
+            let signature = entry.signature.parse::<Signature>().unwrap_or_default();
+            entry.summary = get_transaction(&signature, rpc_client)
+                .await?
+                .map(|transaction| summarize(address, &transaction));
+        }
+    }
+
+    // 数量达到上限时可能还有更早的交易，提示继续翻页的起点
+    let next_before = (entries.len() == limit)
+        .then(|| entries.last().map(|entry| entry.signature.clone()))
+        .flatten();
+    let mut history = CliHistory {
+        address: address.to_string(),
+        entries,
+        next_before,
+        export_file: None,
+    };
+    if let (Some(path), Some(format)) = (export, export_format) {
+        let content = match format {
+            ExportFormat::Csv => history_csv(&history),
+            ExportFormat::Json => serde_json::to_string_pretty(&history.entries)
+                .map_err(|err| CliError::Other(anyhow!("序列化交易历史失败: {}", err)))?,
+        };
+        std::fs::write(path, content)
+            .map_err(|err| CliError::Other(anyhow!("写入 {} 失败: {}", path, err)))?;
+        history.export_file = Some(path.to_string());
+    }
+    Ok(history)
+}
+
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    fn from_path(path: &str) -> CliResult<Self> {
+        let extension = std::path::Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("csv") => Ok(ExportFormat::Csv),
+            Some("json") => Ok(ExportFormat::Json),
+            _ => Err(CliError::InvalidInput(format!(
+                "无法根据扩展名确定导出格式 (支持 .csv / .json): {}",
+                path
+            ))),
+        }
+    }
+}
+
+/// 从地址的角度汇总一笔交易：SOL 与代币余额变化，以及调用的顶层指令
+fn summarize(address: &Pubkey, transaction: &CliConfirmedTransaction) -> CliHistorySummary {
+    let address = address.to_string();
+    let lamports_change = transaction
+        .balance_changes
+        .iter()
+        .find(|change| change.address == address)
+        .map_or(0, |change| change.delta);
+    let token_changes = transaction
+        .token_balance_changes
+        .iter()
+        .filter(|change| change.account == address || change.owner.as_ref() == Some(&address))
+        .map(|change| format!("{} {}", change.delta, change.mint))
+        .collect();
+    let mut instructions: Vec<String> = Vec::new();
+    for instruction in &transaction.instructions {
+        let program = instruction
+            .program
+            .as_ref()
+            .unwrap_or(&instruction.program_id);
+        let name = match &instruction.parsed {
+            Some(parsed) => format!("{}: {}", program, parsed.name),
+            None => program.clone(),
+        };
+        if !instructions.contains(&name) {
+            instructions.push(name);
+        }
+    }
+    CliHistorySummary {
+        fee: transaction.fee,
+        lamports_change,
+        token_changes,
+        instructions,
+    }
+}
+
+fn history_csv(history: &CliHistory) -> String {
+    let mut csv = String::from(
+        "signature,slot,block_time,status,error,memo,fee,sol_change,token_changes,instructions\n",
+    );
+    for entry in &history.entries {
+        let summary = entry.summary.as_ref();
+        let fields = [
+            entry.signature.clone(),
+            entry.slot.to_string(),
+            entry
+                .block_time
+                .map(|time| time.to_string())
+                .unwrap_or_default(),
+            entry.status().to_string(),
+            entry.error.clone().unwrap_or_default(),
+            entry.memo.clone().unwrap_or_default(),
+            summary.map(|s| s.fee.to_string()).unwrap_or_default(),
+            summary.map(|s| s.sol_change()).unwrap_or_default(),
+            summary
+                .map(|s| s.token_changes.join("; "))
+                .unwrap_or_default(),
+            summary
+                .map(|s| s.instructions.join("; "))
+                .unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// 含逗号、引号或换行的字段按 RFC 4180 加引号转义
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
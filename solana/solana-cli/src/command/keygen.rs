@@ -0,0 +1,313 @@
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Instant;
+
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use clap::{Args, Subcommand};
+use solana_clap_utils::keypair::prompt_passphrase;
+use solana_derivation_path::DerivationPath;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use solana_sdk::signer::keypair::{
+    Keypair, keypair_from_seed, keypair_from_seed_and_derivation_path,
+};
+
+use crate::error::{CliError, CliResult};
+use crate::output::{CliGrind, CliGrindKeypair, CliKeypair, CliKeypairVerification};
+use crate::signer::{
+    GrindPattern, grind_keypairs, keypair_from_source, save_keypair_file, signer_from_source,
+};
+
+/// verify 时签名的固定消息
+const VERIFY_MESSAGE: &[u8] = b"solana-cli keygen verify";
+
+/// keypair 管理
+#[derive(Subcommand)]
+pub enum KeygenCommand {
+    /// 生成新的 keypair，默认写入 --keypair 指定的路径 (或配置文件中的 keypair_path)
+    New {
+        #[command(flatten)]
+        outfile: OutfileArgs,
+        /// 不写入文件，直接输出 keypair (JSON 字节数组)
+        #[arg(long = "no-outfile", conflicts_with_all = ["outfile", "force"])]
+        no_outfile: bool,
+        /// 由 BIP39 助记词派生 keypair，并输出助记词以便恢复
+        #[arg(long)]
+        mnemonic: bool,
+        /// 助记词的单词数 [12, 15, 18, 21, 24]
+        #[arg(
+            long = "word-count",
+            value_name = "N",
+            default_value_t = 12,
+            requires = "mnemonic"
+        )]
+        word_count: usize,
+        /// 不设置 BIP39 口令 (默认在交互式终端中提示输入，非交互环境下不设置口令)
+        #[arg(long = "no-bip39-passphrase", requires = "mnemonic")]
+        no_passphrase: bool,
+        /// 按派生路径由助记词派生 keypair，不带值时为 m/44'/501'/0'/0'；不指定时与 solana-keygen 相同，直接使用种子
+        #[arg(
+            long = "derivation-path",
+            value_name = "PATH",
+            num_args = 0..=1,
+            default_missing_value = "m/44'/501'/0'/0'",
+            requires = "mnemonic"
+        )]
+        derivation_path: Option<String>,
+    },
+    /// 输出签名者的公钥
+    Pubkey {
+        /// 签名者 [keypair 文件 | stdin | prompt:[//?key=0/0] | ASK]，默认使用 --keypair
+        #[arg(value_name = "SIGNER")]
+        signer: Option<String>,
+    },
+    /// 从助记词恢复 keypair 并写入文件
+    Recover {
+        /// 助记词来源 [prompt:[//?key=0/0] | ASK | stdin]
+        #[arg(value_name = "SOURCE", default_value = "prompt:")]
+        source: String,
+        #[command(flatten)]
+        outfile: OutfileArgs,
+    },
+    /// 检查 keypair 能否为指定公钥签名
+    Verify {
+        /// 期望的公钥
+        pubkey: Pubkey,
+        /// 待检查的签名者，语法同 pubkey，默认使用 --keypair
+        #[arg(value_name = "SIGNER")]
+        signer: Option<String>,
+    },
+    /// 多线程搜索以指定前缀开头 / 后缀结尾的靓号地址，结果写入当前目录下的 <PUBKEY>.json
+    Grind {
+        /// 以 PREFIX 开头，COUNT 为需要的数量 (默认 1)，可重复指定
+        #[arg(long = "starts-with", value_name = "PREFIX[:COUNT]")]
+        starts_with: Vec<String>,
+        /// 以 SUFFIX 结尾，可重复指定
+        #[arg(long = "ends-with", value_name = "SUFFIX[:COUNT]")]
+        ends_with: Vec<String>,
+        /// 同时以 PREFIX 开头、以 SUFFIX 结尾，可重复指定
+        #[arg(long = "starts-and-ends-with", value_name = "PREFIX:SUFFIX[:COUNT]")]
+        starts_and_ends_with: Vec<String>,
+        /// 匹配时忽略大小写 (更快，但地址的大小写不一定与条件一致)
+        #[arg(long = "ignore-case")]
+        ignore_case: bool,
+        /// 搜索线程数，默认为 CPU 核数
+        #[arg(long = "num-threads", value_name = "N")]
+        num_threads: Option<usize>,
+    },
+}
+
+#[derive(Args)]
+pub struct OutfileArgs {
+    /// keypair 的保存路径，默认为 --keypair 指定的路径 (或配置文件中的 keypair_path)
+    #[arg(short = 'o', long, value_name = "FILEPATH")]
+    pub outfile: Option<String>,
+    /// 覆盖已存在的文件 (旧的私钥将无法找回)
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl OutfileArgs {
+    /// 确定保存路径，文件已存在且未指定 --force 时报错
+    pub fn resolve(&self, default_path: &str) -> CliResult<String> {
+        let path = self.outfile.as_deref().unwrap_or(default_path);
+        if !self.force && Path::new(path).exists() {
+            return Err(CliError::InvalidInput(format!(
+                "{} 已存在，使用 --force 覆盖 (旧的私钥将无法找回)",
+                path
+            )));
+        }
+        Ok(path.to_string())
+    }
+}
+
+/// 生成新的 keypair；`outfile` 为 None 时不落盘，改为在输出中给出 keypair
+pub fn new_keypair(
+    outfile: Option<String>,
+    mnemonic: bool,
+    word_count: usize,
+    no_passphrase: bool,
+    derivation_path: Option<&str>,
+) -> CliResult<CliKeypair> {
+    let (keypair, phrase, passphrase) = if mnemonic {
+        let mnemonic_type = MnemonicType::for_word_count(word_count).map_err(|err| {
+            CliError::InvalidInput(format!("无效的单词数 {}: {}", word_count, err))
+        })?;
+        let mnemonic = Mnemonic::new(mnemonic_type, Language::English);
+        let passphrase = if no_passphrase || !io::stdin().is_terminal() {
+            String::new()
+        } else {
+            prompt_passphrase("BIP39 Passphrase (empty for none): ")
+                .map_err(|err| CliError::InvalidInput(format!("读取 BIP39 口令失败: {}", err)))?
+        };
+        let seed = Seed::new(&mnemonic, &passphrase);
+        let keypair = match derivation_path {
+            Some(path) => {
+                let path = DerivationPath::from_absolute_path_str(path).map_err(|err| {
+                    CliError::InvalidInput(format!("无效的派生路径 {}: {}", path, err))
+                })?;
+                keypair_from_seed_and_derivation_path(seed.as_bytes(), Some(path))
+            }
+            None => keypair_from_seed(seed.as_bytes()),
+        }
+        .map_err(|err| CliError::Other(anyhow::anyhow!("助记词派生 keypair 失败: {}", err)))?;
+        (
+            keypair,
+            Some(mnemonic.phrase().to_string()),
+            !passphrase.is_empty(),
+        )
+    } else {
+        (Keypair::new(), None, false)
+    };
+
+    if let Some(path) = &outfile {
+        save_keypair_file(&keypair, path)?;
+    }
+    Ok(CliKeypair {
+        pubkey: keypair.pubkey().to_string(),
+        keypair: outfile
+            .is_none()
+            .then(|| serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap_or_default()),
+        outfile,
+        mnemonic: phrase,
+        derivation_path: derivation_path.map(str::to_string),
+        has_passphrase: passphrase,
+    })
+}
+
+/// 从助记词 (或 stdin 中的 keypair) 恢复 keypair 并写入 `outfile`
+pub fn recover(source: &str, outfile: String) -> CliResult<CliKeypair> {
+    let keypair = keypair_from_source(source, "recover")?;
+    save_keypair_file(&keypair, &outfile)?;
+    Ok(CliKeypair {
+        pubkey: keypair.pubkey().to_string(),
+        keypair: None,
+        outfile: Some(outfile),
+        mnemonic: None,
+        derivation_path: None,
+        has_passphrase: false,
+    })
+}
+
+/// 用签名者对固定消息签名，并用期望的公钥验证签名
+pub fn verify(pubkey: &Pubkey, signer_source: &str) -> CliResult<CliKeypairVerification> {
+    let signer = signer_from_source(signer_source, "keypair")?;
+    let verified = signer
+        .try_sign_message(VERIFY_MESSAGE)
+        .is_ok_and(|signature| signature.verify(pubkey.as_ref(), VERIFY_MESSAGE));
+    if !verified {
+        return Err(CliError::Signer(format!(
+            "{} 无法为公钥 {} 签名 (该签名者的公钥为 {})",
+            signer_source,
+            pubkey,
+            signer.pubkey()
+        )));
+    }
+    Ok(CliKeypairVerification {
+        pubkey: pubkey.to_string(),
+    })
+}
+
+/// 按条件搜索靓号地址，每找到一个立即写入当前目录下的 `<PUBKEY>.json`
+pub fn grind(
+    starts_with: &[String],
+    ends_with: &[String],
+    starts_and_ends_with: &[String],
+    ignore_case: bool,
+    num_threads: Option<usize>,
+) -> CliResult<CliGrind> {
+    let mut patterns = Vec::new();
+    for arg in starts_with {
+        let (prefix, count) = parse_grind_arg(arg, 1, "--starts-with")?;
+        patterns.push(GrindPattern {
+            prefix: prefix[0].to_string(),
+            suffix: String::new(),
+            count,
+        });
+    }
+    for arg in ends_with {
+        let (suffix, count) = parse_grind_arg(arg, 1, "--ends-with")?;
+        patterns.push(GrindPattern {
+            prefix: String::new(),
+            suffix: suffix[0].to_string(),
+            count,
+        });
+    }
+    for arg in starts_and_ends_with {
+        let (parts, count) = parse_grind_arg(arg, 2, "--starts-and-ends-with")?;
+        patterns.push(GrindPattern {
+            prefix: parts[0].to_string(),
+            suffix: parts[1].to_string(),
+            count,
+        });
+    }
+    if patterns.is_empty() {
+        return Err(CliError::InvalidInput(
+            "请至少指定一个 --starts-with、--ends-with 或 --starts-and-ends-with".to_string(),
+        ));
+    }
+
+    let threads =
+        num_threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, usize::from));
+    let describe =
+        |pattern: &GrindPattern| match (pattern.prefix.is_empty(), pattern.suffix.is_empty()) {
+            (false, true) => format!("starting with {}", pattern.prefix),
+            (true, false) => format!("ending with {}", pattern.suffix),
+            _ => format!(
+                "starting with {} and ending with {}",
+                pattern.prefix, pattern.suffix
+            ),
+        };
+    for pattern in &patterns {
+        eprintln!(
+            "Searching for {} address(es) {} with {} threads...",
+            pattern.count,
+            describe(pattern),
+            threads
+        );
+    }
+
+    let started = Instant::now();
+    let mut keypairs = Vec::new();
+    let attempts = grind_keypairs(&patterns, ignore_case, threads, |keypair, index| {
+        let pubkey = keypair.pubkey().to_string();
+        let outfile = format!("{}.json", pubkey);
+        save_keypair_file(&keypair, &outfile)?;
+        eprintln!("Wrote keypair to {}", outfile);
+        keypairs.push(CliGrindKeypair {
+            pubkey,
+            outfile,
+            pattern: describe(&patterns[index]),
+        });
+        Ok(())
+    })?;
+    Ok(CliGrind {
+        keypairs,
+        attempts,
+        elapsed_secs: started.elapsed().as_secs_f64(),
+    })
+}
+
+/// 解析 `A[:B]:COUNT` 形式的 grind 参数，`parts` 为冒号分隔的匹配串个数，COUNT 可省略 (默认 1)
+fn parse_grind_arg<'a>(arg: &'a str, parts: usize, flag: &str) -> CliResult<(Vec<&'a str>, usize)> {
+    let mut fields: Vec<&str> = arg.split(':').collect();
+    let count = if fields.len() == parts + 1 {
+        let count = fields.pop().unwrap_or_default();
+        usize::from_str(count)
+            .ok()
+            .filter(|count| *count > 0)
+            .ok_or_else(|| {
+                CliError::InvalidInput(format!("{} 的数量必须是正整数: {}", flag, arg))
+            })?
+    } else {
+        1
+    };
+    if fields.len() != parts || fields.iter().any(|field| field.is_empty()) {
+        return Err(CliError::InvalidInput(format!(
+            "无效的 {} 参数: {}",
+            flag, arg
+        )));
+    }
+    Ok((fields, count))
+}
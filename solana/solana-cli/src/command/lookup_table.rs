@@ -0,0 +1,413 @@
+use clap::Subcommand;
+use solana_account::Account;
+use solana_address_lookup_table_interface::instruction as lookup_table_instruction;
+use solana_address_lookup_table_interface::state::{
+    AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES, LookupTableMeta, estimate_last_valid_slot,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+
+use crate::error::{CliError, CliResult};
+use crate::output::{
+    CliLookupTable, CliLookupTableExtend, CliNewLookupTable, CliParsedAccount, CliTxResult,
+};
+use crate::parser::lookup_table::parse_lookup_table;
+use crate::transaction::{TransactionArgs, send_instructions};
+
+/// 单笔 extend 交易追加的地址数上限，保证交易不超过大小限制
+const MAX_ADDRESSES_PER_EXTEND: usize = 20;
+
+/// 地址查找表 (ALT) 管理
+#[derive(Subcommand)]
+pub enum LookupTableCommand {
+    /// 创建地址查找表，地址由授权者与 recent slot 派生
+    Create {
+        /// 查找表的授权者，默认为手续费支付者
+        #[arg(long, value_name = "PUBKEY")]
+        authority: Option<Pubkey>,
+        /// 派生地址使用的 slot，必须是最近 512 个 slot 之一；默认取最新的 finalized slot，--sign-only 时必须指定
+        #[arg(long = "recent-slot", value_name = "SLOT")]
+        recent_slot: Option<u64>,
+        /// 支付手续费与租金的签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 向查找表追加地址，已在表中的地址会被跳过，地址较多时拆分为多笔交易
+    Extend {
+        /// 查找表地址
+        lookup_table: Pubkey,
+        /// 追加的地址
+        #[arg(required = true, value_name = "ADDRESS")]
+        addresses: Vec<Pubkey>,
+        /// 查找表的授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        authority: Option<String>,
+        /// 支付手续费与新增租金的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 冻结查找表：移除授权者，此后不能再追加地址或关闭 (不可撤销)
+    Freeze {
+        /// 查找表地址
+        lookup_table: Pubkey,
+        /// 查找表的授权签名者，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        authority: Option<String>,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 停用查找表，约 513 个 slot 的冷却期后可以关闭
+    Deactivate {
+        /// 查找表地址
+        lookup_table: Pubkey,
+        /// 查找表的授权签名者，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        authority: Option<String>,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 关闭已完成停用的查找表并回收租金
+    Close {
+        /// 查找表地址
+        lookup_table: Pubkey,
+        /// 接收租金的账户，默认为手续费支付者
+        #[arg(long, value_name = "PUBKEY")]
+        recipient: Option<Pubkey>,
+        /// 查找表的授权签名者，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        authority: Option<String>,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 查看查找表的授权者、状态与地址列表
+    Show {
+        /// 查找表地址
+        lookup_table: Pubkey,
+    },
+}
+
+/// 创建查找表；`recent_slot` 为 None 时取最新的 finalized slot
+pub async fn create_lookup_table(
+    fee_payer: &dyn Signer,
+    authority: Option<Pubkey>,
+    recent_slot: Option<u64>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliNewLookupTable> {
+    let recent_slot = match recent_slot {
+        Some(slot) => slot,
+        None if tx_args.sign_only => {
+            return Err(CliError::InvalidInput(
+                "--sign-only 模式下无法查询 slot，请通过 --recent-slot 指定".to_string(),
+            ));
+        }
+        None => {
+            rpc_client
+                .get_slot_with_commitment(CommitmentConfig::finalized())
+                .await?
+        }
+    };
+    let authority = authority.unwrap_or_else(|| fee_payer.pubkey());
+    let (instruction, lookup_table) =
+        lookup_table_instruction::create_lookup_table(authority, fee_payer.pubkey(), recent_slot);
+    eprintln!(
+        "Creating lookup table {} (authority {}, recent slot {})",
+        lookup_table, authority, recent_slot
+    );
+    let result =
+        send_instructions(&[instruction], fee_payer, &[fee_payer], tx_args, rpc_client).await?;
+    Ok(CliNewLookupTable {
+        lookup_table: lookup_table.to_string(),
+        result,
+    })
+}
+
+/// 向查找表追加地址，每笔交易至多 MAX_ADDRESSES_PER_EXTEND 个
+///
+/// 联网时先读取查找表：校验授权者与状态，跳过已在表中的地址，并检查追加后不超过 256 个。
+pub async fn extend_lookup_table(
+    fee_payer: &dyn Signer,
+    lookup_table: &Pubkey,
+    addresses: &[Pubkey],
+    authority: &dyn Signer,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliLookupTableExtend> {
+    let mut new_addresses: Vec<Pubkey> = Vec::new();
+    for address in addresses {
+        if !new_addresses.contains(address) {
+            new_addresses.push(*address);
+        }
+    }
+
+    let mut skipped = Vec::new();
+    let mut existing = 0;
+    if !tx_args.sign_only {
+        let (meta, table_addresses) = get_lookup_table(rpc_client, lookup_table).await?;
+        check_authority(lookup_table, &meta, authority)?;
+        check_active(lookup_table, &meta)?;
+        existing = table_addresses.len();
+        new_addresses.retain(|address| {
+            let present = table_addresses.contains(address);
+            if present {
+                skipped.push(address.to_string());
+            }
+            !present
+        });
+    }
+    if existing + new_addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+        return Err(CliError::InvalidInput(format!(
+            "查找表 {} 已有 {} 个地址，追加 {} 个后超过上限 {}",
+            lookup_table,
+            existing,
+            new_addresses.len(),
+            LOOKUP_TABLE_MAX_ADDRESSES
+        )));
+    }
+    if tx_args.sign_only && new_addresses.len() > MAX_ADDRESSES_PER_EXTEND {
+        return Err(CliError::InvalidInput(format!(
+            "--sign-only 模式下每次最多追加 {} 个地址 (只能签名单笔交易)",
+            MAX_ADDRESSES_PER_EXTEND
+        )));
+    }
+
+    let chunks: Vec<&[Pubkey]> = new_addresses.chunks(MAX_ADDRESSES_PER_EXTEND).collect();
+    if !skipped.is_empty() {
+        eprintln!(
+            "Skipping {} address(es) already in the table",
+            skipped.len()
+        );
+    }
+    let mut transactions = Vec::with_capacity(chunks.len());
+    for (index, chunk) in chunks.iter().enumerate() {
+        eprintln!(
+            "Extending lookup table {} with {} address(es) ({}/{})",
+            lookup_table,
+            chunk.len(),
+            index + 1,
+            chunks.len()
+        );
+        let instruction = lookup_table_instruction::extend_lookup_table(
+            *lookup_table,
+            authority.pubkey(),
+            Some(fee_payer.pubkey()),
+            chunk.to_vec(),
+        );
+        transactions.push(
+            send_instructions(
+                &[instruction],
+                fee_payer,
+                &[fee_payer, authority],
+                tx_args,
+                rpc_client,
+            )
+            .await?,
+        );
+    }
+
+    Ok(CliLookupTableExtend {
+        lookup_table: lookup_table.to_string(),
+        added: new_addresses.iter().map(ToString::to_string).collect(),
+        skipped,
+        transactions,
+    })
+}
+
+/// 冻结查找表 (不能冻结空表)
+pub async fn freeze_lookup_table(
+    fee_payer: &dyn Signer,
+    lookup_table: &Pubkey,
+    authority: &dyn Signer,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    if !tx_args.sign_only {
+        let (meta, addresses) = get_lookup_table(rpc_client, lookup_table).await?;
+        check_authority(lookup_table, &meta, authority)?;
+        check_active(lookup_table, &meta)?;
+        if addresses.is_empty() {
+            return Err(CliError::InvalidInput(format!(
+                "查找表 {} 为空，不能冻结",
+                lookup_table
+            )));
+        }
+    }
+    let instruction =
+        lookup_table_instruction::freeze_lookup_table(*lookup_table, authority.pubkey());
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, authority],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 停用查找表
+pub async fn deactivate_lookup_table(
+    fee_payer: &dyn Signer,
+    lookup_table: &Pubkey,
+    authority: &dyn Signer,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    if !tx_args.sign_only {
+        let (meta, _) = get_lookup_table(rpc_client, lookup_table).await?;
+        check_authority(lookup_table, &meta, authority)?;
+        check_active(lookup_table, &meta)?;
+    }
+    let instruction =
+        lookup_table_instruction::deactivate_lookup_table(*lookup_table, authority.pubkey());
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, authority],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 关闭已完成停用的查找表，租金转入 `recipient`
+pub async fn close_lookup_table(
+    fee_payer: &dyn Signer,
+    lookup_table: &Pubkey,
+    recipient: &Pubkey,
+    authority: &dyn Signer,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    if !tx_args.sign_only {
+        let (meta, _) = get_lookup_table(rpc_client, lookup_table).await?;
+        check_authority(lookup_table, &meta, authority)?;
+        if meta.deactivation_slot == u64::MAX {
+            return Err(CliError::InvalidInput(format!(
+                "查找表 {} 尚未停用，请先执行 lookup-table deactivate",
+                lookup_table
+            )));
+        }
+        let slot = rpc_client.get_slot().await?;
+        let last_valid_slot = estimate_last_valid_slot(meta.deactivation_slot);
+        if slot <= last_valid_slot {
+            return Err(CliError::InvalidInput(format!(
+                "查找表 {} 仍在冷却期，约在 slot {} 之后可以关闭 (当前 slot {})",
+                lookup_table, last_valid_slot, slot
+            )));
+        }
+    }
+    let instruction =
+        lookup_table_instruction::close_lookup_table(*lookup_table, authority.pubkey(), *recipient);
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, authority],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 查看查找表，状态按当前 slot 与停用 slot 估算
+pub async fn show_lookup_table(
+    lookup_table: &Pubkey,
+    rpc_client: &RpcClient,
+) -> CliResult<CliLookupTable> {
+    let account = get_lookup_table_account(rpc_client, lookup_table).await?;
+    let Some(CliParsedAccount::LookupTable(table)) =
+        parse_lookup_table(&account.owner, &account.data)
+    else {
+        return Err(CliError::InvalidInput(format!(
+            "无法解析查找表 {}",
+            lookup_table
+        )));
+    };
+    let status = match table.deactivation_slot {
+        None => "active",
+        Some(deactivation_slot) => {
+            if rpc_client.get_slot().await? > estimate_last_valid_slot(deactivation_slot) {
+                "deactivated"
+            } else {
+                "deactivating"
+            }
+        }
+    };
+    Ok(CliLookupTable {
+        address: lookup_table.to_string(),
+        status: status.to_string(),
+        lamports: account.lamports,
+        table,
+    })
+}
+
+async fn get_lookup_table_account(rpc_client: &RpcClient, address: &Pubkey) -> CliResult<Account> {
+    let account = rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?
+        .value
+        .ok_or_else(|| CliError::InvalidInput(format!("查找表 {} 不存在", address)))?;
+    if account.owner != solana_sdk_ids::address_lookup_table::ID {
+        return Err(CliError::InvalidInput(format!(
+            "{} 不是地址查找表 (owner 为 {})",
+            address, account.owner
+        )));
+    }
+    Ok(account)
+}
+
+/// 读取查找表的元数据与地址列表
+async fn get_lookup_table(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> CliResult<(LookupTableMeta, Vec<Pubkey>)> {
+    let account = get_lookup_table_account(rpc_client, address).await?;
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|err| CliError::InvalidInput(format!("无法解析查找表 {}: {}", address, err)))?;
+    Ok((table.meta, table.addresses.to_vec()))
+}
+
+fn check_authority(
+    lookup_table: &Pubkey,
+    meta: &LookupTableMeta,
+    authority: &dyn Signer,
+) -> CliResult<()> {
+    match meta.authority {
+        None => Err(CliError::InvalidInput(format!(
+            "查找表 {} 已冻结，不能再修改",
+            lookup_table
+        ))),
+        Some(expected) if expected != authority.pubkey() => Err(CliError::Signer(format!(
+            "查找表 {} 的授权者为 {}，而不是 {}",
+            lookup_table,
+            expected,
+            authority.pubkey()
+        ))),
+        Some(_) => Ok(()),
+    }
+}
+
+fn check_active(lookup_table: &Pubkey, meta: &LookupTableMeta) -> CliResult<()> {
+    if meta.deactivation_slot == u64::MAX {
+        Ok(())
+    } else {
+        Err(CliError::InvalidInput(format!(
+            "查找表 {} 已停用 (slot {})",
+            lookup_table, meta.deactivation_slot
+        )))
+    }
+}
@@ -1,69 +1,340 @@
-use std::str::FromStr;
-
+use clap::Args;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_pubkey::Pubkey;
-use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::rent::Rent;
 use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
-use solana_sdk::transaction::Transaction;
 use solana_system_interface::instruction as system_instruction;
+use spl_token::solana_program::program_error::ProgramError;
 use spl_token::solana_program::program_pack::Pack;
-use spl_token::{ID as TOKEN_PROGRAM_ID, instruction::initialize_mint2, state::Mint};
+use spl_token::solana_program::pubkey::Pubkey as SplPubkey;
+use spl_token::{ID as TOKEN_PROGRAM_ID, state::Mint};
+use spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
+use spl_token_2022::extension::{
+    ExtensionType, interest_bearing_mint, metadata_pointer, transfer_fee,
+};
+use spl_token_2022::instruction::{
+    initialize_mint2, initialize_non_transferable_mint, initialize_permanent_delegate,
+};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+use crate::command::token::{checked_instruction, spl_pubkey, to_sdk_instruction};
+use crate::error::{CliError, CliResult};
+use crate::output::CliMintToken;
+use crate::parser::token::token_program_name;
+use crate::signer::{grind_keypair, write_new_keypair_file};
+use crate::transaction::{
+    DurableNonce, TransactionArgs, new_transaction, process_transaction, sign_transaction,
+};
+
+/// Token-2022 mint 的可选扩展，均需配合 --program-2022 使用
+#[derive(Args, Clone, Debug, Default)]
+pub struct MintExtensionArgs {
+    /// 在 Token-2022 程序下创建 mint (才能启用下列扩展)
+    #[arg(long = "program-2022")]
+    pub program_2022: bool,
+    /// 转账手续费率，单位为基点 (1 = 0.01%)，需同时指定 --transfer-fee-maximum-fee
+    #[arg(
+        long = "transfer-fee-basis-points",
+        value_name = "BPS",
+        requires_all = ["program_2022", "transfer_fee_maximum_fee"]
+    )]
+    pub transfer_fee_basis_points: Option<u16>,
+    /// 单笔转账手续费的上限，按 mint 精度解析 (如 1.5)
+    #[arg(
+        long = "transfer-fee-maximum-fee",
+        value_name = "AMOUNT",
+        requires = "transfer_fee_basis_points"
+    )]
+    pub transfer_fee_maximum_fee: Option<String>,
+    /// 年利率，单位为基点 (可为负)，启用 interest-bearing 扩展，利率权限为增发权限
+    #[arg(
+        long = "interest-rate",
+        value_name = "BPS",
+        allow_negative_numbers = true,
+        requires = "program_2022"
+    )]
+    pub interest_rate: Option<i16>,
+    /// 启用 metadata pointer 扩展，元数据存放在 mint 账户自身
+    #[arg(long = "enable-metadata", requires = "program_2022")]
+    pub enable_metadata: bool,
+    /// 代币名称，与 --symbol、--uri 一起在创建时写入链上元数据
+    #[arg(long, value_name = "NAME", requires_all = ["enable_metadata", "symbol", "uri"])]
+    pub name: Option<String>,
+    /// 代币符号
+    #[arg(long, value_name = "SYMBOL", requires = "name")]
+    pub symbol: Option<String>,
+    /// 指向链下元数据 (JSON) 的 URI
+    #[arg(long, value_name = "URI", requires = "name")]
+    pub uri: Option<String>,
+    /// 启用 non-transferable 扩展，代币只能增发和销毁，不能转让
+    #[arg(long = "enable-non-transferable", requires = "program_2022")]
+    pub enable_non_transferable: bool,
+    /// 启用 permanent delegate 扩展，该地址可转移或销毁任意代币账户中的此代币
+    #[arg(
+        long = "permanent-delegate",
+        value_name = "PUBKEY",
+        requires = "program_2022"
+    )]
+    pub permanent_delegate: Option<Pubkey>,
+}
+
+/// 生成新的 mint keypair (可按前缀 grind)，并写入 `outfile` (默认 `<MINT>.json`)
+///
+/// `write_file` 为 false 时 (如 --dry-run) 不落盘，返回的文件路径为 None。
+pub fn generate_mint_keypair(
+    grind_prefix: Option<&str>,
+    outfile: Option<String>,
+    write_file: bool,
+) -> CliResult<(Keypair, Option<String>)> {
+    let keypair = match grind_prefix {
+        Some(prefix) => {
+            eprintln!("Searching for a mint address starting with {}...", prefix);
+            grind_keypair(prefix)?
+        }
+        None => Keypair::new(),
+    };
+    if !write_file {
+        return Ok((keypair, None));
+    }
+
+    let outfile = outfile.unwrap_or_else(|| format!("{}.json", keypair.pubkey()));
+    write_new_keypair_file(&keypair, &outfile)?;
+    Ok((keypair, Some(outfile)))
+}
 
+/// 创建 mint 账户并初始化，`freeze_authority` 为 None 时该代币不可冻结
+///
+/// `keypair_file` 为本命令生成的 mint keypair 的保存路径，仅用于输出。
+#[allow(clippy::too_many_arguments)]
 pub async fn mint_token(
-    mint_account: &Keypair,
-    funding_account: &Keypair,
+    mint_account: &dyn Signer,
+    keypair_file: Option<String>,
+    funding_account: &dyn Signer,
+    decimals: u8,
+    mint_authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+    extensions: &MintExtensionArgs,
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
     rpc_client: &RpcClient,
-) -> anyhow::Result<()> {
-    let mint_account_len = Mint::LEN;
-    let mint_account_rent = rpc_client
-        .get_minimum_balance_for_rent_exemption(mint_account_len)
-        .await?;
-    let token_program_id = Pubkey::from_str(&TOKEN_PROGRAM_ID.to_string()).unwrap();
+) -> CliResult<CliMintToken> {
+    let token_program_id = if extensions.program_2022 {
+        spl_token_2022::ID
+    } else {
+        TOKEN_PROGRAM_ID
+    };
+    let mint = spl_pubkey(&mint_account.pubkey());
+    let (extension_types, extension_instructions) = extension_instructions(
+        &token_program_id,
+        &mint,
+        decimals,
+        mint_authority,
+        extensions,
+    )?;
+    let metadata = token_metadata(&mint, mint_authority, extensions)?;
+    if metadata.is_some() && *mint_authority != funding_account.pubkey() {
+        return Err(CliError::InvalidInput(
+            "写入链上元数据需要增发权限签名，--name 不能与其它地址的 --mint-authority 同时使用"
+                .to_string(),
+        ));
+    }
+
+    let mint_account_len = if extensions.program_2022 {
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extension_types)
+            .map_err(|err| CliError::InvalidInput(err.to_string()))?
+    } else {
+        Mint::LEN
+    };
+    // 元数据由 token 程序在初始化时追加到账户末尾 (realloc)，需预留对应的租金
+    let metadata_len = match &metadata {
+        Some(metadata) => metadata
+            .tlv_size_of()
+            .map_err(|err| CliError::InvalidInput(err.to_string()))?,
+        None => 0,
+    };
+    // 离线签名时无法访问 RPC，按默认租金参数 (与 mainnet-beta/devnet/testnet 一致) 计算
+    let mint_account_rent = if tx_args.sign_only {
+        Rent::default().minimum_balance(mint_account_len + metadata_len)
+    } else {
+        rpc_client
+            .get_minimum_balance_for_rent_exemption(mint_account_len + metadata_len)
+            .await?
+    };
     let create_mint_account_ix = system_instruction::create_account(
         &funding_account.pubkey(),
         &mint_account.pubkey(),
         mint_account_rent,
         mint_account_len as u64,
-        &token_program_id,
+        &token_program_id.to_bytes().into(),
     );
 
-    let initialize_mint_ix = initialize_mint2(
-        &TOKEN_PROGRAM_ID.to_bytes().into(),
-        &mint_account.pubkey().to_bytes().into(),
-        &mint_account.pubkey().to_bytes().into(),
-        Some(&mint_account.pubkey().to_bytes().into()),
-        9,
+    let freeze_authority = freeze_authority.map(spl_pubkey);
+    let initialize_mint_ix = checked_instruction(initialize_mint2(
+        &token_program_id,
+        &mint,
+        &spl_pubkey(mint_authority),
+        freeze_authority.as_ref(),
+        decimals,
+    ))?;
+
+    let mut instructions = vec![create_mint_account_ix];
+    // 扩展必须在 InitializeMint 之前初始化
+    instructions.extend(extension_instructions);
+    instructions.push(initialize_mint_ix);
+    if let Some(metadata) = metadata {
+        instructions.push(to_sdk_instruction(
+            spl_token_metadata_interface::instruction::initialize(
+                &token_program_id,
+                &mint,
+                &spl_pubkey(mint_authority),
+                &mint,
+                &spl_pubkey(mint_authority),
+                metadata.name,
+                metadata.symbol,
+                metadata.uri,
+            ),
+        ));
+    }
+
+    let mut transaction = new_transaction(
+        instructions,
+        &funding_account.pubkey(),
+        nonce,
+        tx_args,
+        rpc_client,
+    )
+    .await?;
+
+    let mut signers = vec![funding_account, mint_account];
+    if let Some(nonce) = nonce {
+        signers.push(nonce.authority);
+    }
+    sign_transaction(
+        &mut transaction,
+        &signers,
+        tx_args.blockhash(rpc_client, nonce).await?,
+        tx_args,
     )?;
 
-    let wrap_initialize_mint_ix = Instruction {
-        program_id: initialize_mint_ix.program_id.to_bytes().into(),
-        accounts: initialize_mint_ix
-            .accounts
-            .into_iter()
-            .map(|account| AccountMeta {
-                pubkey: account.pubkey.to_bytes().into(),
-                is_signer: account.is_signer,
-                is_writable: account.is_writable,
-            })
+    Ok(CliMintToken {
+        mint: mint_account.pubkey().to_string(),
+        program: token_program_name(&token_program_id.to_bytes().into())
+            .unwrap_or_default()
+            .to_string(),
+        decimals,
+        mint_authority: mint_authority.to_string(),
+        freeze_authority: freeze_authority.map(|authority| authority.to_string()),
+        extensions: extension_types
+            .iter()
+            .map(|extension_type| format!("{:?}", extension_type))
             .collect(),
-        data: initialize_mint_ix.data,
-    };
-
-    let mut transaction = Transaction::new_with_payer(
-        &[create_mint_account_ix, wrap_initialize_mint_ix],
-        Some(&funding_account.pubkey()),
-    );
+        keypair_file,
+        result: process_transaction(rpc_client, &transaction, tx_args).await?,
+    })
+}
 
-    transaction.sign(
-        &[&funding_account, &mint_account],
-        rpc_client.get_latest_blockhash().await?,
-    );
+/// 所选扩展的类型及其初始化指令，各扩展的权限均设为增发权限
+fn extension_instructions(
+    token_program_id: &SplPubkey,
+    mint: &SplPubkey,
+    decimals: u8,
+    mint_authority: &Pubkey,
+    extensions: &MintExtensionArgs,
+) -> CliResult<(Vec<ExtensionType>, Vec<Instruction>)> {
+    let authority = spl_pubkey(mint_authority);
+    let mut extension_types = Vec::new();
+    let mut instructions = Vec::new();
 
-    match rpc_client.send_and_confirm_transaction(&transaction).await {
-        Ok(signature) => println!("Transaction Signature: {}", signature),
-        Err(err) => eprintln!("Error sending transaction: {}", err),
+    if let (Some(basis_points), Some(maximum_fee)) = (
+        extensions.transfer_fee_basis_points,
+        &extensions.transfer_fee_maximum_fee,
+    ) {
+        if basis_points > MAX_FEE_BASIS_POINTS {
+            return Err(CliError::InvalidInput(format!(
+                "转账手续费率不能超过 {} 基点 (100%)",
+                MAX_FEE_BASIS_POINTS
+            )));
+        }
+        let maximum_fee = spl_token_2022::try_ui_amount_into_amount(maximum_fee.clone(), decimals)
+            .map_err(|_| {
+                CliError::InvalidInput(format!(
+                    "无效的手续费上限: {} (该代币最多支持 {} 位小数)",
+                    maximum_fee, decimals
+                ))
+            })?;
+        extension_types.push(ExtensionType::TransferFeeConfig);
+        instructions.push(checked_instruction(
+            transfer_fee::instruction::initialize_transfer_fee_config(
+                token_program_id,
+                mint,
+                Some(&authority),
+                Some(&authority),
+                basis_points,
+                maximum_fee,
+            ),
+        )?);
+    }
+    if let Some(rate) = extensions.interest_rate {
+        extension_types.push(ExtensionType::InterestBearingConfig);
+        instructions.push(checked_instruction(
+            interest_bearing_mint::instruction::initialize(
+                token_program_id,
+                mint,
+                Some(authority),
+                rate,
+            ),
+        )?);
+    }
+    if extensions.enable_metadata {
+        extension_types.push(ExtensionType::MetadataPointer);
+        instructions.push(checked_instruction(
+            metadata_pointer::instruction::initialize(
+                token_program_id,
+                mint,
+                Some(authority),
+                Some(*mint),
+            ),
+        )?);
     }
+    if extensions.enable_non_transferable {
+        extension_types.push(ExtensionType::NonTransferable);
+        instructions.push(checked_instruction(initialize_non_transferable_mint(
+            token_program_id,
+            mint,
+        ))?);
+    }
+    if let Some(delegate) = &extensions.permanent_delegate {
+        extension_types.push(ExtensionType::PermanentDelegate);
+        instructions.push(checked_instruction(initialize_permanent_delegate(
+            token_program_id,
+            mint,
+            &spl_pubkey(delegate),
+        ))?);
+    }
+    Ok((extension_types, instructions))
+}
 
-    Ok(())
+/// 由 --name/--symbol/--uri 组成的链上元数据，更新权限为增发权限
+fn token_metadata(
+    mint: &SplPubkey,
+    mint_authority: &Pubkey,
+    extensions: &MintExtensionArgs,
+) -> CliResult<Option<TokenMetadata>> {
+    let (Some(name), Some(symbol), Some(uri)) =
+        (&extensions.name, &extensions.symbol, &extensions.uri)
+    else {
+        return Ok(None);
+    };
+    Ok(Some(TokenMetadata {
+        update_authority: Some(spl_pubkey(mint_authority))
+            .try_into()
+            .map_err(|err: ProgramError| CliError::InvalidInput(err.to_string()))?,
+        mint: *mint,
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: Vec::new(),
+    }))
 }
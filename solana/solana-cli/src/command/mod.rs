@@ -1,15 +1,65 @@
 use clap::{Parser, Subcommand};
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::command::address::{DerivationArgs, Seed, parse_seed};
+use crate::command::cluster::ValidatorSort;
+use crate::command::keygen::KeygenCommand;
+use crate::command::lookup_table::LookupTableCommand;
+use crate::command::mint_token::MintExtensionArgs;
+use crate::command::program::ProgramCommand;
+use crate::command::stake::LockupArgs;
+use crate::command::token::MintArgs;
+use crate::command::watch::WatchCommand;
+use crate::output::{CliAccountEncoding, OutputFormat};
+use crate::transaction::{NonceArgs, TransactionArgs};
 
 pub mod accountinfo;
+pub mod address;
 pub mod balance;
+pub mod cluster;
+pub mod confirm;
+pub mod distribute;
+pub mod history;
+pub mod keygen;
+pub mod lookup_table;
 pub mod mint_token;
+pub mod nonce;
+pub mod program;
+pub mod stake;
+pub mod token;
 pub mod token_analysis;
 pub mod transfer;
+pub mod watch;
 
 #[derive(Parser)]
 #[command(version, about)]
 pub struct Args {
+    /// 配置文件路径 (默认 ~/.config/solana/cli/config.yml)
+    #[arg(short = 'C', long, global = true, value_name = "FILEPATH")]
+    pub config: Option<String>,
+    /// RPC 节点地址或简称 [mainnet-beta, testnet, devnet, localhost]
+    #[arg(
+        short = 'u',
+        long = "url",
+        global = true,
+        value_name = "URL_OR_MONIKER"
+    )]
+    pub url: Option<String>,
+    /// 签名账户的 keypair 文件路径
+    #[arg(short = 'k', long, global = true, value_name = "KEYPAIR")]
+    pub keypair: Option<String>,
+    /// 交易与查询使用的提交级别
+    #[arg(
+        long,
+        global = true,
+        value_name = "COMMITMENT_LEVEL",
+        value_parser = ["processed", "confirmed", "finalized"]
+    )]
+    pub commitment: Option<String>,
+    /// 输出格式，json / json-compact 便于脚本解析
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "display")]
+    pub output: OutputFormat,
     #[command(subcommand)]
     pub command: Command,
 }
@@ -17,25 +67,552 @@ pub struct Args {
 // Use patten: https://github.com/clap-rs/clap/blob/8e3d03639756241aa2b7dd624a7f5852bef76f31/examples/git-derive.rs
 #[derive(Subcommand)]
 pub enum Command {
-    /// 目前只能使用默认配置文件(～/.config/solana/cli/config.yml)中的账户作为发送账户转移SOL
+    /// 转移SOL，发送账户默认为 --keypair 指定的账户 (或配置文件中的账户)
     Transfer {
         /// 目标账户的公钥
         to: Pubkey,
-        /// 转移的SOL数量
-        amount: u64,
+        /// 转移的数量，默认单位为 SOL (支持小数，如 0.5)；ALL 表示转出全部余额 (扣除手续费)
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 将 AMOUNT 视为 lamports 而不是 SOL
+        #[arg(long)]
+        lamports: bool,
+        /// 转出账户的签名者 [keypair 文件 | stdin | prompt:[//?key=0/0] | ASK | 公钥]，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        from: Option<String>,
+        /// 支付手续费的签名者，语法同 --from，默认由转出账户支付
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        nonce: NonceArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 按 CSV (recipient,amount) 批量发放 SOL 或代币，进度记录在本地账本中，中断后重新运行即可继续
+    Distribute {
+        /// CSV 文件，每行 recipient,amount；数量默认单位为 SOL，指定 --mint 时按代币精度解析
+        #[arg(value_name = "CSV_FILE")]
+        csv: String,
+        /// 发放的代币 mint，不指定时发放 SOL
+        #[arg(long, value_name = "MINT")]
+        mint: Option<Pubkey>,
+        /// 进度账本文件，默认为 <CSV_FILE>.ledger.json
+        #[arg(long, value_name = "FILEPATH")]
+        ledger: Option<String>,
+        /// 每笔交易最多包含的转账数，实际数量还受交易大小限制
+        #[arg(long = "batch-size", value_name = "N", default_value_t = 10)]
+        batch_size: usize,
+        /// 转出账户的签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        from: Option<String>,
+        /// 支付手续费的签名者，语法同 --from，默认由转出账户支付
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        /// 接收方的关联代币账户不存在时一并创建 (由手续费支付者支付租金)
+        #[arg(long = "fund-recipient", requires = "mint")]
+        fund_recipient: bool,
+        #[command(flatten)]
+        tx: TransactionArgs,
     },
     /// 获取账户的信息
     Account {
         /// 账户的公钥
         address: Pubkey,
+        /// 账户数据的编码方式；无法解码的账户按此编码显示原始数据
+        #[arg(long, default_value = "base64")]
+        encoding: CliAccountEncoding,
+        /// 将账户的原始数据写入该文件
+        #[arg(long = "output-file", value_name = "FILEPATH")]
+        output_file: Option<String>,
+        #[command(flatten)]
+        derivation: DerivationArgs,
+    },
+    /// 按程序与 seed 计算程序派生地址 (PDA) 及其规范 bump
+    FindPda {
+        /// 派生地址的程序
+        #[arg(long, value_name = "PROGRAM_ID")]
+        program: Pubkey,
+        /// seed，按出现顺序参与派生，可重复；TYPE 为 str、pubkey、hex、base58、u8、
+        /// u16le/u16be、u32le/u32be、u64le/u64be、i64le/i64be、u128le/u128be 之一
+        #[arg(long = "seed", value_name = "TYPE:VALUE", value_parser = parse_seed)]
+        seeds: Vec<Seed>,
     },
-    /// 获取账户的SOL的余额
+    /// 计算 create_with_seed(BASE, SEED, OWNER) 派生的地址
+    CreateWithSeed {
+        /// seed 字符串，最长 32 字节
+        seed: String,
+        /// 账户的 owner 程序
+        #[arg(value_name = "OWNER_PROGRAM_ID")]
+        owner: Pubkey,
+        /// base 地址，默认为 --keypair 对应的地址
+        #[arg(long, value_name = "PUBKEY")]
+        base: Option<Pubkey>,
+    },
+    /// 获取一个或多个账户的SOL的余额，可附带代币持仓
     Balance {
-        /// 账户的公钥
+        /// 账户的公钥，可指定多个
+        #[arg(required_unless_present = "addresses_file")]
+        addresses: Vec<Pubkey>,
+        /// 从文件读取地址，每行一个，忽略空行与 # 开头的注释
+        #[arg(long = "addresses-file", value_name = "FILEPATH")]
+        addresses_file: Option<String>,
+        /// 同时列出每个地址持有的 spl-token / Token-2022 代币账户，并按 mint 合计
+        #[arg(long = "include-tokens")]
+        include_tokens: bool,
+    },
+    /// 查询已上链交易的状态，并解码指令、程序日志、手续费与余额变化
+    #[command(visible_alias = "tx")]
+    Confirm {
+        /// 交易签名
+        signature: Signature,
+    },
+    /// 列出地址的历史交易 (自动分页)，可逐笔解析为摘要并导出为 CSV / JSON
+    History {
+        /// 查询的地址
         address: Pubkey,
+        /// 只列出早于该签名的交易，用于从上一次的末尾继续翻页
+        #[arg(long, value_name = "SIGNATURE")]
+        before: Option<Signature>,
+        /// 列到该签名为止 (不含该签名)
+        #[arg(long, value_name = "SIGNATURE")]
+        until: Option<Signature>,
+        /// 最多列出的交易数，超过单页上限 (1000) 时自动翻页
+        #[arg(long, value_name = "N", default_value_t = 100)]
+        limit: usize,
+        /// 逐笔获取交易，解析出该地址的 SOL / 代币余额变化、调用的指令与手续费
+        #[arg(long)]
+        summary: bool,
+        /// 将结果导出到文件，按扩展名 (.csv / .json) 选择格式
+        #[arg(long, value_name = "FILEPATH")]
+        export: Option<String>,
+    },
+    /// 生成、恢复、校验 keypair 与搜索靓号地址，无需另装 solana-keygen
+    Keygen {
+        #[command(subcommand)]
+        command: KeygenCommand,
+    },
+    /// 通过 WebSocket 订阅账户、日志、交易状态或 slot 的变化并持续输出，断线自动重连，Ctrl-C 退出
+    Watch {
+        #[command(subcommand)]
+        target: WatchCommand,
+    },
+    /// 查询 RPC 节点的软件版本与特性集
+    ClusterVersion,
+    /// 查询当前 epoch 的进度与预计剩余时间
+    EpochInfo,
+    /// 查询当前 slot
+    Slot,
+    /// 查询当前区块高度
+    BlockHeight,
+    /// 列出验证者的质押、佣金、投票与跳块率，离线 (delinquent) 的验证者以 ! 标记
+    Validators {
+        /// 排序字段
+        #[arg(
+            long = "sort",
+            value_enum,
+            value_name = "FIELD",
+            default_value = "stake"
+        )]
+        sort: ValidatorSort,
+        /// 反转排序
+        #[arg(long)]
+        reverse: bool,
+        /// 只列出离线 (delinquent) 的验证者
+        #[arg(long)]
+        delinquent: bool,
+        /// 保留没有质押的离线验证者 (默认由节点过滤掉)
+        #[arg(long = "keep-unstaked-delinquents")]
+        keep_unstaked_delinquents: bool,
+    },
+    /// 查询 epoch 的 leader 排班
+    LeaderSchedule {
+        /// 查询的 epoch，默认为当前 epoch
+        #[arg(long, value_name = "EPOCH")]
+        epoch: Option<u64>,
+        /// 只列出该验证者身份 (identity) 的 leader slot
+        #[arg(long, value_name = "PUBKEY")]
+        identity: Option<Pubkey>,
+    },
+    /// 统计 epoch 内各 leader 的出块数与跳块率
+    BlockProduction {
+        /// 统计的 epoch，默认为当前 epoch (截至最新 slot)；节点只保留有限的历史
+        #[arg(long, value_name = "EPOCH")]
+        epoch: Option<u64>,
+        /// 只统计该验证者身份 (identity)
+        #[arg(long, value_name = "PUBKEY")]
+        identity: Option<Pubkey>,
     },
     /// 创建一个新账户并初始化为一个代币账户
-    MintToken,
+    MintToken {
+        /// 代币精度 (小数位数)
+        #[arg(long, value_name = "DECIMALS", default_value_t = 9)]
+        decimals: u8,
+        /// 增发权限，默认为 --keypair 对应的出资账户
+        #[arg(long = "mint-authority", value_name = "PUBKEY")]
+        mint_authority: Option<Pubkey>,
+        /// 冻结权限，默认与增发权限相同
+        #[arg(long = "freeze-authority", value_name = "PUBKEY")]
+        freeze_authority: Option<Pubkey>,
+        /// 不设置冻结权限 (代币账户永远不可冻结)
+        #[arg(long = "no-freeze-authority", conflicts_with = "freeze_authority")]
+        no_freeze_authority: bool,
+        /// mint 账户的签名者，语法同 transfer --from (离线组装时可只传公钥)；默认随机生成
+        #[arg(long = "mint-keypair", value_name = "SIGNER")]
+        mint_keypair: Option<String>,
+        /// 随机生成 mint 账户时，寻找以该前缀开头的地址 (每多一个字符耗时约增加 58 倍)
+        #[arg(
+            long = "grind-prefix",
+            value_name = "PREFIX",
+            conflicts_with = "mint_keypair"
+        )]
+        grind_prefix: Option<String>,
+        /// 生成的 mint keypair 的保存路径，默认为当前目录下的 <MINT>.json
+        #[arg(long, value_name = "FILEPATH", conflicts_with = "mint_keypair")]
+        outfile: Option<String>,
+        #[command(flatten)]
+        extensions: MintExtensionArgs,
+        #[command(flatten)]
+        nonce: NonceArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 创建 durable nonce 账户，出资账户为 --keypair
+    CreateNonceAccount {
+        /// nonce 账户的签名者 [keypair 文件 | stdin | prompt:[//?key=0/0] | ASK]
+        #[arg(value_name = "NONCE_ACCOUNT_KEYPAIR")]
+        nonce_account: String,
+        /// 存入 nonce 账户的数量，默认单位为 SOL，需满足租金豁免
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 将 AMOUNT 视为 lamports 而不是 SOL
+        #[arg(long)]
+        lamports: bool,
+        /// nonce 账户的授权者，默认为出资账户
+        #[arg(long = "nonce-authority", value_name = "PUBKEY")]
+        nonce_authority: Option<Pubkey>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 查询 nonce 账户当前存储的 nonce 与授权者
+    Nonce {
+        /// nonce 账户的公钥
+        nonce_account: Pubkey,
+    },
+    /// 推进 nonce 账户存储的 nonce
+    NewNonce {
+        /// nonce 账户的公钥
+        nonce_account: Pubkey,
+        /// nonce 账户的授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "nonce-authority", value_name = "SIGNER")]
+        nonce_authority: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 从 nonce 账户提取 SOL
+    WithdrawFromNonceAccount {
+        /// nonce 账户的公钥
+        nonce_account: Pubkey,
+        /// 接收账户的公钥
+        destination: Pubkey,
+        /// 提取的数量，默认单位为 SOL；ALL 表示提取全部余额并关闭 nonce 账户
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 将 AMOUNT 视为 lamports 而不是 SOL
+        #[arg(long)]
+        lamports: bool,
+        /// nonce 账户的授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "nonce-authority", value_name = "SIGNER")]
+        nonce_authority: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 将 nonce 账户的授权转移给新的授权者
+    AuthorizeNonceAccount {
+        /// nonce 账户的公钥
+        nonce_account: Pubkey,
+        /// 新的授权者公钥
+        new_authority: Pubkey,
+        /// nonce 账户当前的授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "nonce-authority", value_name = "SIGNER")]
+        nonce_authority: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 创建 stake 账户，出资账户默认为 --keypair
+    CreateStakeAccount {
+        /// stake 账户的签名者 [keypair 文件 | stdin | prompt:[//?key=0/0] | ASK]；指定 --seed 时作为派生地址的 base
+        #[arg(value_name = "STAKE_ACCOUNT_KEYPAIR")]
+        stake_account: String,
+        /// 存入 stake 账户的数量，默认单位为 SOL，需满足租金豁免
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 将 AMOUNT 视为 lamports 而不是 SOL
+        #[arg(long)]
+        lamports: bool,
+        /// 以 create_with_seed(STAKE_ACCOUNT_KEYPAIR, SEED, Stake 程序) 派生 stake 账户地址
+        #[arg(long, value_name = "SEED")]
+        seed: Option<String>,
+        /// stake 授权者 (委托、取消委托、拆分、合并)，默认为出资账户
+        #[arg(long = "stake-authority", value_name = "PUBKEY")]
+        stake_authority: Option<Pubkey>,
+        /// 提取授权者，默认为出资账户
+        #[arg(long = "withdraw-authority", value_name = "PUBKEY")]
+        withdraw_authority: Option<Pubkey>,
+        #[command(flatten)]
+        lockup: LockupArgs,
+        /// 出资账户的签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        from: Option<String>,
+        /// 支付手续费的签名者，语法同 --from，默认由出资账户支付
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        nonce: NonceArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 将 stake 账户委托给投票账户
+    DelegateStake {
+        /// stake 账户的公钥
+        stake_account: Pubkey,
+        /// 投票账户的公钥
+        vote_account: Pubkey,
+        /// stake 授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "stake-authority", value_name = "SIGNER")]
+        stake_authority: Option<String>,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        nonce: NonceArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 取消 stake 账户的委托
+    DeactivateStake {
+        /// stake 账户的公钥
+        stake_account: Pubkey,
+        /// stake 授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "stake-authority", value_name = "SIGNER")]
+        stake_authority: Option<String>,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        nonce: NonceArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 从 stake 账户提取 SOL (委托中的部分需先取消委托并完成冷却)
+    WithdrawStake {
+        /// stake 账户的公钥
+        stake_account: Pubkey,
+        /// 接收账户的公钥
+        destination: Pubkey,
+        /// 提取的数量，默认单位为 SOL；ALL 表示提取全部余额并关闭 stake 账户
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 将 AMOUNT 视为 lamports 而不是 SOL
+        #[arg(long)]
+        lamports: bool,
+        /// 提取授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "withdraw-authority", value_name = "SIGNER")]
+        withdraw_authority: Option<String>,
+        /// 锁定期内提取时需要的托管人签名者
+        #[arg(long, value_name = "SIGNER")]
+        custodian: Option<String>,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        nonce: NonceArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 从 stake 账户拆分出一部分到新的 stake 账户，新账户的租金豁免由手续费账户补足
+    SplitStake {
+        /// 被拆分的 stake 账户公钥
+        stake_account: Pubkey,
+        /// 新 stake 账户的签名者；指定 --seed 时作为派生地址的 base
+        #[arg(value_name = "SPLIT_STAKE_ACCOUNT_KEYPAIR")]
+        split_stake_account: String,
+        /// 拆分出的数量，默认单位为 SOL
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 将 AMOUNT 视为 lamports 而不是 SOL
+        #[arg(long)]
+        lamports: bool,
+        /// 以 create_with_seed(SPLIT_STAKE_ACCOUNT_KEYPAIR, SEED, Stake 程序) 派生新账户地址
+        #[arg(long, value_name = "SEED")]
+        seed: Option<String>,
+        /// stake 授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "stake-authority", value_name = "SIGNER")]
+        stake_authority: Option<String>,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        nonce: NonceArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 将 SOURCE_STAKE_ACCOUNT 合并进 STAKE_ACCOUNT，两者的授权者、锁定期与委托状态须兼容
+    MergeStake {
+        /// 合并后保留的 stake 账户公钥
+        stake_account: Pubkey,
+        /// 被合并 (随后关闭) 的 stake 账户公钥
+        source_stake_account: Pubkey,
+        /// stake 授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "stake-authority", value_name = "SIGNER")]
+        stake_authority: Option<String>,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        nonce: NonceArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 查询 stake 账户的授权者、委托与当前激活状态
+    StakeAccount {
+        /// stake 账户的公钥
+        stake_account: Pubkey,
+        /// 同时查询最近若干 epoch 的通胀奖励
+        #[arg(long = "with-rewards")]
+        with_rewards: bool,
+        /// 查询奖励的 epoch 数
+        #[arg(
+            long = "num-rewards-epochs",
+            value_name = "N",
+            default_value_t = 1,
+            requires = "with_rewards"
+        )]
+        num_rewards_epochs: u64,
+    },
+    /// 创建、扩展、冻结、停用、关闭与查看地址查找表 (ALT)，配合 --lookup-table 发送 v0 交易
+    LookupTable {
+        #[command(subcommand)]
+        command: LookupTableCommand,
+    },
+    /// 部署、升级、查看、导出与关闭 BPF upgradeable loader 程序及其 buffer
+    Program {
+        #[command(subcommand)]
+        command: ProgramCommand,
+    },
+    /// 为钱包创建指定代币的关联代币账户 (ATA)，已存在时不报错
+    CreateTokenAccount {
+        /// 代币铸造地址 (Mint)
+        mint: Pubkey,
+        /// 代币账户的所有者，默认为 --keypair 对应的钱包
+        #[arg(long, value_name = "PUBKEY")]
+        owner: Option<Pubkey>,
+        /// mint 属于 Token-2022 程序；联网时按 mint 的 owner 自动识别，离线签名时需显式指定
+        #[arg(long = "program-2022")]
+        program_2022: bool,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 向钱包的关联代币账户增发代币
+    MintTo {
+        /// 代币铸造地址 (Mint)
+        mint: Pubkey,
+        /// 增发数量，按 mint 精度解析 (如 1.5)
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 接收代币的钱包地址，默认为 --keypair 对应的钱包
+        recipient: Option<Pubkey>,
+        /// mint 授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "mint-authority", value_name = "SIGNER")]
+        mint_authority: Option<String>,
+        #[command(flatten)]
+        mint_args: MintArgs,
+        /// 接收方的关联代币账户不存在时一并创建 (由 --keypair 支付租金)
+        #[arg(long = "fund-recipient")]
+        fund_recipient: bool,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 在钱包之间转移 SPL 代币 (双方均使用关联代币账户)
+    TransferToken {
+        /// 代币铸造地址 (Mint)
+        mint: Pubkey,
+        /// 转移数量，按 mint 精度解析 (如 1.5)；ALL 表示转出全部余额
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 接收代币的钱包地址
+        recipient: Pubkey,
+        /// 代币账户所有者的签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        owner: Option<String>,
+        #[command(flatten)]
+        mint_args: MintArgs,
+        /// 接收方的关联代币账户不存在时一并创建 (由 --keypair 支付租金)
+        #[arg(long = "fund-recipient")]
+        fund_recipient: bool,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 销毁关联代币账户中的代币
+    Burn {
+        /// 代币铸造地址 (Mint)
+        mint: Pubkey,
+        /// 销毁数量，按 mint 精度解析 (如 1.5)；ALL 表示销毁全部余额
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 代币账户所有者的签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        owner: Option<String>,
+        #[command(flatten)]
+        mint_args: MintArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 关闭余额为 0 的关联代币账户并取回租金
+    CloseTokenAccount {
+        /// 代币铸造地址 (Mint)
+        mint: Pubkey,
+        /// 代币账户所有者的签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        owner: Option<String>,
+        /// 接收退还租金的账户，默认为代币账户所有者
+        #[arg(long, value_name = "PUBKEY")]
+        destination: Option<Pubkey>,
+        /// mint 属于 Token-2022 程序；联网时按 mint 的 owner 自动识别，离线签名时需显式指定
+        #[arg(long = "program-2022")]
+        program_2022: bool,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 授权委托人从关联代币账户中转出代币
+    Approve {
+        /// 代币铸造地址 (Mint)
+        mint: Pubkey,
+        /// 委托人公钥
+        delegate: Pubkey,
+        /// 授权额度，按 mint 精度解析 (如 1.5)；ALL 表示当前全部余额
+        #[arg(value_name = "AMOUNT")]
+        amount: String,
+        /// 代币账户所有者的签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        owner: Option<String>,
+        #[command(flatten)]
+        mint_args: MintArgs,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 撤销关联代币账户上的委托
+    Revoke {
+        /// 代币铸造地址 (Mint)
+        mint: Pubkey,
+        /// 代币账户所有者的签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long, value_name = "SIGNER")]
+        owner: Option<String>,
+        /// mint 属于 Token-2022 程序；联网时按 mint 的 owner 自动识别，离线签名时需显式指定
+        #[arg(long = "program-2022")]
+        program_2022: bool,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
     /// 使用 Helius Rust SDK (RPC) 分析 SPL 代币持有人分布
     TokenAnalysis {
         /// 代币铸造地址 (Mint)
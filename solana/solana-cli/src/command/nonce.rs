@@ -0,0 +1,147 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_nonce::state::State as NonceState;
+use solana_sdk::native_token::Sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signer::Signer;
+use solana_system_interface::instruction as system_instruction;
+
+use crate::command::transfer::SpendAmount;
+use crate::error::{CliError, CliResult};
+use crate::output::{CliNonceAccount, CliTxResult};
+use crate::transaction::{TransactionArgs, get_nonce_account, send_instructions};
+
+/// 创建并初始化 durable nonce 账户，`authority` 默认为出资账户
+pub async fn create_nonce_account(
+    funding_account: &dyn Signer,
+    nonce_account: &dyn Signer,
+    authority: Option<Pubkey>,
+    amount: SpendAmount,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let SpendAmount::Lamports(lamports) = amount else {
+        return Err(CliError::InvalidInput(
+            "创建 nonce 账户需要明确的数量，不支持 ALL".to_string(),
+        ));
+    };
+    let minimum_balance = if tx_args.sign_only {
+        Rent::default().minimum_balance(NonceState::size())
+    } else {
+        rpc_client
+            .get_minimum_balance_for_rent_exemption(NonceState::size())
+            .await?
+    };
+    if lamports < minimum_balance {
+        return Err(CliError::InvalidInput(format!(
+            "nonce 账户至少需要 {} 以满足租金豁免",
+            Sol(minimum_balance)
+        )));
+    }
+
+    let authority = authority.unwrap_or_else(|| funding_account.pubkey());
+    let instructions = system_instruction::create_nonce_account(
+        &funding_account.pubkey(),
+        &nonce_account.pubkey(),
+        &authority,
+        lamports,
+    );
+    send_instructions(
+        &instructions,
+        funding_account,
+        &[funding_account, nonce_account],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 查询 nonce 账户当前存储的 nonce、授权者与余额
+pub async fn get_nonce(address: &Pubkey, rpc_client: &RpcClient) -> CliResult<CliNonceAccount> {
+    let (account, data) = get_nonce_account(rpc_client, address).await?;
+    Ok(CliNonceAccount::new(address, account.lamports, &data))
+}
+
+/// 推进 nonce 账户存储的 nonce，使之前用旧 nonce 签名的交易失效
+pub async fn new_nonce(
+    fee_payer: &dyn Signer,
+    nonce_account: &Pubkey,
+    authority: &dyn Signer,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let instruction = system_instruction::advance_nonce_account(nonce_account, &authority.pubkey());
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, authority],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 从 nonce 账户提取 SOL，`ALL` 会提取全部余额并关闭该账户
+pub async fn withdraw_from_nonce_account(
+    fee_payer: &dyn Signer,
+    nonce_account: &Pubkey,
+    authority: &dyn Signer,
+    destination: &Pubkey,
+    amount: SpendAmount,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let lamports = match amount {
+        SpendAmount::Lamports(lamports) => lamports,
+        SpendAmount::All if tx_args.sign_only => {
+            return Err(CliError::InvalidInput(
+                "--sign-only 模式下无法查询余额，不支持 ALL".to_string(),
+            ));
+        }
+        SpendAmount::All => {
+            get_nonce_account(rpc_client, nonce_account)
+                .await?
+                .0
+                .lamports
+        }
+    };
+
+    let instruction = system_instruction::withdraw_nonce_account(
+        nonce_account,
+        &authority.pubkey(),
+        destination,
+        lamports,
+    );
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, authority],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 将 nonce 账户的授权转移给 `new_authority`
+pub async fn authorize_nonce_account(
+    fee_payer: &dyn Signer,
+    nonce_account: &Pubkey,
+    authority: &dyn Signer,
+    new_authority: &Pubkey,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let instruction = system_instruction::authorize_nonce_account(
+        nonce_account,
+        &authority.pubkey(),
+        new_authority,
+    );
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, authority],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
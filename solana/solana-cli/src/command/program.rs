@@ -0,0 +1,1243 @@
+use std::path::Path;
+
+use clap::Subcommand;
+use futures_util::{StreamExt, stream};
+use solana_account::Account;
+use solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig,
+};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_loader_v3_interface::state::UpgradeableLoaderState;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::Sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+use solana_sdk::signer::keypair::Keypair;
+use solana_sdk_ids::{bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable};
+
+use crate::error::{CliError, CliResult};
+use crate::output::{
+    CliProgram, CliProgramBuffer, CliProgramBuffers, CliProgramClose, CliProgramDeploy,
+    CliProgramDump, CliTxResult,
+};
+use crate::transaction::{
+    ComputeBudget, MAX_COMPUTE_UNIT_LIMIT, MAX_SIGNATURE_STATUSES, PACKET_DATA_SIZE,
+    STATUS_POLL_INTERVAL, TransactionArgs, build_transaction, fee_for_message, send_instructions,
+    sign_transaction,
+};
+
+/// 写入 buffer 时同时在途的交易数
+const MAX_PARALLEL_WRITES: usize = 16;
+
+/// 写入交易的最多发送轮数：每轮使用新的区块哈希重发上一轮未确认的交易
+const MAX_WRITE_ROUNDS: usize = 5;
+
+/// program close --buffers 时单笔交易关闭的 buffer 数
+const MAX_CLOSES_PER_TRANSACTION: usize = 10;
+
+/// 通过 BPF upgradeable loader 部署与管理链上程序
+#[derive(Subcommand)]
+pub enum ProgramCommand {
+    /// 部署新程序：创建 buffer，并行写入程序数据后完成部署
+    Deploy {
+        /// 程序的 ELF 文件 (cargo build-sbf 生成的 .so)
+        #[arg(value_name = "PROGRAM_FILEPATH")]
+        program_path: String,
+        /// 程序账户的签名者，默认为 .so 同目录下的 <名称>-keypair.json (cargo build-sbf 生成)
+        #[arg(long = "program-id", value_name = "SIGNER")]
+        program_id: Option<String>,
+        /// 升级授权签名者，语法同 transfer --from，默认使用 --keypair
+        #[arg(long = "upgrade-authority", value_name = "SIGNER")]
+        upgrade_authority: Option<String>,
+        /// programdata 为日后升级预留的最大长度 (字节)，默认为程序大小的 2 倍
+        #[arg(long = "max-len", value_name = "BYTES")]
+        max_len: Option<usize>,
+        /// 部署后撤销升级授权，程序将不可升级 (不可撤销)
+        #[arg(long = "final")]
+        is_final: bool,
+        /// 继续使用上次中断时保留的 buffer，只重写内容不一致的部分
+        #[arg(long, value_name = "BUFFER_ADDRESS")]
+        buffer: Option<Pubkey>,
+        /// 支付手续费与租金的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 升级已部署的程序：写入新的 buffer 后替换 programdata 中的程序
+    Upgrade {
+        /// 新版本的 ELF 文件
+        #[arg(value_name = "PROGRAM_FILEPATH")]
+        program_path: String,
+        /// 程序地址
+        program_id: Pubkey,
+        /// 升级授权签名者，默认使用 --keypair
+        #[arg(long = "upgrade-authority", value_name = "SIGNER")]
+        upgrade_authority: Option<String>,
+        /// 继续使用上次中断时保留的 buffer，只重写内容不一致的部分
+        #[arg(long, value_name = "BUFFER_ADDRESS")]
+        buffer: Option<Pubkey>,
+        /// 支付手续费与 buffer 租金的签名者，默认使用 --keypair；buffer 的租金在升级后退回该账户
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 转移或撤销程序的升级授权
+    SetUpgradeAuthority {
+        /// 程序地址
+        program_id: Pubkey,
+        /// 当前的升级授权签名者，默认使用 --keypair
+        #[arg(long = "upgrade-authority", value_name = "SIGNER")]
+        upgrade_authority: Option<String>,
+        /// 新的升级授权者；默认需要其签名，以免转给无法签名的地址
+        #[arg(
+            long = "new-upgrade-authority",
+            value_name = "SIGNER",
+            required_unless_present = "is_final",
+            conflicts_with = "is_final"
+        )]
+        new_upgrade_authority: Option<String>,
+        /// 不要求新授权者签名，--new-upgrade-authority 可以只传公钥
+        #[arg(
+            long = "skip-new-upgrade-authority-signer-check",
+            requires = "new_upgrade_authority"
+        )]
+        skip_new_upgrade_authority_signer_check: bool,
+        /// 撤销升级授权，程序将不可再升级 (不可撤销)
+        #[arg(long = "final")]
+        is_final: bool,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+    /// 查看程序的 programdata 地址、升级授权者、最近部署的 slot 与数据长度，或列出遗留的 buffer
+    Show {
+        /// 程序地址
+        #[arg(required_unless_present = "buffers", conflicts_with = "buffers")]
+        program_id: Option<Pubkey>,
+        /// 列出 --buffer-authority 的全部 buffer 账户 (部署中断后遗留的)
+        #[arg(long)]
+        buffers: bool,
+        /// buffer 的授权者，默认为 --keypair 对应的地址
+        #[arg(long = "buffer-authority", value_name = "PUBKEY", requires = "buffers")]
+        buffer_authority: Option<Pubkey>,
+    },
+    /// 将程序 (或 buffer) 的 ELF 保存到文件
+    Dump {
+        /// 程序或 buffer 地址
+        address: Pubkey,
+        /// 保存的文件路径
+        #[arg(value_name = "OUTPUT_FILEPATH")]
+        output_file: String,
+    },
+    /// 关闭 buffer 账户并回收租金
+    Close {
+        /// buffer 地址
+        #[arg(required_unless_present = "buffers", conflicts_with = "buffers")]
+        buffer: Option<Pubkey>,
+        /// 关闭 --buffer-authority 的全部 buffer 账户
+        #[arg(long, conflicts_with = "sign_only")]
+        buffers: bool,
+        /// 接收租金的账户，默认为手续费支付者
+        #[arg(long, value_name = "PUBKEY")]
+        recipient: Option<Pubkey>,
+        /// buffer 的授权签名者，默认使用 --keypair
+        #[arg(long = "buffer-authority", value_name = "SIGNER")]
+        buffer_authority: Option<String>,
+        /// 支付手续费的签名者，默认使用 --keypair
+        #[arg(long = "fee-payer", value_name = "SIGNER")]
+        fee_payer: Option<String>,
+        #[command(flatten)]
+        tx: TransactionArgs,
+    },
+}
+
+/// deploy 未指定 --program-id 时使用 cargo build-sbf 生成的 `<名称>-keypair.json`
+pub fn program_keypair_source(program_path: &str, program_id: Option<&str>) -> CliResult<String> {
+    if let Some(source) = program_id {
+        return Ok(source.to_string());
+    }
+    let path = Path::new(program_path);
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let keypair_path = path.with_file_name(format!("{}-keypair.json", stem));
+    if !keypair_path.exists() {
+        return Err(CliError::InvalidInput(format!(
+            "找不到程序 keypair {}，请通过 --program-id 指定",
+            keypair_path.display()
+        )));
+    }
+    Ok(keypair_path.display().to_string())
+}
+
+/// 部署新程序：创建 buffer (或沿用 `buffer`)、写入程序数据，最后创建程序与 programdata 账户
+#[allow(clippy::too_many_arguments)]
+pub async fn deploy(
+    fee_payer: &dyn Signer,
+    program_path: &str,
+    program_keypair: &dyn Signer,
+    upgrade_authority: &dyn Signer,
+    max_len: Option<usize>,
+    is_final: bool,
+    buffer: Option<Pubkey>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliProgramDeploy> {
+    check_multi_transaction_args(tx_args)?;
+    let program_data = read_program(program_path)?;
+    let program_id = program_keypair.pubkey();
+    if let Some(account) = rpc_client
+        .get_account_with_commitment(&program_id, rpc_client.commitment())
+        .await?
+        .value
+        && account.owner == bpf_loader_upgradeable::ID
+    {
+        return Err(CliError::InvalidInput(format!(
+            "程序 {} 已部署，请使用 program upgrade",
+            program_id
+        )));
+    }
+    let max_len = max_len.unwrap_or(program_data.len() * 2);
+    if max_len < program_data.len() {
+        return Err(CliError::InvalidInput(format!(
+            "--max-len {} 小于程序大小 {}",
+            max_len,
+            program_data.len()
+        )));
+    }
+
+    let program_rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_program())
+        .await?;
+    let programdata_rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_programdata(
+            max_len,
+        ))
+        .await?;
+    let final_instructions = |buffer: &Pubkey| {
+        let mut instructions = loader_instruction::deploy_with_max_program_len(
+            &fee_payer.pubkey(),
+            &program_id,
+            buffer,
+            &upgrade_authority.pubkey(),
+            program_rent,
+            max_len,
+        );
+        if is_final {
+            instructions.push(loader_instruction::set_upgrade_authority(
+                &program_id,
+                &upgrade_authority.pubkey(),
+                None,
+            ));
+        }
+        instructions
+    };
+
+    eprintln!(
+        "Deploying {} ({} bytes, max length {}) as program {}",
+        program_path,
+        program_data.len(),
+        max_len,
+        program_id
+    );
+    write_and_finalize(
+        fee_payer,
+        &program_id,
+        &program_data,
+        upgrade_authority,
+        buffer,
+        program_rent,
+        program_rent + programdata_rent,
+        final_instructions,
+        &[program_keypair],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 升级程序：写入新的 buffer 后执行 Upgrade，buffer 的租金退回手续费支付者
+pub async fn upgrade(
+    fee_payer: &dyn Signer,
+    program_path: &str,
+    program_id: &Pubkey,
+    upgrade_authority: &dyn Signer,
+    buffer: Option<Pubkey>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliProgramDeploy> {
+    check_multi_transaction_args(tx_args)?;
+    let program_data = read_program(program_path)?;
+    let current = get_program_data(rpc_client, program_id).await?;
+    check_upgrade_authority(program_id, &current, upgrade_authority)?;
+    let capacity = current
+        .account
+        .data
+        .len()
+        .saturating_sub(UpgradeableLoaderState::size_of_programdata_metadata());
+    if program_data.len() > capacity {
+        return Err(CliError::InvalidInput(format!(
+            "新程序大小 {} 超过 programdata {} 的容量 {}，需要先扩展 programdata",
+            program_data.len(),
+            current.address,
+            capacity
+        )));
+    }
+
+    let final_instructions = |buffer: &Pubkey| {
+        vec![loader_instruction::upgrade(
+            program_id,
+            buffer,
+            &upgrade_authority.pubkey(),
+            &fee_payer.pubkey(),
+        )]
+    };
+    eprintln!(
+        "Upgrading program {} with {} ({} bytes)",
+        program_id,
+        program_path,
+        program_data.len()
+    );
+    write_and_finalize(
+        fee_payer,
+        program_id,
+        &program_data,
+        upgrade_authority,
+        buffer,
+        0,
+        0,
+        final_instructions,
+        &[],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// deploy 与 upgrade 的公共流程：准备 buffer、写入数据，再发送 `final_instructions` 按 buffer 地址构造的指令
+///
+/// `--dry-run` 时只模拟第一笔交易 (创建 buffer，或沿用 buffer 时的第一笔写入)。
+#[allow(clippy::too_many_arguments)]
+async fn write_and_finalize(
+    fee_payer: &dyn Signer,
+    program_id: &Pubkey,
+    program_data: &[u8],
+    authority: &dyn Signer,
+    buffer: Option<Pubkey>,
+    program_rent: u64,
+    final_rent: u64,
+    final_instructions: impl FnOnce(&Pubkey) -> Vec<Instruction>,
+    final_signers: &[&dyn Signer],
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliProgramDeploy> {
+    // 沿用 buffer 时读取其中已写入的数据，只重写不一致的部分
+    let (buffer_address, buffer_keypair, existing) = match buffer {
+        Some(address) => {
+            let data = get_buffer_data(rpc_client, &address, authority, program_data.len()).await?;
+            (address, None, Some(data))
+        }
+        None => {
+            let keypair = Keypair::new();
+            (keypair.pubkey(), Some(keypair), None)
+        }
+    };
+    let final_instructions = final_instructions(&buffer_address);
+
+    let chunk_size = write_chunk_size(fee_payer, &buffer_address, authority)?;
+    let pending: Vec<(usize, &[u8])> = program_data
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(index, chunk)| (index * chunk_size, chunk))
+        .filter(|(offset, chunk)| {
+            existing
+                .as_deref()
+                .is_none_or(|data| data.get(*offset..*offset + chunk.len()) != Some(*chunk))
+        })
+        .collect();
+    let buffer_rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_buffer(
+            program_data.len(),
+        ))
+        .await?;
+    let create_instructions = match &buffer_keypair {
+        Some(keypair) => loader_instruction::create_buffer(
+            &fee_payer.pubkey(),
+            &keypair.pubkey(),
+            &authority.pubkey(),
+            buffer_rent,
+            program_data.len(),
+        ),
+        None => Vec::new(),
+    };
+    check_payer_balance(
+        fee_payer,
+        buffer_keypair.as_ref().map_or(0, |_| buffer_rent),
+        program_rent,
+        final_rent,
+        pending.len() + 2,
+        &buffer_address,
+        authority,
+        rpc_client,
+    )
+    .await?;
+    eprintln!(
+        "Writing {} bytes to buffer {} in {} transactions",
+        pending.iter().map(|(_, chunk)| chunk.len()).sum::<usize>(),
+        buffer_address,
+        pending.len()
+    );
+
+    let mut report = CliProgramDeploy {
+        program_id: program_id.to_string(),
+        buffer: buffer_address.to_string(),
+        program_len: program_data.len(),
+        write_transactions: pending.len(),
+        result: CliTxResult::default(),
+    };
+    if tx_args.dry_run {
+        report.result = match (&buffer_keypair, pending.first()) {
+            (Some(keypair), _) => {
+                send_instructions(
+                    &create_instructions,
+                    fee_payer,
+                    &[fee_payer, keypair],
+                    tx_args,
+                    rpc_client,
+                )
+                .await?
+            }
+            (None, Some((offset, chunk))) => {
+                let instruction = loader_instruction::write(
+                    &buffer_address,
+                    &authority.pubkey(),
+                    *offset as u32,
+                    chunk.to_vec(),
+                );
+                send_instructions(
+                    &[instruction],
+                    fee_payer,
+                    &[fee_payer, authority],
+                    tx_args,
+                    rpc_client,
+                )
+                .await?
+            }
+            (None, None) => {
+                let mut signers = vec![fee_payer, authority];
+                signers.extend(final_signers);
+                send_instructions(
+                    &final_instructions,
+                    fee_payer,
+                    &signers,
+                    tx_args,
+                    rpc_client,
+                )
+                .await?
+            }
+        };
+        return Ok(report);
+    }
+
+    if let Some(keypair) = &buffer_keypair {
+        send_instructions(
+            &create_instructions,
+            fee_payer,
+            &[fee_payer, keypair],
+            tx_args,
+            rpc_client,
+        )
+        .await?;
+    }
+    let finalized = async {
+        write_buffer(
+            fee_payer,
+            &buffer_address,
+            authority,
+            &pending,
+            tx_args,
+            rpc_client,
+        )
+        .await?;
+        let mut signers = vec![fee_payer, authority];
+        signers.extend(final_signers);
+        send_instructions(
+            &final_instructions,
+            fee_payer,
+            &signers,
+            tx_args,
+            rpc_client,
+        )
+        .await
+    }
+    .await;
+    match finalized {
+        Ok(result) => {
+            report.result = result;
+            Ok(report)
+        }
+        Err(err) => {
+            eprintln!(
+                "buffer {} 已保留：加上 --buffer {} 重新运行可继续 (只重写未完成的部分)，或使用 program close {} 回收租金",
+                buffer_address, buffer_address, buffer_address
+            );
+            Err(err)
+        }
+    }
+}
+
+/// 并行发送写入交易，每轮等待确认直到区块哈希过期，未确认的交易在下一轮用新的区块哈希重发
+async fn write_buffer(
+    fee_payer: &dyn Signer,
+    buffer: &Pubkey,
+    authority: &dyn Signer,
+    chunks: &[(usize, &[u8])],
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<()> {
+    let Some((first_offset, first_chunk)) = chunks.first() else {
+        return Ok(());
+    };
+    let write_instruction = |offset: usize, chunk: &[u8]| {
+        loader_instruction::write(buffer, &authority.pubkey(), offset as u32, chunk.to_vec())
+    };
+    // buffer 已经存在，可以用第一笔写入估算计算预算
+    let budget = tx_args
+        .compute_budget(
+            &[write_instruction(*first_offset, first_chunk)],
+            &fee_payer.pubkey(),
+            &[],
+            rpc_client,
+        )
+        .await?;
+    let commitment = rpc_client.commitment();
+    let config = RpcSendTransactionConfig {
+        skip_preflight: true,
+        preflight_commitment: Some(commitment.commitment),
+        ..RpcSendTransactionConfig::default()
+    };
+
+    let total = chunks.len();
+    let mut pending: Vec<usize> = (0..total).collect();
+    for round in 1..=MAX_WRITE_ROUNDS {
+        if pending.is_empty() {
+            break;
+        }
+        if round > 1 {
+            eprintln!(
+                "Resending {} unconfirmed write transactions (round {}/{})",
+                pending.len(),
+                round,
+                MAX_WRITE_ROUNDS
+            );
+        }
+        let (blockhash, last_valid_block_height) = rpc_client
+            .get_latest_blockhash_with_commitment(commitment)
+            .await?;
+        let mut transactions = Vec::with_capacity(pending.len());
+        for &index in &pending {
+            let (offset, chunk) = chunks[index];
+            let mut transaction = build_transaction(
+                vec![write_instruction(offset, chunk)],
+                &fee_payer.pubkey(),
+                None,
+                &budget,
+                &[],
+            )?;
+            sign_transaction(
+                &mut transaction,
+                &[fee_payer, authority],
+                blockhash,
+                tx_args,
+            )?;
+            transactions.push((index, transaction));
+        }
+
+        let sent: Vec<_> = stream::iter(&transactions)
+            .map(|(_, transaction)| rpc_client.send_transaction_with_config(transaction, config))
+            .buffer_unordered(MAX_PARALLEL_WRITES)
+            .collect()
+            .await;
+        for result in sent {
+            // 发送失败的交易不会被确认，留到下一轮重发
+            if let Err(err) = result {
+                eprintln!("Write transaction failed to send: {}", err);
+            }
+        }
+
+        let mut unconfirmed: Vec<(usize, Signature)> = transactions
+            .iter()
+            .map(|(index, transaction)| (*index, transaction.signatures[0]))
+            .collect();
+        loop {
+            tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+            let mut remaining = Vec::new();
+            for batch in unconfirmed.chunks(MAX_SIGNATURE_STATUSES) {
+                let signatures: Vec<Signature> =
+                    batch.iter().map(|(_, signature)| *signature).collect();
+                let statuses = rpc_client.get_signature_statuses(&signatures).await?.value;
+                for (&(index, signature), status) in batch.iter().zip(statuses) {
+                    match status {
+                        Some(status) => {
+                            if let Some(error) = status.err {
+                                return Err(CliError::from_executed_error(error, signature));
+                            }
+                            if !status.satisfies_commitment(commitment) {
+                                remaining.push((index, signature));
+                            }
+                        }
+                        None => remaining.push((index, signature)),
+                    }
+                }
+            }
+            if remaining.len() != unconfirmed.len() {
+                eprintln!(
+                    "Confirmed {}/{} write transactions",
+                    total - remaining.len(),
+                    total
+                );
+            }
+            unconfirmed = remaining;
+            if unconfirmed.is_empty()
+                || rpc_client.get_block_height().await? > last_valid_block_height
+            {
+                break;
+            }
+        }
+        pending = unconfirmed.into_iter().map(|(index, _)| index).collect();
+    }
+
+    if !pending.is_empty() {
+        return Err(CliError::Other(anyhow::anyhow!(
+            "经过 {} 轮重发仍有 {} 笔写入交易未确认",
+            MAX_WRITE_ROUNDS,
+            pending.len()
+        )));
+    }
+    Ok(())
+}
+
+/// 转移 (或以 `new_authority` 为 None 撤销) 程序的升级授权
+///
+/// 默认使用 SetAuthorityChecked，要求新授权者签名；`skip_new_authority_signer_check` 时只需其公钥。
+#[allow(clippy::too_many_arguments)]
+pub async fn set_upgrade_authority(
+    fee_payer: &dyn Signer,
+    program_id: &Pubkey,
+    upgrade_authority: &dyn Signer,
+    new_authority: Option<&dyn Signer>,
+    skip_new_authority_signer_check: bool,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    if !tx_args.sign_only {
+        let current = get_program_data(rpc_client, program_id).await?;
+        check_upgrade_authority(program_id, &current, upgrade_authority)?;
+    }
+    let mut signers = vec![fee_payer, upgrade_authority];
+    let instruction = match new_authority {
+        None => {
+            loader_instruction::set_upgrade_authority(program_id, &upgrade_authority.pubkey(), None)
+        }
+        Some(new_authority) if skip_new_authority_signer_check => {
+            loader_instruction::set_upgrade_authority(
+                program_id,
+                &upgrade_authority.pubkey(),
+                Some(&new_authority.pubkey()),
+            )
+        }
+        Some(new_authority) => {
+            signers.push(new_authority);
+            loader_instruction::set_upgrade_authority_checked(
+                program_id,
+                &upgrade_authority.pubkey(),
+                &new_authority.pubkey(),
+            )
+        }
+    };
+    send_instructions(&[instruction], fee_payer, &signers, tx_args, rpc_client).await
+}
+
+/// 查看程序；upgradeable 程序的信息来自其 programdata 账户
+pub async fn show_program(program_id: &Pubkey, rpc_client: &RpcClient) -> CliResult<CliProgram> {
+    let account = get_account(rpc_client, program_id).await?;
+    if account.owner == bpf_loader::ID || account.owner == bpf_loader_deprecated::ID {
+        return Ok(CliProgram {
+            program_id: program_id.to_string(),
+            owner: account.owner.to_string(),
+            program_data: None,
+            authority: None,
+            last_deploy_slot: None,
+            data_len: account.data.len(),
+            lamports: account.lamports,
+        });
+    }
+    let program_data = get_program_data(rpc_client, program_id).await?;
+    Ok(CliProgram {
+        program_id: program_id.to_string(),
+        owner: account.owner.to_string(),
+        program_data: Some(program_data.address.to_string()),
+        authority: program_data
+            .authority
+            .map(|authority| authority.to_string()),
+        last_deploy_slot: Some(program_data.slot),
+        data_len: program_data
+            .account
+            .data
+            .len()
+            .saturating_sub(UpgradeableLoaderState::size_of_programdata_metadata()),
+        lamports: program_data.account.lamports,
+    })
+}
+
+/// 列出授权者为 `authority` 的全部 buffer 账户
+pub async fn show_buffers(
+    authority: &Pubkey,
+    rpc_client: &RpcClient,
+) -> CliResult<CliProgramBuffers> {
+    let buffers = get_buffers(rpc_client, authority).await?;
+    Ok(CliProgramBuffers {
+        authority: authority.to_string(),
+        buffers,
+    })
+}
+
+/// 将程序 (或 buffer) 中的 ELF 写入 `output_file`
+pub async fn dump(
+    address: &Pubkey,
+    output_file: &str,
+    rpc_client: &RpcClient,
+) -> CliResult<CliProgramDump> {
+    let account = get_account(rpc_client, address).await?;
+    let elf = if account.owner == bpf_loader::ID || account.owner == bpf_loader_deprecated::ID {
+        account.data
+    } else {
+        match loader_state(address, &account)? {
+            UpgradeableLoaderState::Buffer { .. } => {
+                account.data[UpgradeableLoaderState::size_of_buffer_metadata()..].to_vec()
+            }
+            UpgradeableLoaderState::Program { .. } => {
+                let program_data = get_program_data(rpc_client, address).await?;
+                program_data.account.data[UpgradeableLoaderState::size_of_programdata_metadata()..]
+                    .to_vec()
+            }
+            _ => {
+                return Err(CliError::InvalidInput(format!(
+                    "{} 不是程序或 buffer 账户",
+                    address
+                )));
+            }
+        }
+    };
+    std::fs::write(output_file, &elf)
+        .map_err(|err| CliError::Other(anyhow::anyhow!("写入 {} 失败: {}", output_file, err)))?;
+    Ok(CliProgramDump {
+        address: address.to_string(),
+        output_file: output_file.to_string(),
+        data_len: elf.len(),
+    })
+}
+
+/// 关闭 `buffer` (为 None 时关闭授权者的全部 buffer)，租金转入 `recipient`
+pub async fn close_buffers(
+    fee_payer: &dyn Signer,
+    buffer: Option<Pubkey>,
+    recipient: &Pubkey,
+    authority: &dyn Signer,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliProgramClose> {
+    let buffers = match buffer {
+        Some(address) if tx_args.sign_only => vec![CliProgramBuffer {
+            address: address.to_string(),
+            authority: Some(authority.pubkey().to_string()),
+            lamports: 0,
+        }],
+        Some(address) => {
+            let account = get_account(rpc_client, &address).await?;
+            let UpgradeableLoaderState::Buffer { authority_address } =
+                loader_state(&address, &account)?
+            else {
+                return Err(CliError::InvalidInput(format!(
+                    "{} 不是 buffer 账户 (关闭程序不可逆，本命令只回收 buffer 的租金)",
+                    address
+                )));
+            };
+            check_buffer_authority(&address, authority_address, authority)?;
+            vec![CliProgramBuffer {
+                address: address.to_string(),
+                authority: authority_address.map(|authority| authority.to_string()),
+                lamports: account.lamports,
+            }]
+        }
+        None => get_buffers(rpc_client, &authority.pubkey()).await?,
+    };
+
+    let mut transactions = Vec::new();
+    for batch in buffers.chunks(MAX_CLOSES_PER_TRANSACTION) {
+        let instructions = batch
+            .iter()
+            .map(|buffer| {
+                let address = buffer.address.parse::<Pubkey>().map_err(|err| {
+                    CliError::Other(anyhow::anyhow!("无效的 buffer 地址: {}", err))
+                })?;
+                Ok(loader_instruction::close(
+                    &address,
+                    recipient,
+                    &authority.pubkey(),
+                ))
+            })
+            .collect::<CliResult<Vec<_>>>()?;
+        eprintln!("Closing {} buffer account(s)", batch.len());
+        transactions.push(
+            send_instructions(
+                &instructions,
+                fee_payer,
+                &[fee_payer, authority],
+                tx_args,
+                rpc_client,
+            )
+            .await?,
+        );
+    }
+
+    Ok(CliProgramClose {
+        recipient: recipient.to_string(),
+        lamports: buffers.iter().map(|buffer| buffer.lamports).sum(),
+        buffers: buffers.into_iter().map(|buffer| buffer.address).collect(),
+        transactions,
+    })
+}
+
+/// upgradeable 程序的 programdata 账户
+struct ProgramData {
+    address: Pubkey,
+    account: Account,
+    slot: u64,
+    authority: Option<Pubkey>,
+}
+
+/// 读取 upgradeable 程序及其 programdata 账户
+async fn get_program_data(rpc_client: &RpcClient, program_id: &Pubkey) -> CliResult<ProgramData> {
+    let program = get_account(rpc_client, program_id).await?;
+    let UpgradeableLoaderState::Program {
+        programdata_address,
+    } = loader_state(program_id, &program)?
+    else {
+        return Err(CliError::InvalidInput(format!(
+            "{} 不是程序账户 (可能是 buffer 或 programdata 账户)",
+            program_id
+        )));
+    };
+    let account = get_account(rpc_client, &programdata_address).await?;
+    let UpgradeableLoaderState::ProgramData {
+        slot,
+        upgrade_authority_address,
+    } = loader_state(&programdata_address, &account)?
+    else {
+        return Err(CliError::InvalidInput(format!(
+            "{} 不是 programdata 账户",
+            programdata_address
+        )));
+    };
+    Ok(ProgramData {
+        address: programdata_address,
+        account,
+        slot,
+        authority: upgrade_authority_address,
+    })
+}
+
+/// 沿用的 buffer：校验授权者与大小，返回其中已写入的程序数据
+async fn get_buffer_data(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+    authority: &dyn Signer,
+    program_len: usize,
+) -> CliResult<Vec<u8>> {
+    let account = get_account(rpc_client, address).await?;
+    let UpgradeableLoaderState::Buffer { authority_address } = loader_state(address, &account)?
+    else {
+        return Err(CliError::InvalidInput(format!(
+            "{} 不是 buffer 账户",
+            address
+        )));
+    };
+    check_buffer_authority(address, authority_address, authority)?;
+    if account.data.len() != UpgradeableLoaderState::size_of_buffer(program_len) {
+        return Err(CliError::InvalidInput(format!(
+            "buffer {} 的大小为 {} 字节，与程序大小 {} 不符",
+            address,
+            account
+                .data
+                .len()
+                .saturating_sub(UpgradeableLoaderState::size_of_buffer_metadata()),
+            program_len
+        )));
+    }
+    Ok(account.data[UpgradeableLoaderState::size_of_buffer_metadata()..].to_vec())
+}
+
+async fn get_buffers(
+    rpc_client: &RpcClient,
+    authority: &Pubkey,
+) -> CliResult<Vec<CliProgramBuffer>> {
+    // Buffer 变体 (u32 = 1)、Some(authority)
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![1, 0, 0, 0])),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(4, vec![1])),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(5, authority.to_bytes().to_vec())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(UiDataSliceConfig {
+                offset: 0,
+                length: 0,
+            }),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = rpc_client
+        .get_program_accounts_with_config(&bpf_loader_upgradeable::ID, config)
+        .await?;
+    Ok(accounts
+        .into_iter()
+        .map(|(address, account)| CliProgramBuffer {
+            address: address.to_string(),
+            authority: Some(authority.to_string()),
+            lamports: account.lamports,
+        })
+        .collect())
+}
+
+async fn get_account(rpc_client: &RpcClient, address: &Pubkey) -> CliResult<Account> {
+    rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?
+        .value
+        .ok_or_else(|| CliError::InvalidInput(format!("账户 {} 不存在", address)))
+}
+
+fn loader_state(address: &Pubkey, account: &Account) -> CliResult<UpgradeableLoaderState> {
+    if account.owner != bpf_loader_upgradeable::ID {
+        return Err(CliError::InvalidInput(format!(
+            "{} 不属于 BPF upgradeable loader (owner 为 {})",
+            address, account.owner
+        )));
+    }
+    bincode::deserialize(&account.data)
+        .map_err(|err| CliError::InvalidInput(format!("无法解析 loader 账户 {}: {}", address, err)))
+}
+
+fn check_upgrade_authority(
+    program_id: &Pubkey,
+    program_data: &ProgramData,
+    authority: &dyn Signer,
+) -> CliResult<()> {
+    match program_data.authority {
+        None => Err(CliError::InvalidInput(format!(
+            "程序 {} 已撤销升级授权，不可再升级",
+            program_id
+        ))),
+        Some(expected) if expected != authority.pubkey() => Err(CliError::Signer(format!(
+            "程序 {} 的升级授权者为 {}，而不是 {}",
+            program_id,
+            expected,
+            authority.pubkey()
+        ))),
+        Some(_) => Ok(()),
+    }
+}
+
+fn check_buffer_authority(
+    buffer: &Pubkey,
+    expected: Option<Pubkey>,
+    authority: &dyn Signer,
+) -> CliResult<()> {
+    if expected != Some(authority.pubkey()) {
+        return Err(CliError::Signer(format!(
+            "buffer {} 的授权者为 {}，而不是 {}",
+            buffer,
+            expected.map_or_else(|| "(none)".to_string(), |expected| expected.to_string()),
+            authority.pubkey()
+        )));
+    }
+    Ok(())
+}
+
+/// deploy / upgrade 会发送多笔交易，不支持离线签名
+fn check_multi_transaction_args(tx_args: &TransactionArgs) -> CliResult<()> {
+    if tx_args.sign_only || tx_args.blockhash.is_some() || !tx_args.signers.is_empty() {
+        return Err(CliError::InvalidInput(
+            "program deploy / upgrade 会发送多笔交易，不支持 --sign-only、--blockhash 与 --signer"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 读取 ELF 文件并做基本校验
+fn read_program(program_path: &str) -> CliResult<Vec<u8>> {
+    let data = std::fs::read(program_path).map_err(|err| {
+        CliError::InvalidInput(format!("读取程序文件 {} 失败: {}", program_path, err))
+    })?;
+    if !data.starts_with(b"\x7fELF") {
+        return Err(CliError::InvalidInput(format!(
+            "{} 不是 ELF 文件",
+            program_path
+        )));
+    }
+    Ok(data)
+}
+
+/// 单笔写入交易能携带的程序数据字节数 (为计算预算指令预留空间)
+fn write_chunk_size(
+    fee_payer: &dyn Signer,
+    buffer: &Pubkey,
+    authority: &dyn Signer,
+) -> CliResult<usize> {
+    let reserved_budget = ComputeBudget {
+        unit_limit: Some(MAX_COMPUTE_UNIT_LIMIT),
+        unit_price: Some(1),
+    };
+    let transaction = build_transaction(
+        vec![loader_instruction::write(
+            buffer,
+            &authority.pubkey(),
+            0,
+            Vec::new(),
+        )],
+        &fee_payer.pubkey(),
+        None,
+        &reserved_budget,
+        &[],
+    )?;
+    let size = bincode::serialized_size(&transaction)
+        .map_err(|err| CliError::Other(anyhow::anyhow!("序列化交易失败: {}", err)))?;
+    // 指令数据超过 127 字节后，其长度前缀 (compact-u16) 多占 1 字节
+    Ok(PACKET_DATA_SIZE - size as usize - 1)
+}
+
+/// 发送任何交易前确认手续费支付者足以支付 buffer 与最终账户的租金以及全部手续费
+#[allow(clippy::too_many_arguments)]
+async fn check_payer_balance(
+    fee_payer: &dyn Signer,
+    buffer_rent: u64,
+    program_rent: u64,
+    final_rent: u64,
+    transactions: usize,
+    buffer: &Pubkey,
+    authority: &dyn Signer,
+    rpc_client: &RpcClient,
+) -> CliResult<()> {
+    let mut message = build_transaction(
+        vec![loader_instruction::write(
+            buffer,
+            &authority.pubkey(),
+            0,
+            Vec::new(),
+        )],
+        &fee_payer.pubkey(),
+        None,
+        &ComputeBudget::default(),
+        &[],
+    )?
+    .message;
+    message.set_recent_blockhash(rpc_client.get_latest_blockhash().await?);
+    let fee = fee_for_message(rpc_client, &message).await? * transactions as u64;
+    let required = required_balance(buffer_rent, program_rent, final_rent, fee);
+    let balance = rpc_client.get_balance(&fee_payer.pubkey()).await?;
+    if balance < required {
+        return Err(CliError::InsufficientFunds(format!(
+            "账户 {} 需要约 {} (租金与 {} 笔交易的手续费)，当前余额 {}",
+            fee_payer.pubkey(),
+            Sol(required),
+            transactions,
+            Sol(balance)
+        )));
+    }
+    Ok(())
+}
+
+/// 部署全程手续费支付者需要的最低余额
+///
+/// 部署交易先创建程序账户 (此时 buffer 的租金仍未退回)，之后 buffer 的租金才退回并用于创建
+/// programdata，因此峰值为 max(buffer + 程序账户, 程序账户 + programdata) 的租金加上手续费。
+/// `final_rent` 为程序账户与 programdata 的租金之和；沿用已有 buffer 时 `buffer_rent` 为 0。
+fn required_balance(buffer_rent: u64, program_rent: u64, final_rent: u64, fee: u64) -> u64 {
+    (buffer_rent + program_rent).max(final_rent) + fee
+}
+
+/// BPF upgradeable loader 的指令构造，与 solana-loader-v3-interface 的 bincode 特性中的实现一致
+/// (该特性依赖的 solana-system-interface 1.x 与本工具使用的版本不兼容)
+mod loader_instruction {
+    use solana_loader_v3_interface::get_program_data_address;
+    use solana_loader_v3_interface::instruction::UpgradeableLoaderInstruction;
+    use solana_loader_v3_interface::state::UpgradeableLoaderState;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk_ids::{bpf_loader_upgradeable, system_program, sysvar};
+    use solana_system_interface::instruction as system_instruction;
+
+    fn instruction(
+        instruction: &UpgradeableLoaderInstruction,
+        accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        Instruction::new_with_bincode(bpf_loader_upgradeable::ID, instruction, accounts)
+    }
+
+    pub fn create_buffer(
+        payer: &Pubkey,
+        buffer: &Pubkey,
+        authority: &Pubkey,
+        lamports: u64,
+        program_len: usize,
+    ) -> Vec<Instruction> {
+        vec![
+            system_instruction::create_account(
+                payer,
+                buffer,
+                lamports,
+                UpgradeableLoaderState::size_of_buffer(program_len) as u64,
+                &bpf_loader_upgradeable::ID,
+            ),
+            instruction(
+                &UpgradeableLoaderInstruction::InitializeBuffer,
+                vec![
+                    AccountMeta::new(*buffer, false),
+                    AccountMeta::new_readonly(*authority, false),
+                ],
+            ),
+        ]
+    }
+
+    pub fn write(buffer: &Pubkey, authority: &Pubkey, offset: u32, bytes: Vec<u8>) -> Instruction {
+        instruction(
+            &UpgradeableLoaderInstruction::Write { offset, bytes },
+            vec![
+                AccountMeta::new(*buffer, false),
+                AccountMeta::new_readonly(*authority, true),
+            ],
+        )
+    }
+
+    pub fn deploy_with_max_program_len(
+        payer: &Pubkey,
+        program: &Pubkey,
+        buffer: &Pubkey,
+        upgrade_authority: &Pubkey,
+        program_lamports: u64,
+        max_data_len: usize,
+    ) -> Vec<Instruction> {
+        vec![
+            system_instruction::create_account(
+                payer,
+                program,
+                program_lamports,
+                UpgradeableLoaderState::size_of_program() as u64,
+                &bpf_loader_upgradeable::ID,
+            ),
+            instruction(
+                &UpgradeableLoaderInstruction::DeployWithMaxDataLen { max_data_len },
+                vec![
+                    AccountMeta::new(*payer, true),
+                    AccountMeta::new(get_program_data_address(program), false),
+                    AccountMeta::new(*program, false),
+                    AccountMeta::new(*buffer, false),
+                    AccountMeta::new_readonly(sysvar::rent::ID, false),
+                    AccountMeta::new_readonly(sysvar::clock::ID, false),
+                    AccountMeta::new_readonly(system_program::ID, false),
+                    AccountMeta::new_readonly(*upgrade_authority, true),
+                ],
+            ),
+        ]
+    }
+
+    pub fn upgrade(
+        program: &Pubkey,
+        buffer: &Pubkey,
+        authority: &Pubkey,
+        spill: &Pubkey,
+    ) -> Instruction {
+        instruction(
+            &UpgradeableLoaderInstruction::Upgrade,
+            vec![
+                AccountMeta::new(get_program_data_address(program), false),
+                AccountMeta::new(*program, false),
+                AccountMeta::new(*buffer, false),
+                AccountMeta::new(*spill, false),
+                AccountMeta::new_readonly(sysvar::rent::ID, false),
+                AccountMeta::new_readonly(sysvar::clock::ID, false),
+                AccountMeta::new_readonly(*authority, true),
+            ],
+        )
+    }
+
+    pub fn set_upgrade_authority(
+        program: &Pubkey,
+        authority: &Pubkey,
+        new_authority: Option<&Pubkey>,
+    ) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new(get_program_data_address(program), false),
+            AccountMeta::new_readonly(*authority, true),
+        ];
+        if let Some(new_authority) = new_authority {
+            accounts.push(AccountMeta::new_readonly(*new_authority, false));
+        }
+        instruction(&UpgradeableLoaderInstruction::SetAuthority, accounts)
+    }
+
+    pub fn set_upgrade_authority_checked(
+        program: &Pubkey,
+        authority: &Pubkey,
+        new_authority: &Pubkey,
+    ) -> Instruction {
+        instruction(
+            &UpgradeableLoaderInstruction::SetAuthorityChecked,
+            vec![
+                AccountMeta::new(get_program_data_address(program), false),
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(*new_authority, true),
+            ],
+        )
+    }
+
+    pub fn close(buffer: &Pubkey, recipient: &Pubkey, authority: &Pubkey) -> Instruction {
+        instruction(
+            &UpgradeableLoaderInstruction::Close,
+            vec![
+                AccountMeta::new(*buffer, false),
+                AccountMeta::new(*recipient, false),
+                AccountMeta::new_readonly(*authority, true),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_balance_covers_program_rent_before_buffer_refund() {
+        // buffer 租金大于 programdata 租金时，峰值出现在创建程序账户、buffer 尚未退回时
+        assert_eq!(required_balance(1_000, 100, 100 + 600, 10), 1_110);
+        // programdata 更大时，峰值为最终占用的租金
+        assert_eq!(required_balance(1_000, 100, 100 + 2_000, 10), 2_110);
+        // 沿用已有 buffer：其租金已经付过
+        assert_eq!(required_balance(0, 100, 100 + 2_000, 10), 2_110);
+        // upgrade：只需 buffer 的租金
+        assert_eq!(required_balance(1_000, 0, 0, 10), 1_010);
+    }
+}
@@ -0,0 +1,484 @@
+use clap::Args;
+use solana_account::Account;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::clock::Epoch;
+use solana_sdk::native_token::Sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signer::Signer;
+use solana_stake_interface::instruction as stake_instruction;
+use solana_stake_interface::stake_history::StakeHistory;
+use solana_stake_interface::state::{Authorized, Lockup, StakeStateV2};
+use solana_system_interface::instruction as system_instruction;
+
+use crate::command::transfer::SpendAmount;
+use crate::error::{CliError, CliResult};
+use crate::output::{
+    CliNewStakeAccount, CliParsedAccount, CliStakeAccount, CliStakeActivation, CliStakeReward,
+    CliTxResult,
+};
+use crate::parser::stake::parse_stake;
+use crate::transaction::{DurableNonce, TransactionArgs, send_instructions_with_nonce};
+
+/// reduce_stake_warmup_cooldown 特性：激活后每个 epoch 的预热 / 冷却比例由 25% 降为 9%
+const REDUCE_STAKE_WARMUP_COOLDOWN: Pubkey =
+    Pubkey::from_str_const("GwtDQBghCTBgmX2cpEGNPxTEBUTQRaDMGTr5qychdGMj");
+
+#[derive(Args)]
+pub struct LockupArgs {
+    /// 锁定期截止的 epoch，期间只有 custodian 能解除锁定提取
+    #[arg(long = "lockup-epoch", value_name = "EPOCH")]
+    pub epoch: Option<u64>,
+    /// 锁定期截止的 unix 时间戳
+    #[arg(long = "lockup-unix-timestamp", value_name = "UNIX_TIMESTAMP")]
+    pub unix_timestamp: Option<i64>,
+    /// 锁定期的托管人 (custodian)，默认为出资账户
+    #[arg(long, value_name = "PUBKEY")]
+    pub custodian: Option<Pubkey>,
+}
+
+impl LockupArgs {
+    fn lockup(&self, default_custodian: &Pubkey) -> Lockup {
+        Lockup {
+            unix_timestamp: self.unix_timestamp.unwrap_or_default(),
+            epoch: self.epoch.unwrap_or_default(),
+            custodian: self.custodian.unwrap_or(*default_custodian),
+        }
+    }
+}
+
+/// 创建并初始化 stake 账户；指定 `seed` 时 `stake_account` 作为 base，地址由 create_with_seed 派生
+#[allow(clippy::too_many_arguments)]
+pub async fn create_stake_account(
+    from: &dyn Signer,
+    fee_payer: &dyn Signer,
+    stake_account: &dyn Signer,
+    seed: Option<&str>,
+    amount: SpendAmount,
+    staker: Option<Pubkey>,
+    withdrawer: Option<Pubkey>,
+    lockup: &LockupArgs,
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliNewStakeAccount> {
+    let SpendAmount::Lamports(lamports) = amount else {
+        return Err(CliError::InvalidInput(
+            "创建 stake 账户需要明确的数量，不支持 ALL".to_string(),
+        ));
+    };
+    let minimum_balance = stake_rent_exemption(tx_args, rpc_client).await?;
+    if lamports < minimum_balance {
+        return Err(CliError::InvalidInput(format!(
+            "stake 账户至少需要 {} 以满足租金豁免",
+            Sol(minimum_balance)
+        )));
+    }
+
+    let from_pubkey = from.pubkey();
+    let authorized = Authorized {
+        staker: staker.unwrap_or(from_pubkey),
+        withdrawer: withdrawer.unwrap_or(from_pubkey),
+    };
+    let lockup = lockup.lockup(&from_pubkey);
+    let (stake_address, instructions) = match seed {
+        Some(seed) => {
+            let address = derive_stake_address(&stake_account.pubkey(), seed)?;
+            let instructions = stake_instruction::create_account_with_seed(
+                &from_pubkey,
+                &address,
+                &stake_account.pubkey(),
+                seed,
+                &authorized,
+                &lockup,
+                lamports,
+            );
+            (address, instructions)
+        }
+        None => {
+            let address = stake_account.pubkey();
+            let instructions = stake_instruction::create_account(
+                &from_pubkey,
+                &address,
+                &authorized,
+                &lockup,
+                lamports,
+            );
+            (address, instructions)
+        }
+    };
+    let result = send_instructions_with_nonce(
+        &instructions,
+        fee_payer,
+        &[fee_payer, from, stake_account],
+        nonce,
+        tx_args,
+        rpc_client,
+    )
+    .await?;
+    Ok(CliNewStakeAccount {
+        stake_account: stake_address.to_string(),
+        result,
+    })
+}
+
+/// 将 stake 账户委托给投票账户
+pub async fn delegate_stake(
+    fee_payer: &dyn Signer,
+    stake_account: &Pubkey,
+    vote_account: &Pubkey,
+    stake_authority: &dyn Signer,
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    if !tx_args.sign_only {
+        let vote = rpc_client
+            .get_account_with_commitment(vote_account, rpc_client.commitment())
+            .await?
+            .value;
+        match vote {
+            Some(vote) if vote.owner == solana_sdk_ids::vote::ID => {}
+            Some(vote) => {
+                return Err(CliError::InvalidInput(format!(
+                    "{} 不是投票账户 (owner 为 {})",
+                    vote_account, vote.owner
+                )));
+            }
+            None => {
+                return Err(CliError::InvalidInput(format!(
+                    "投票账户 {} 不存在",
+                    vote_account
+                )));
+            }
+        }
+    }
+
+    let instruction =
+        stake_instruction::delegate_stake(stake_account, &stake_authority.pubkey(), vote_account);
+    send_instructions_with_nonce(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, stake_authority],
+        nonce,
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 取消委托，stake 在当前 epoch 结束后开始冷却
+pub async fn deactivate_stake(
+    fee_payer: &dyn Signer,
+    stake_account: &Pubkey,
+    stake_authority: &dyn Signer,
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let instruction = stake_instruction::deactivate_stake(stake_account, &stake_authority.pubkey());
+    send_instructions_with_nonce(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, stake_authority],
+        nonce,
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 从 stake 账户提取未委托 (或已完全冷却) 的 SOL，`ALL` 会提取全部余额并关闭该账户
+#[allow(clippy::too_many_arguments)]
+pub async fn withdraw_stake(
+    fee_payer: &dyn Signer,
+    stake_account: &Pubkey,
+    destination: &Pubkey,
+    amount: SpendAmount,
+    withdraw_authority: &dyn Signer,
+    custodian: Option<&dyn Signer>,
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let lamports = match amount {
+        SpendAmount::Lamports(lamports) => lamports,
+        SpendAmount::All if tx_args.sign_only => {
+            return Err(CliError::InvalidInput(
+                "--sign-only 模式下无法查询余额，不支持 ALL".to_string(),
+            ));
+        }
+        SpendAmount::All => {
+            get_stake_account(rpc_client, stake_account)
+                .await?
+                .0
+                .lamports
+        }
+    };
+
+    let custodian_pubkey = custodian.map(|custodian| custodian.pubkey());
+    let instruction = stake_instruction::withdraw(
+        stake_account,
+        &withdraw_authority.pubkey(),
+        destination,
+        lamports,
+        custodian_pubkey.as_ref(),
+    );
+    let mut signers = vec![fee_payer, withdraw_authority];
+    signers.extend(custodian);
+    send_instructions_with_nonce(
+        &[instruction],
+        fee_payer,
+        &signers,
+        nonce,
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 从 stake 账户拆分出 `amount` 到新账户；新账户不足租金豁免的部分由手续费账户补足
+#[allow(clippy::too_many_arguments)]
+pub async fn split_stake(
+    fee_payer: &dyn Signer,
+    stake_account: &Pubkey,
+    split_stake_account: &dyn Signer,
+    seed: Option<&str>,
+    amount: SpendAmount,
+    stake_authority: &dyn Signer,
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliNewStakeAccount> {
+    let SpendAmount::Lamports(lamports) = amount else {
+        return Err(CliError::InvalidInput(
+            "拆分 stake 账户需要明确的数量，不支持 ALL".to_string(),
+        ));
+    };
+    let split_address = match seed {
+        Some(seed) => derive_stake_address(&split_stake_account.pubkey(), seed)?,
+        None => split_stake_account.pubkey(),
+    };
+
+    // 新账户必须预先满足租金豁免；离线签名时无法查询余额，按空账户补足
+    let minimum_balance = stake_rent_exemption(tx_args, rpc_client).await?;
+    let current_balance = if tx_args.sign_only {
+        0
+    } else {
+        rpc_client.get_balance(&split_address).await?
+    };
+
+    let mut instructions = Vec::new();
+    let shortfall = minimum_balance.saturating_sub(current_balance);
+    if shortfall > 0 {
+        eprintln!(
+            "Funding {} with {} for rent exemption",
+            split_address,
+            Sol(shortfall)
+        );
+        instructions.push(system_instruction::transfer(
+            &fee_payer.pubkey(),
+            &split_address,
+            shortfall,
+        ));
+    }
+    instructions.extend(match seed {
+        Some(seed) => stake_instruction::split_with_seed(
+            stake_account,
+            &stake_authority.pubkey(),
+            lamports,
+            &split_address,
+            &split_stake_account.pubkey(),
+            seed,
+        ),
+        None => stake_instruction::split(
+            stake_account,
+            &stake_authority.pubkey(),
+            lamports,
+            &split_address,
+        ),
+    });
+
+    let result = send_instructions_with_nonce(
+        &instructions,
+        fee_payer,
+        &[fee_payer, stake_authority, split_stake_account],
+        nonce,
+        tx_args,
+        rpc_client,
+    )
+    .await?;
+    Ok(CliNewStakeAccount {
+        stake_account: split_address.to_string(),
+        result,
+    })
+}
+
+/// 将 `source_stake_account` 合并进 `stake_account`，合并后源账户被关闭
+pub async fn merge_stake(
+    fee_payer: &dyn Signer,
+    stake_account: &Pubkey,
+    source_stake_account: &Pubkey,
+    stake_authority: &dyn Signer,
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    if stake_account == source_stake_account {
+        return Err(CliError::InvalidInput(
+            "不能将 stake 账户合并到它自身".to_string(),
+        ));
+    }
+    let instructions = stake_instruction::merge(
+        stake_account,
+        source_stake_account,
+        &stake_authority.pubkey(),
+    );
+    send_instructions_with_nonce(
+        &instructions,
+        fee_payer,
+        &[fee_payer, stake_authority],
+        nonce,
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 查询 stake 账户：授权者、锁定期、委托，以及按 stake history 计算的当前激活状态；
+/// `num_rewards_epochs` 大于 0 时附带最近若干 epoch 的通胀奖励
+pub async fn show_stake_account(
+    address: &Pubkey,
+    num_rewards_epochs: u64,
+    rpc_client: &RpcClient,
+) -> CliResult<CliStakeAccount> {
+    let (account, state) = get_stake_account(rpc_client, address).await?;
+    let Some(CliParsedAccount::Stake(stake)) = parse_stake(&account.owner, &account.data) else {
+        return Err(CliError::InvalidInput(format!(
+            "stake 账户 {} 尚未初始化",
+            address
+        )));
+    };
+
+    let epoch_info = rpc_client.get_epoch_info().await?;
+    let activation = match state.delegation() {
+        Some(delegation) => {
+            let history_account = rpc_client
+                .get_account(&solana_sdk_ids::sysvar::stake_history::ID)
+                .await?;
+            let history: StakeHistory =
+                bincode::deserialize(&history_account.data).map_err(|err| {
+                    CliError::Other(anyhow::anyhow!("无法解析 stake history sysvar: {}", err))
+                })?;
+            let status = delegation.stake_activating_and_deactivating(
+                epoch_info.epoch,
+                &history,
+                new_rate_activation_epoch(rpc_client).await?,
+            );
+            let state = if status.deactivating > 0 {
+                "deactivating"
+            } else if status.activating > 0 {
+                "activating"
+            } else if status.effective > 0 {
+                "active"
+            } else {
+                "inactive"
+            };
+            Some(CliStakeActivation {
+                epoch: epoch_info.epoch,
+                state: state.to_string(),
+                active_stake: status.effective,
+                activating_stake: status.activating,
+                deactivating_stake: status.deactivating,
+            })
+        }
+        None => None,
+    };
+
+    // epoch N 的奖励在 epoch N + 1 开始时发放，因此从上一个 epoch 往前查询
+    let mut rewards = Vec::new();
+    let first_epoch = epoch_info.epoch.saturating_sub(num_rewards_epochs);
+    for epoch in (first_epoch..epoch_info.epoch).rev() {
+        let reward = rpc_client
+            .get_inflation_reward(&[*address], Some(epoch))
+            .await?
+            .into_iter()
+            .next()
+            .flatten();
+        if let Some(reward) = reward {
+            let previous_balance = reward.post_balance.saturating_sub(reward.amount);
+            rewards.push(CliStakeReward {
+                epoch: reward.epoch,
+                effective_slot: reward.effective_slot,
+                amount: reward.amount,
+                post_balance: reward.post_balance,
+                percent_change: if previous_balance == 0 {
+                    0.0
+                } else {
+                    reward.amount as f64 * 100.0 / previous_balance as f64
+                },
+                commission: reward.commission,
+            });
+        }
+    }
+
+    Ok(CliStakeAccount {
+        address: address.to_string(),
+        lamports: account.lamports,
+        stake,
+        activation,
+        rewards,
+    })
+}
+
+/// 读取并解码 stake 账户
+async fn get_stake_account(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> CliResult<(Account, StakeStateV2)> {
+    let account = rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?
+        .value
+        .ok_or_else(|| CliError::InvalidInput(format!("stake 账户 {} 不存在", address)))?;
+    if account.owner != solana_sdk_ids::stake::ID {
+        return Err(CliError::InvalidInput(format!(
+            "{} 不是 stake 账户 (owner 为 {})",
+            address, account.owner
+        )));
+    }
+    let state = bincode::deserialize(&account.data).map_err(|err| {
+        CliError::InvalidInput(format!("无法解析 stake 账户 {}: {}", address, err))
+    })?;
+    Ok((account, state))
+}
+
+/// stake 账户的租金豁免最低余额，离线签名时按默认租金参数计算
+async fn stake_rent_exemption(tx_args: &TransactionArgs, rpc_client: &RpcClient) -> CliResult<u64> {
+    if tx_args.sign_only {
+        Ok(Rent::default().minimum_balance(StakeStateV2::size_of()))
+    } else {
+        Ok(rpc_client
+            .get_minimum_balance_for_rent_exemption(StakeStateV2::size_of())
+            .await?)
+    }
+}
+
+fn derive_stake_address(base: &Pubkey, seed: &str) -> CliResult<Pubkey> {
+    Pubkey::create_with_seed(base, seed, &solana_sdk_ids::stake::ID)
+        .map_err(|err| CliError::InvalidInput(format!("无效的 seed {}: {}", seed, err)))
+}
+
+/// reduce_stake_warmup_cooldown 特性生效的 epoch，未激活时为 None
+async fn new_rate_activation_epoch(rpc_client: &RpcClient) -> CliResult<Option<Epoch>> {
+    let activated_slot = rpc_client
+        .get_account_with_commitment(&REDUCE_STAKE_WARMUP_COOLDOWN, rpc_client.commitment())
+        .await?
+        .value
+        .and_then(|account| bincode::deserialize::<Option<u64>>(&account.data).ok())
+        .flatten();
+    match activated_slot {
+        Some(slot) => Ok(Some(rpc_client.get_epoch_schedule().await?.get_epoch(slot))),
+        None => Ok(None),
+    }
+}
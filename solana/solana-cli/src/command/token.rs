@@ -0,0 +1,533 @@
+use clap::Args;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use spl_associated_token_account_client::address::get_associated_token_address_with_program_id;
+use spl_associated_token_account_client::instruction::create_associated_token_account_idempotent;
+use spl_token::solana_program::instruction::Instruction as SplInstruction;
+use spl_token::solana_program::program_error::ProgramError;
+use spl_token::solana_program::pubkey::Pubkey as SplPubkey;
+use spl_token::{ID as TOKEN_PROGRAM_ID, amount_to_ui_amount_string_trimmed};
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+use spl_token_2022::{ID as TOKEN_2022_PROGRAM_ID, instruction};
+
+use crate::error::{CliError, CliResult};
+use crate::output::{CliTokenAccount, CliTxResult};
+use crate::transaction::{TransactionArgs, send_instructions};
+
+/// 离线签名时描述 mint 的参数；联网时从链上读取 mint 并校验
+#[derive(Args, Clone, Copy, Debug, Default)]
+pub struct MintArgs {
+    /// mint 的精度，离线签名时必须指定
+    #[arg(long = "mint-decimals", value_name = "DECIMALS")]
+    pub mint_decimals: Option<u8>,
+    /// mint 属于 Token-2022 程序；联网时按 mint 的 owner 自动识别，离线签名时需显式指定
+    #[arg(long = "program-2022")]
+    pub program_2022: bool,
+}
+
+/// mint 所属的代币程序与精度
+pub struct MintInfo {
+    pub program_id: Pubkey,
+    pub decimals: Option<u8>,
+}
+
+impl MintInfo {
+    pub fn decimals(&self) -> CliResult<u8> {
+        self.decimals.ok_or_else(|| {
+            CliError::InvalidInput(
+                "--sign-only 模式下无法查询 mint，请通过 --mint-decimals 指定精度".to_string(),
+            )
+        })
+    }
+}
+
+/// 代币数量：按 mint 精度解析的 UI 数量 (如 1.5)，或 `ALL` (代币账户的全部余额)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenAmount {
+    Ui(String),
+    All,
+}
+
+impl TokenAmount {
+    pub fn parse(amount: &str) -> Self {
+        if amount == "ALL" {
+            TokenAmount::All
+        } else {
+            TokenAmount::Ui(amount.to_string())
+        }
+    }
+
+    /// 转换为最小单位的原始数量，`ALL` 需要提供代币账户当前余额
+    pub fn to_raw(&self, decimals: u8, balance: Option<u64>) -> CliResult<u64> {
+        let amount = match self {
+            TokenAmount::Ui(amount) => {
+                spl_token::try_ui_amount_into_amount(amount.clone(), decimals).map_err(|_| {
+                    CliError::InvalidInput(format!(
+                        "无效的代币数量: {} (该代币最多支持 {} 位小数)",
+                        amount, decimals
+                    ))
+                })?
+            }
+            TokenAmount::All => balance.ok_or_else(|| {
+                CliError::InvalidInput("--sign-only 模式下无法查询余额，不支持 ALL".to_string())
+            })?,
+        };
+        if amount == 0 {
+            return Err(CliError::InvalidInput("代币数量必须大于 0".to_string()));
+        }
+        Ok(amount)
+    }
+}
+
+/// spl-token 仍使用旧版 solana_program 类型，与 solana-sdk 之间按字节转换
+pub fn spl_pubkey(pubkey: &Pubkey) -> SplPubkey {
+    SplPubkey::new_from_array(pubkey.to_bytes())
+}
+
+pub fn to_sdk_instruction(instruction: SplInstruction) -> Instruction {
+    Instruction {
+        program_id: instruction.program_id.to_bytes().into(),
+        accounts: instruction
+            .accounts
+            .into_iter()
+            .map(|account| AccountMeta {
+                pubkey: account.pubkey.to_bytes().into(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: instruction.data,
+    }
+}
+
+/// 转换 spl 指令构造函数的结果，参数错误 (如无效的程序 ID) 视为输入错误
+pub fn checked_instruction(
+    instruction: Result<SplInstruction, ProgramError>,
+) -> CliResult<Instruction> {
+    instruction
+        .map(to_sdk_instruction)
+        .map_err(|err| CliError::InvalidInput(err.to_string()))
+}
+
+/// 钱包地址在该 mint 下的关联代币账户 (ATA)，地址与 mint 所属的代币程序有关
+pub fn associated_token_address(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Pubkey {
+    get_associated_token_address_with_program_id(
+        &spl_pubkey(owner),
+        &spl_pubkey(mint),
+        &spl_pubkey(token_program_id),
+    )
+    .to_bytes()
+    .into()
+}
+
+pub fn create_associated_token_account_instruction(
+    fee_payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    to_sdk_instruction(create_associated_token_account_idempotent(
+        &spl_pubkey(fee_payer),
+        &spl_pubkey(owner),
+        &spl_pubkey(mint),
+        &spl_pubkey(token_program_id),
+    ))
+}
+
+/// 读取 mint 所属的代币程序与精度；离线签名时改用 `mint_args` 提供的值
+pub async fn get_mint(
+    mint: &Pubkey,
+    mint_args: &MintArgs,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<MintInfo> {
+    if tx_args.sign_only {
+        return Ok(MintInfo {
+            program_id: if mint_args.program_2022 {
+                TOKEN_2022_PROGRAM_ID
+            } else {
+                TOKEN_PROGRAM_ID
+            }
+            .to_bytes()
+            .into(),
+            decimals: mint_args.mint_decimals,
+        });
+    }
+
+    let account = rpc_client.get_account(mint).await?;
+    let owner = spl_pubkey(&account.owner);
+    if owner != TOKEN_PROGRAM_ID && owner != TOKEN_2022_PROGRAM_ID {
+        return Err(CliError::InvalidInput(format!(
+            "{} 不是 SPL Token mint (owner: {})",
+            mint, account.owner
+        )));
+    }
+    let decimals = StateWithExtensions::<Mint>::unpack(&account.data)
+        .map_err(|err| CliError::InvalidInput(format!("无法解析 mint {}: {}", mint, err)))?
+        .base
+        .decimals;
+    if let Some(expected) = mint_args.mint_decimals
+        && expected != decimals
+    {
+        return Err(CliError::InvalidInput(format!(
+            "--mint-decimals {} 与 mint {} 的实际精度 {} 不一致",
+            expected, mint, decimals
+        )));
+    }
+    Ok(MintInfo {
+        program_id: account.owner,
+        decimals: Some(decimals),
+    })
+}
+
+/// 读取代币账户的余额；离线签名时返回 None
+pub async fn get_token_balance(
+    token_account: &Pubkey,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<Option<u64>> {
+    if tx_args.sign_only {
+        return Ok(None);
+    }
+    let account = rpc_client
+        .get_account_with_commitment(token_account, rpc_client.commitment())
+        .await?
+        .value
+        .ok_or_else(|| CliError::InvalidInput(format!("代币账户 {} 不存在", token_account)))?;
+    let state = StateWithExtensions::<TokenAccount>::unpack(&account.data).map_err(|err| {
+        CliError::InvalidInput(format!("无法解析代币账户 {}: {}", token_account, err))
+    })?;
+    Ok(Some(state.base.amount))
+}
+
+fn check_balance(
+    token_account: &Pubkey,
+    amount: u64,
+    balance: Option<u64>,
+    decimals: u8,
+) -> CliResult<()> {
+    match balance {
+        Some(balance) if amount > balance => Err(CliError::InsufficientFunds(format!(
+            "代币账户 {} 需要 {}，当前余额 {}",
+            token_account,
+            amount_to_ui_amount_string_trimmed(amount, decimals),
+            amount_to_ui_amount_string_trimmed(balance, decimals)
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// 为 `owner` 创建该 mint 的关联代币账户 (已存在时不报错)
+pub async fn create_token_account(
+    fee_payer: &dyn Signer,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    program_2022: bool,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTokenAccount> {
+    let mint_args = MintArgs {
+        program_2022,
+        ..Default::default()
+    };
+    let mint_info = get_mint(mint, &mint_args, tx_args, rpc_client).await?;
+    let account = associated_token_address(owner, mint, &mint_info.program_id);
+    let instruction = create_associated_token_account_instruction(
+        &fee_payer.pubkey(),
+        owner,
+        mint,
+        &mint_info.program_id,
+    );
+    Ok(CliTokenAccount {
+        account: account.to_string(),
+        result: send_instructions(&[instruction], fee_payer, &[fee_payer], tx_args, rpc_client)
+            .await?,
+    })
+}
+
+/// 向 `recipient` 的关联代币账户增发代币，`fund_recipient` 时先创建该账户
+#[allow(clippy::too_many_arguments)]
+pub async fn mint_to(
+    fee_payer: &dyn Signer,
+    mint_authority: &dyn Signer,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    amount: &TokenAmount,
+    mint_args: &MintArgs,
+    fund_recipient: bool,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let mint_info = get_mint(mint, mint_args, tx_args, rpc_client).await?;
+    let decimals = mint_info.decimals()?;
+    if *amount == TokenAmount::All {
+        return Err(CliError::InvalidInput("增发代币不支持 ALL".to_string()));
+    }
+    let amount = amount.to_raw(decimals, None)?;
+    let token_program_id = mint_info.program_id;
+    let destination = associated_token_address(recipient, mint, &token_program_id);
+
+    eprintln!(
+        "Minting {} tokens of {} to {}",
+        amount_to_ui_amount_string_trimmed(amount, decimals),
+        mint,
+        destination
+    );
+
+    let mut instructions = Vec::new();
+    if fund_recipient {
+        instructions.push(create_associated_token_account_instruction(
+            &fee_payer.pubkey(),
+            recipient,
+            mint,
+            &token_program_id,
+        ));
+    }
+    instructions.push(checked_instruction(instruction::mint_to_checked(
+        &spl_pubkey(&token_program_id),
+        &spl_pubkey(mint),
+        &spl_pubkey(&destination),
+        &spl_pubkey(&mint_authority.pubkey()),
+        &[],
+        amount,
+        decimals,
+    ))?);
+    send_instructions(
+        &instructions,
+        fee_payer,
+        &[fee_payer, mint_authority],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 从 `owner` 的关联代币账户向 `recipient` 的关联代币账户转账
+#[allow(clippy::too_many_arguments)]
+pub async fn transfer_token(
+    fee_payer: &dyn Signer,
+    owner: &dyn Signer,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    amount: &TokenAmount,
+    mint_args: &MintArgs,
+    fund_recipient: bool,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let mint_info = get_mint(mint, mint_args, tx_args, rpc_client).await?;
+    let decimals = mint_info.decimals()?;
+    let token_program_id = mint_info.program_id;
+    let source = associated_token_address(&owner.pubkey(), mint, &token_program_id);
+    let destination = associated_token_address(recipient, mint, &token_program_id);
+    let balance = get_token_balance(&source, tx_args, rpc_client).await?;
+    let amount = amount.to_raw(decimals, balance)?;
+    check_balance(&source, amount, balance, decimals)?;
+
+    eprintln!(
+        "Transferring {} tokens of {} from {} to {}",
+        amount_to_ui_amount_string_trimmed(amount, decimals),
+        mint,
+        source,
+        destination
+    );
+
+    let mut instructions = Vec::new();
+    if fund_recipient {
+        instructions.push(create_associated_token_account_instruction(
+            &fee_payer.pubkey(),
+            recipient,
+            mint,
+            &token_program_id,
+        ));
+    }
+    instructions.push(checked_instruction(instruction::transfer_checked(
+        &spl_pubkey(&token_program_id),
+        &spl_pubkey(&source),
+        &spl_pubkey(mint),
+        &spl_pubkey(&destination),
+        &spl_pubkey(&owner.pubkey()),
+        &[],
+        amount,
+        decimals,
+    ))?);
+    send_instructions(
+        &instructions,
+        fee_payer,
+        &[fee_payer, owner],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 销毁 `owner` 关联代币账户中的代币
+pub async fn burn(
+    fee_payer: &dyn Signer,
+    owner: &dyn Signer,
+    mint: &Pubkey,
+    amount: &TokenAmount,
+    mint_args: &MintArgs,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let mint_info = get_mint(mint, mint_args, tx_args, rpc_client).await?;
+    let decimals = mint_info.decimals()?;
+    let token_program_id = mint_info.program_id;
+    let source = associated_token_address(&owner.pubkey(), mint, &token_program_id);
+    let balance = get_token_balance(&source, tx_args, rpc_client).await?;
+    let amount = amount.to_raw(decimals, balance)?;
+    check_balance(&source, amount, balance, decimals)?;
+
+    eprintln!(
+        "Burning {} tokens of {} from {}",
+        amount_to_ui_amount_string_trimmed(amount, decimals),
+        mint,
+        source
+    );
+
+    let instruction = checked_instruction(instruction::burn_checked(
+        &spl_pubkey(&token_program_id),
+        &spl_pubkey(&source),
+        &spl_pubkey(mint),
+        &spl_pubkey(&owner.pubkey()),
+        &[],
+        amount,
+        decimals,
+    ))?;
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, owner],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 关闭余额为 0 的关联代币账户，租金退回 `destination` (默认为账户所有者)
+pub async fn close_token_account(
+    fee_payer: &dyn Signer,
+    owner: &dyn Signer,
+    mint: &Pubkey,
+    destination: Option<Pubkey>,
+    program_2022: bool,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let mint_args = MintArgs {
+        program_2022,
+        ..Default::default()
+    };
+    let token_program_id = get_mint(mint, &mint_args, tx_args, rpc_client)
+        .await?
+        .program_id;
+    let account = associated_token_address(&owner.pubkey(), mint, &token_program_id);
+    if let Some(balance) = get_token_balance(&account, tx_args, rpc_client).await?
+        && balance > 0
+    {
+        return Err(CliError::InvalidInput(format!(
+            "代币账户 {} 仍有 {} 个最小单位的余额，请先转出或销毁",
+            account, balance
+        )));
+    }
+    let destination = destination.unwrap_or_else(|| owner.pubkey());
+
+    let instruction = checked_instruction(instruction::close_account(
+        &spl_pubkey(&token_program_id),
+        &spl_pubkey(&account),
+        &spl_pubkey(&destination),
+        &spl_pubkey(&owner.pubkey()),
+        &[],
+    ))?;
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, owner],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 授权 `delegate` 从 `owner` 的关联代币账户中最多转出 `amount`
+#[allow(clippy::too_many_arguments)]
+pub async fn approve(
+    fee_payer: &dyn Signer,
+    owner: &dyn Signer,
+    mint: &Pubkey,
+    delegate: &Pubkey,
+    amount: &TokenAmount,
+    mint_args: &MintArgs,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let mint_info = get_mint(mint, mint_args, tx_args, rpc_client).await?;
+    let decimals = mint_info.decimals()?;
+    let token_program_id = mint_info.program_id;
+    let source = associated_token_address(&owner.pubkey(), mint, &token_program_id);
+    let balance = match amount {
+        TokenAmount::All => get_token_balance(&source, tx_args, rpc_client).await?,
+        TokenAmount::Ui(_) => None,
+    };
+    let amount = amount.to_raw(decimals, balance)?;
+
+    let instruction = checked_instruction(instruction::approve_checked(
+        &spl_pubkey(&token_program_id),
+        &spl_pubkey(&source),
+        &spl_pubkey(mint),
+        &spl_pubkey(delegate),
+        &spl_pubkey(&owner.pubkey()),
+        &[],
+        amount,
+        decimals,
+    ))?;
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, owner],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
+
+/// 撤销 `owner` 关联代币账户上的委托
+pub async fn revoke(
+    fee_payer: &dyn Signer,
+    owner: &dyn Signer,
+    mint: &Pubkey,
+    program_2022: bool,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let mint_args = MintArgs {
+        program_2022,
+        ..Default::default()
+    };
+    let token_program_id = get_mint(mint, &mint_args, tx_args, rpc_client)
+        .await?
+        .program_id;
+    let source = associated_token_address(&owner.pubkey(), mint, &token_program_id);
+    let instruction = checked_instruction(instruction::revoke(
+        &spl_pubkey(&token_program_id),
+        &spl_pubkey(&source),
+        &spl_pubkey(&owner.pubkey()),
+        &[],
+    ))?;
+    send_instructions(
+        &[instruction],
+        fee_payer,
+        &[fee_payer, owner],
+        tx_args,
+        rpc_client,
+    )
+    .await
+}
@@ -1,4 +1,9 @@
-use std::{cmp::Ordering, collections::HashMap, env, str::FromStr};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::HashMap,
+    env,
+    str::FromStr,
+};
 
 use anyhow::{Context, Result, anyhow};
 use helius::{
@@ -10,10 +15,13 @@ use reqwest::Client;
 use serde::Deserialize;
 use solana_client::nonblocking::rpc_client::RpcClient as SolanaRpcClient;
 use solana_sdk::pubkey::Pubkey;
-use spl_token::{
-    solana_program::program_pack::Pack,
-    state::{Account as TokenAccountState, Mint},
+
+use crate::error::{CliError, CliResult};
+use crate::output::{
+    CliHolderOtherTokens, CliOtherToken, CliParsedMint, CliTokenAnalysis, CliTokenExtension,
+    CliTokenHolder,
 };
+use crate::parser::token::{parse_token_account, parse_token_mint};
 
 const DEFAULT_CLUSTER: Cluster = Cluster::MainnetBeta;
 const JUPITER_PRICE_ENDPOINT: &str = "https://price.jup.ag/v4/price";
@@ -33,16 +41,6 @@ impl AggregatedHolder {
         let factor = 10f64.powi(decimals as i32);
         (self.total_raw as f64) / factor
     }
-
-    fn primary_account(&self) -> Option<&str> {
-        self.token_accounts
-            .first()
-            .map(|snapshot| snapshot.token_account.as_str())
-    }
-
-    fn account_count(&self) -> usize {
-        self.token_accounts.len()
-    }
 }
 
 struct HolderToken {
@@ -104,23 +102,15 @@ impl PriceFetcher {
         let price = match response {
             Ok(resp) => {
                 if resp.status().is_success() {
-                    let payload: JupiterPriceResponse = resp
-                        .json()
-                        .await
-                        .context("解析 Jupiter price 响应失败")?;
-                    payload
-                        .data
-                        .get(mint)
-                        .and_then(|entry| entry.price)
+                    let payload: JupiterPriceResponse =
+                        resp.json().await.context("解析 Jupiter price 响应失败")?;
+                    payload.data.get(mint).and_then(|entry| entry.price)
                 } else {
                     None
                 }
             }
             Err(err) => {
-                println!(
-                    "    › Jupiter price 请求失败 ({}): {}",
-                    mint, err
-                );
+                eprintln!("    › Jupiter price 请求失败 ({}): {}", mint, err);
                 None
             }
         };
@@ -148,6 +138,7 @@ struct JupiterPriceEntry {
 }
 
 /// 使用 Helius Rust SDK (RPC) 分析指定 SPL 代币
+#[allow(clippy::too_many_arguments)]
 pub async fn analyze_token(
     mint: &str,
     api_key: Option<String>,
@@ -158,11 +149,13 @@ pub async fn analyze_token(
     transfer_limit: usize,
     holders_only: bool,
     solana_rpc: &SolanaRpcClient,
-) -> Result<()> {
+) -> CliResult<CliTokenAnalysis> {
     let api_key = resolve_api_key(api_key)?;
-    let mint_pubkey = Pubkey::from_str(mint).context("无效的代币 mint 地址")?;
+    let mint_pubkey = Pubkey::from_str(mint)
+        .map_err(|err| CliError::InvalidInput(format!("无效的代币 mint 地址 {}: {}", mint, err)))?;
 
-    let decimals = fetch_mint_decimals(solana_rpc, &mint_pubkey).await?;
+    let mint_state = fetch_mint(solana_rpc, &mint_pubkey).await?;
+    let decimals = mint_state.decimals;
     let supply_info = solana_rpc.get_token_supply(&mint_pubkey).await.ok();
 
     let helius = Helius::new_with_async_solana(api_key.as_str(), DEFAULT_CLUSTER)
@@ -174,7 +167,7 @@ pub async fn analyze_token(
     decimals_cache.insert(mint.to_string(), decimals);
     let mut price_fetcher = PriceFetcher::new()?;
 
-    let mint_metadata =
+    let mut mint_metadata =
         match get_or_fetch_metadata(mint, &mut metadata_cache, helius_rpc.as_ref()).await {
             Ok(meta) => {
                 if let Some(meta_decimals) = meta.decimals {
@@ -185,11 +178,27 @@ pub async fn analyze_token(
                 meta
             }
             Err(err) => {
-                println!("提示：获取代币元数据失败 ({})，将仅显示 Mint 地址。", err);
+                eprintln!("提示：获取代币元数据失败 ({})，将仅显示 Mint 地址。", err);
                 TokenMetadata::default()
             }
         };
-    let base_label = mint_metadata.label(mint);
+    // Token-2022 的 metadata 扩展直接存放在链上，Helius 未返回名称时以此为准
+    for extension in &mint_state.extensions {
+        if let CliTokenExtension::TokenMetadata { name, symbol, .. } = extension {
+            mint_metadata.name.get_or_insert_with(|| name.clone());
+            mint_metadata.symbol.get_or_insert_with(|| symbol.clone());
+        }
+    }
+
+    let mut analysis = CliTokenAnalysis {
+        mint: mint.to_string(),
+        label: mint_metadata.label(mint),
+        program: mint_state.program,
+        decimals,
+        supply_ui: supply_info.and_then(|supply| supply.ui_amount),
+        extensions: mint_state.extensions,
+        ..Default::default()
+    };
 
     let largest_accounts = solana_rpc
         .get_token_largest_accounts(&mint_pubkey)
@@ -197,8 +206,10 @@ pub async fn analyze_token(
         .context("获取代币最大持有人失败")?;
 
     if largest_accounts.is_empty() {
-        println!("RPC 未返回任何代币账户，可能该代币暂无持仓或 mint 地址无效。");
-        return Ok(());
+        analysis
+            .notes
+            .push("RPC 未返回任何代币账户，可能该代币暂无持仓或 mint 地址无效。".to_string());
+        return Ok(analysis);
     }
 
     let mut account_pubkeys = Vec::new();
@@ -211,8 +222,10 @@ pub async fn analyze_token(
     }
 
     if account_pubkeys.is_empty() {
-        println!("未能解析 RPC 返回的代币账户地址。");
-        return Ok(());
+        analysis
+            .notes
+            .push("未能解析 RPC 返回的代币账户地址。".to_string());
+        return Ok(analysis);
     }
 
     let account_infos = solana_rpc
@@ -223,13 +236,13 @@ pub async fn analyze_token(
     let mut aggregated_map: HashMap<String, AggregatedHolder> = HashMap::new();
     for ((balance, pubkey), account_opt) in account_balances
         .into_iter()
-        .zip(account_pubkeys.into_iter())
-        .zip(account_infos.into_iter())
+        .zip(account_pubkeys)
+        .zip(account_infos)
     {
         let Some(account) = account_opt else { continue };
-        let token_account = TokenAccountState::unpack(&account.data)
-            .map_err(|_| anyhow!("解析代币账户 {} 失败", pubkey))?;
-        let owner_key = token_account.owner.to_string();
+        let token_account = parse_token_account(&account.owner, &account.data)
+            .ok_or_else(|| anyhow!("解析代币账户 {} 失败", pubkey))?;
+        let owner_key = token_account.owner;
         let raw_amount = balance.amount.amount.parse::<u128>().unwrap_or_default();
         if raw_amount == 0 {
             continue;
@@ -249,56 +262,45 @@ pub async fn analyze_token(
     }
 
     if aggregated_map.is_empty() {
-        println!("未能汇总出有效的持有人数据。");
-        return Ok(());
+        analysis
+            .notes
+            .push("未能汇总出有效的持有人数据。".to_string());
+        return Ok(analysis);
     }
 
     let mut holders = aggregated_map.into_values().collect::<Vec<_>>();
-    holders.sort_by(|a, b| b.total_raw.cmp(&a.total_raw));
+    holders.sort_by_key(|holder| Reverse(holder.total_raw));
 
     let display_count = holders.len().min(top_holders.max(1));
-
-    println!(
-        "=== {} ({}) 的持有人清单 (前 {} 名 / 共 {} 名) ===",
-        base_label,
-        mint,
-        display_count,
-        holders.len()
-    );
-    println!("代币精度: {} 位小数", decimals);
-    if let Some(supply) = supply_info {
-        if let Some(ui_amount) = supply.ui_amount {
-            println!("链上报告的总供应量: {:.6}", ui_amount);
-        }
-    }
+    analysis.holder_count = holders.len();
 
     let mut total_raw = 0u128;
     for (idx, holder) in holders.iter().enumerate().take(display_count) {
         total_raw += holder.total_raw;
-        let account_hint = holder
-            .primary_account()
-            .unwrap_or("<unknown-token-account>");
-        println!(
-            "{:>3}. {} 持有 {:.6} 枚 (共 {} 个代币账户，示例: {})",
-            idx + 1,
-            holder.owner,
-            holder.total_ui(decimals),
-            holder.account_count(),
-            account_hint
-        );
+        analysis.holders.push(CliTokenHolder {
+            rank: idx + 1,
+            owner: holder.owner.clone(),
+            total: holder.total_raw.to_string(),
+            total_ui: holder.total_ui(decimals),
+            accounts: holder
+                .token_accounts
+                .iter()
+                .map(|snapshot| snapshot.token_account.clone())
+                .collect(),
+        });
     }
-
-    let total_ui = total_raw as f64 / 10f64.powi(decimals as i32);
-    println!("小计 (前 {} 名): {:.6} 枚", display_count, total_ui);
+    analysis.displayed_total_ui = total_raw as f64 / 10f64.powi(decimals as i32);
 
     if holders_only {
-        println!("\n提示：使用 --top-holders N 可调整展示数量。");
-        return Ok(());
+        analysis
+            .notes
+            .push("提示：使用 --top-holders N 可调整展示数量。".to_string());
+        return Ok(analysis);
     }
 
-    println!("\n=== 持有人常见其它 SPL 代币持仓 (按余额排序) ===");
+    let mut other_holdings = Vec::new();
     for holder in holders.iter().take(display_count) {
-        let others = match fetch_owner_top_tokens(
+        match fetch_owner_top_tokens(
             helius_rpc.as_ref(),
             &holder.owner,
             mint,
@@ -310,47 +312,37 @@ pub async fn analyze_token(
         )
         .await
         {
-            Ok(tokens) => tokens,
-            Err(err) => {
-                println!("- {} 的其它 SPL 代币未能成功获取 ({})", holder.owner, err);
-                continue;
-            }
-        };
-
-        if others.is_empty() {
-            println!("- {} 暂无其它 SPL 代币余额 (或不足筛选条件)", holder.owner);
-            continue;
-        }
-
-        println!("- {} 还持有:", holder.owner);
-        for token in others {
-            let token_label = token.metadata.label(&token.mint);
-            let price_info = token
-                .price_usd
-                .map(|p| format!("价格: ${:.6}", p))
-                .unwrap_or_else(|| "价格: 未知".to_string());
-            let value_info = token
-                .value_usd
-                .map(|v| format!("≈ ${:.2}", v))
-                .unwrap_or_else(|| "≈ $-".to_string());
-            println!(
-                "    - {} ({}) : {:.6} 枚 (精度: {}) [{} | {}]",
-                token_label, token.mint, token.amount_ui, token.decimals, price_info, value_info
-            );
+            Ok(tokens) => other_holdings.push(CliHolderOtherTokens {
+                owner: holder.owner.clone(),
+                tokens: tokens
+                    .into_iter()
+                    .map(|token| CliOtherToken {
+                        label: token.metadata.label(&token.mint),
+                        mint: token.mint,
+                        amount_ui: token.amount_ui,
+                        decimals: token.decimals,
+                        price_usd: token.price_usd,
+                        value_usd: token.value_usd,
+                    })
+                    .collect(),
+                error: None,
+            }),
+            Err(err) => other_holdings.push(CliHolderOtherTokens {
+                owner: holder.owner.clone(),
+                tokens: Vec::new(),
+                error: Some(err.to_string()),
+            }),
         }
     }
+    analysis.other_holdings = Some(other_holdings);
 
     if transfer_limit > 0 {
         if decimals > 0 {
-            println!(
-                "\n提示：getSignaturesForAsset 主要适用于 NFT/cNFT。当前代币精度为 {}，跳过签名查询。",
+            analysis.notes.push(format!(
+                "提示：getSignaturesForAsset 主要适用于 NFT/cNFT。当前代币精度为 {}，跳过签名查询。",
                 decimals
-            );
+            ));
         } else {
-            println!(
-                "\n=== 最近的 {} 条代币相关交易签名 (基于 DAS getSignaturesForAsset) ===",
-                transfer_limit
-            );
             match helius_rpc
                 .get_signatures_for_asset(GetAssetSignatures {
                     id: Some(mint.to_string()),
@@ -361,41 +353,50 @@ pub async fn analyze_token(
             {
                 Ok(signatures) => {
                     if signatures.items.is_empty() {
-                        println!("未获取到交易签名，可稍后再试或调整 limit 值。");
+                        analysis
+                            .notes
+                            .push("未获取到交易签名，可稍后再试或调整 limit 值。".to_string());
                     } else {
-                        for (sig, _slot) in signatures.items {
-                            println!("- {}", sig);
-                        }
+                        analysis.signatures = Some(
+                            signatures
+                                .items
+                                .into_iter()
+                                .map(|(sig, _slot)| sig)
+                                .collect(),
+                        );
                     }
                 }
                 Err(err) => {
-                    println!(
+                    analysis.notes.push(format!(
                         "未能获取交易签名，Helius 返回: {} (部分资产暂不支持该接口)",
                         err
-                    );
+                    ));
                 }
             }
         }
     } else {
-        println!("\n提示：使用 --transfer-limit N 可查看近期代币交易签名。");
+        analysis
+            .notes
+            .push("提示：使用 --transfer-limit N 可查看近期代币交易签名。".to_string());
     }
 
-    println!(
-        "\n说明：当前使用 Helius RPC SDK 获取数据，无法直接获得成本或 USD 估值。如需更全面的 Token API，请关注官方更新。"
+    analysis.notes.push(
+        "说明：当前使用 Helius RPC SDK 获取数据，无法直接获得成本或 USD 估值。如需更全面的 Token API，请关注官方更新。"
+            .to_string(),
     );
 
-    Ok(())
+    Ok(analysis)
 }
 
-fn resolve_api_key(api_key: Option<String>) -> Result<String> {
-    if let Some(explicit) = api_key {
-        if !explicit.trim().is_empty() {
-            return Ok(explicit);
-        }
+fn resolve_api_key(api_key: Option<String>) -> CliResult<String> {
+    if let Some(explicit) = api_key.filter(|key| !key.trim().is_empty()) {
+        return Ok(explicit);
     }
 
     env::var("HELIUS_API_KEY").map_err(|_| {
-        anyhow!("未提供 Helius API key。请使用 --api-key 或设置环境变量 HELIUS_API_KEY")
+        CliError::Config(
+            "未提供 Helius API key。请使用 --api-key 或设置环境变量 HELIUS_API_KEY".to_string(),
+        )
     })
 }
 
@@ -417,6 +418,7 @@ fn build_token_accounts_request(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn fetch_owner_top_tokens(
     helius_rpc: &HeliusRpcClient,
     owner: &str,
@@ -455,7 +457,7 @@ async fn fetch_owner_top_tokens(
         let decimals = match get_or_fetch_decimals(&mint, decimals_cache, solana_rpc).await {
             Ok(value) => value,
             Err(err) => {
-                println!("    › 获取 {} 精度失败 ({})，已跳过", mint, err);
+                eprintln!("    › 获取 {} 精度失败 ({})，已跳过", mint, err);
                 continue;
             }
         };
@@ -479,7 +481,7 @@ async fn fetch_owner_top_tokens(
                 price
             }
             None => {
-                println!("    › {} 暂无 Jupiter 报价，已跳过", mint);
+                eprintln!("    › {} 暂无 Jupiter 报价，已跳过", mint);
                 continue;
             }
         };
@@ -516,20 +518,20 @@ async fn get_or_fetch_decimals(
     }
 
     let mint_pubkey = Pubkey::from_str(mint).context("解析其他代币 mint 失败")?;
-    let decimals = fetch_mint_decimals(solana_rpc, &mint_pubkey).await?;
+    let mint_state = fetch_mint(solana_rpc, &mint_pubkey).await?;
+    let decimals = mint_state.decimals;
     cache.insert(mint.to_string(), decimals);
     Ok(decimals)
 }
 
-async fn fetch_mint_decimals(client: &SolanaRpcClient, mint_pubkey: &Pubkey) -> Result<u8> {
+/// 读取并解码 mint 账户 (兼容 spl-token 与带扩展的 Token-2022)
+async fn fetch_mint(client: &SolanaRpcClient, mint_pubkey: &Pubkey) -> Result<CliParsedMint> {
     let account = client
         .get_account(mint_pubkey)
         .await
         .with_context(|| format!("获取 mint {} 账户失败", mint_pubkey))?;
 
-    let mint_state = Mint::unpack(&account.data).map_err(|_| anyhow!("解析 mint 账户数据失败"))?;
-
-    Ok(mint_state.decimals)
+    parse_token_mint(&account.owner, &account.data).ok_or_else(|| anyhow!("解析 mint 账户数据失败"))
 }
 
 async fn get_or_fetch_metadata(
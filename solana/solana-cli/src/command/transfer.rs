@@ -1,39 +1,272 @@
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::native_token::sol_str_to_lamports;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::AddressLookupTableAccount;
+use solana_sdk::native_token::{Sol, sol_str_to_lamports};
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
-use solana_sdk::transaction::Transaction;
 use solana_system_interface::instruction as system_instruction;
 
+use crate::error::{CliError, CliResult};
+use crate::output::CliTxResult;
+use crate::transaction::{
+    ComputeBudget, DurableNonce, TransactionArgs, build_transaction, fee_for_message,
+    process_transaction, sign_transaction, with_nonce,
+};
+
+/// 转账数量：精确的 lamports，或 `ALL` (余额扣除手续费后全部转出)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpendAmount {
+    Lamports(u64),
+    All,
+}
+
+impl SpendAmount {
+    /// 解析命令行输入的数量，默认单位为 SOL (支持小数)，`in_lamports` 时按整数 lamports 解析
+    pub fn parse(amount: &str, in_lamports: bool) -> CliResult<Self> {
+        if amount == "ALL" {
+            return Ok(SpendAmount::All);
+        }
+
+        let lamports = if in_lamports {
+            amount
+                .parse::<u64>()
+                .map_err(|_| CliError::InvalidInput(format!("无效的 lamports 数量: {}", amount)))?
+        } else {
+            // sol_str_to_lamports 会静默截断第 9 位之后的小数，这里显式拒绝以免精度丢失
+            let fraction_digits = amount.split_once('.').map_or(0, |(_, frac)| frac.len());
+            sol_str_to_lamports(amount)
+                .filter(|_| fraction_digits <= 9)
+                .ok_or_else(|| {
+                    CliError::InvalidInput(format!(
+                        "无效的 SOL 数量: {} (最多支持 9 位小数)",
+                        amount
+                    ))
+                })?
+        };
+        if lamports == 0 {
+            return Err(CliError::InvalidInput("转账数量必须大于 0".to_string()));
+        }
+
+        Ok(SpendAmount::Lamports(lamports))
+    }
+}
+
+/// `from` 为转出账户，`fee_payer` 支付交易手续费，二者可以是同一个签名者
 pub async fn transfer(
-    from: &Keypair,
+    from: &dyn Signer,
+    fee_payer: &dyn Signer,
     to: &Pubkey,
-    amount: u64,
+    amount: SpendAmount,
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
     rpc_client: &RpcClient,
-) -> anyhow::Result<()> {
-    println!(
-        "Transferring {} SOL from {} to {}",
-        amount,
+) -> CliResult<CliTxResult> {
+    let blockhash = tx_args.blockhash(rpc_client, nonce).await?;
+    let lookup_tables = tx_args.lookup_tables(rpc_client).await?;
+    let (lamports, budget) = match amount {
+        // 离线签名时无法查询余额，只能使用明确的数量
+        // (--auto-compute-budget、--lookup-table 与 --sign-only 互斥，不会访问 RPC)
+        SpendAmount::Lamports(lamports) if tx_args.sign_only => {
+            let budget = tx_args
+                .compute_budget(&[], &fee_payer.pubkey(), &[], rpc_client)
+                .await?;
+            (lamports, budget)
+        }
+        SpendAmount::All if tx_args.sign_only => {
+            return Err(CliError::InvalidInput(
+                "--sign-only 模式下无法查询余额，不支持 ALL".to_string(),
+            ));
+        }
+        _ => {
+            resolve_lamports(
+                from,
+                fee_payer,
+                to,
+                amount,
+                nonce,
+                blockhash,
+                &lookup_tables,
+                tx_args,
+                rpc_client,
+            )
+            .await?
+        }
+    };
+
+    eprintln!(
+        "Transferring {} ({} lamports) from {} to {}",
+        Sol(lamports),
+        lamports,
         from.pubkey(),
         to
     );
 
-    let amount = sol_str_to_lamports(&amount.to_string()).unwrap();
     // system_instruction.transfer() 方法创建一个指令，用于将 SOL 从 fromPubkey 账户转移到 toPubkey 账户，
     // 转移的金额为指定的 lamports。
-    let transfer_ix = system_instruction::transfer(&from.pubkey(), to, amount);
+    let transfer_ix = system_instruction::transfer(&from.pubkey(), to, lamports);
     // 创建一个交易并将指令添加到交易中。
     //
     // 在此示例中，我们创建了一个包含单个指令的交易。然而，您可以向一个交易中添加多个指令。
-    let mut transaction = Transaction::new_with_payer(&[transfer_ix], Some(&from.pubkey()));
-    transaction.sign(&[&from], rpc_client.get_latest_blockhash().await?);
+    // 使用 durable nonce 时，第一条指令必须是 advance_nonce_account。
+    let mut transaction = build_transaction(
+        vec![transfer_ix],
+        &fee_payer.pubkey(),
+        nonce,
+        &budget,
+        &lookup_tables,
+    )?;
+    let mut signers = vec![fee_payer, from];
+    if let Some(nonce) = nonce {
+        signers.push(nonce.authority);
+    }
+    sign_transaction(&mut transaction, &signers, blockhash, tx_args)?;
+
+    // 先模拟再发送；交易签名是一个唯一标识符，可用于在 Solana Explorer 上查询交易。
+    process_transaction(rpc_client, &transaction, tx_args).await
+}
 
-    match rpc_client.send_and_confirm_transaction(&transaction).await {
-        //交易签名是一个唯一标识符，可用于在 Solana Explorer 上查询交易。
-        Ok(signature) => println!("Transaction Signature: {}", signature),
-        Err(err) => eprintln!("Error sending transaction: {}", err),
+/// 确定转账数量与计算预算
+///
+/// `ALL` 时用 get_fee_for_message 估算手续费 (含优先费)；若 from 自己支付手续费，则转出余额减去手续费的部分。
+#[allow(clippy::too_many_arguments)]
+async fn resolve_lamports(
+    from: &dyn Signer,
+    fee_payer: &dyn Signer,
+    to: &Pubkey,
+    amount: SpendAmount,
+    nonce: Option<&DurableNonce<'_>>,
+    blockhash: Hash,
+    lookup_tables: &[AddressLookupTableAccount],
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<(u64, ComputeBudget)> {
+    let balance = rpc_client.get_balance(&from.pubkey()).await?;
+    let insufficient = |lamports: u64| {
+        CliError::InsufficientFunds(format!(
+            "账户 {} 需要 {}，当前余额 {}",
+            from.pubkey(),
+            Sol(lamports),
+            Sol(balance)
+        ))
+    };
+    let self_funded = fee_payer.pubkey() == from.pubkey();
+    let probe_lamports = match amount {
+        SpendAmount::Lamports(lamports) if lamports > balance => {
+            return Err(insufficient(lamports));
+        }
+        SpendAmount::Lamports(lamports) => lamports,
+        SpendAmount::All if !self_funded => balance,
+        // 转账消耗的计算单元与数量无关，用一半余额估算以免模拟因不够支付手续费而失败
+        SpendAmount::All => balance / 2,
+    };
+    if probe_lamports == 0 {
+        return Err(CliError::InsufficientFunds(format!(
+            "账户 {} 余额 {} 不足以支付手续费",
+            from.pubkey(),
+            Sol(balance)
+        )));
     }
+    let budget = tx_args
+        .compute_budget(
+            &with_nonce(
+                vec![system_instruction::transfer(
+                    &from.pubkey(),
+                    to,
+                    probe_lamports,
+                )],
+                nonce,
+            ),
+            &fee_payer.pubkey(),
+            lookup_tables,
+            rpc_client,
+        )
+        .await?;
+    let lamports = match amount {
+        SpendAmount::Lamports(lamports) => lamports,
+        SpendAmount::All if !self_funded => balance,
+        SpendAmount::All => {
+            let mut message = build_transaction(
+                vec![system_instruction::transfer(&from.pubkey(), to, balance)],
+                &fee_payer.pubkey(),
+                nonce,
+                &budget,
+                lookup_tables,
+            )?
+            .message;
+            message.set_recent_blockhash(blockhash);
+            let fee = fee_for_message(rpc_client, &message).await?;
+            balance.saturating_sub(fee)
+        }
+    };
 
-    Ok(())
+    if lamports == 0 {
+        return Err(CliError::InsufficientFunds(format!(
+            "账户 {} 余额 {} 不足以支付手续费",
+            from.pubkey(),
+            Sol(balance)
+        )));
+    }
+    Ok((lamports, budget))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lamports(amount: &str, in_lamports: bool) -> Option<u64> {
+        match SpendAmount::parse(amount, in_lamports) {
+            Ok(SpendAmount::Lamports(lamports)) => Some(lamports),
+            Ok(SpendAmount::All) => panic!("{} 不应解析为 ALL", amount),
+            Err(_) => None,
+        }
+    }
+
+    #[test]
+    fn parse_all() {
+        assert_eq!(SpendAmount::parse("ALL", false).unwrap(), SpendAmount::All);
+        assert_eq!(SpendAmount::parse("ALL", true).unwrap(), SpendAmount::All);
+        // ALL 区分大小写
+        assert_eq!(lamports("all", false), None);
+    }
+
+    #[test]
+    fn parse_sol_amounts() {
+        assert_eq!(lamports("1", false), Some(1_000_000_000));
+        assert_eq!(lamports("0.5", false), Some(500_000_000));
+        assert_eq!(lamports("1.000000001", false), Some(1_000_000_001));
+        assert_eq!(lamports("0.000000001", false), Some(1));
+        assert_eq!(lamports("18446744073.709551615", false), Some(u64::MAX));
+    }
+
+    #[test]
+    fn parse_lamport_amounts() {
+        assert_eq!(lamports("1", true), Some(1));
+        assert_eq!(lamports(&u64::MAX.to_string(), true), Some(u64::MAX));
+        assert_eq!(lamports("1.5", true), None);
+    }
+
+    #[test]
+    fn parse_rejects_more_than_nine_decimals() {
+        assert_eq!(lamports("0.0000000001", false), None);
+        assert_eq!(lamports("1.0000000010", false), None);
+    }
+
+    #[test]
+    fn parse_rejects_overflow() {
+        assert_eq!(lamports("18446744073.709551616", false), None);
+        assert_eq!(lamports("18446744074", false), None);
+        assert_eq!(lamports("18446744073709551616", true), None);
+    }
+
+    #[test]
+    fn parse_rejects_empty_zero_and_negative() {
+        for in_lamports in [false, true] {
+            assert_eq!(lamports("", in_lamports), None);
+            assert_eq!(lamports("0", in_lamports), None);
+            assert_eq!(lamports("-1", in_lamports), None);
+        }
+        assert_eq!(lamports("0.0", false), None);
+        assert_eq!(lamports("-0.5", false), None);
+        assert_eq!(lamports("abc", false), None);
+    }
 }
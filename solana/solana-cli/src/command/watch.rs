@@ -0,0 +1,414 @@
+use std::time::Duration;
+
+use clap::Subcommand;
+use futures_util::StreamExt;
+use solana_account::Account;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::{PubsubClient, PubsubClientError};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
+    RpcTransactionLogsFilter,
+};
+use solana_client::rpc_response::RpcSignatureResult;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::error::{CliResult, describe_transaction_error};
+use crate::output::{
+    CliAccountUpdate, CliLogsUpdate, CliSignatureUpdate, CliSlotUpdate, CliTokenAmountUpdate,
+    OutputFormat,
+};
+use crate::parser::token::{parse_token_account, parse_token_mint};
+
+/// 断线后第一次重连前的等待时间，之后每次失败翻倍
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// 重连等待时间的上限
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// watch 的订阅目标
+#[derive(Subcommand)]
+pub enum WatchCommand {
+    /// 订阅账户变化，输出 SOL 余额变化；代币账户同时输出代币余额变化
+    Account {
+        /// 账户的公钥
+        address: Pubkey,
+    },
+    /// 订阅交易的程序日志
+    Logs {
+        /// 只接收提及该地址的交易，不指定时接收除投票交易外的全部交易
+        #[arg(long, value_name = "PUBKEY")]
+        mentions: Option<Pubkey>,
+        /// 同时接收投票交易
+        #[arg(long = "include-votes", conflicts_with = "mentions")]
+        include_votes: bool,
+    },
+    /// 订阅交易状态，交易达到 --commitment 指定的提交级别后退出
+    Signature {
+        /// 交易签名
+        signature: Signature,
+    },
+    /// 订阅 slot 变化
+    Slot,
+}
+
+/// 一次 WebSocket 会话的结束方式
+enum Session {
+    /// 订阅目标已完成 (如交易已确认)，不再重连
+    Finished,
+    /// 订阅成功后连接断开
+    Disconnected,
+    /// 连接成功但订阅请求失败
+    SubscribeFailed(String),
+}
+
+/// 通过 WebSocket 订阅并持续输出更新，直到 Ctrl-C 中断 (watch signature 在交易确认后自动退出)
+///
+/// 连接断开或订阅失败时按指数退避自动重连。
+pub async fn watch(
+    target: &WatchCommand,
+    websocket_url: &str,
+    output_format: OutputFormat,
+    rpc_client: &RpcClient,
+) -> CliResult<()> {
+    let watching = async {
+        match target {
+            WatchCommand::Account { address } => {
+                watch_account(address, websocket_url, output_format, rpc_client).await
+            }
+            WatchCommand::Logs {
+                mentions,
+                include_votes,
+            } => {
+                let filter = match mentions {
+                    Some(address) => RpcTransactionLogsFilter::Mentions(vec![address.to_string()]),
+                    None if *include_votes => RpcTransactionLogsFilter::AllWithVotes,
+                    None => RpcTransactionLogsFilter::All,
+                };
+                watch_logs(filter, websocket_url, output_format, rpc_client).await
+            }
+            WatchCommand::Signature { signature } => {
+                watch_signature(signature, websocket_url, output_format, rpc_client).await
+            }
+            WatchCommand::Slot => watch_slot(websocket_url, output_format).await,
+        }
+    };
+    tokio::select! {
+        result = watching => result,
+        _ = tokio::signal::ctrl_c() => {
+            eprintln!("Interrupted, stopped watching");
+            Ok(())
+        }
+    }
+}
+
+fn emit<T>(output_format: OutputFormat, item: &T) -> CliResult<()>
+where
+    T: serde::Serialize + std::fmt::Display,
+{
+    println!("{}", output_format.formatted_string(item)?);
+    Ok(())
+}
+
+/// 建立连接并运行 `session`，会话因断线或订阅失败结束时按指数退避重连
+async fn subscribe_with_reconnect(
+    websocket_url: &str,
+    mut session: impl AsyncFnMut(&PubsubClient) -> CliResult<Session>,
+) -> CliResult<()> {
+    let mut delay = INITIAL_RECONNECT_DELAY;
+    loop {
+        let reason = match PubsubClient::new(websocket_url).await {
+            Ok(client) => {
+                let result = session(&client).await;
+                let _ = client.shutdown().await;
+                match result? {
+                    Session::Finished => return Ok(()),
+                    Session::Disconnected => {
+                        delay = INITIAL_RECONNECT_DELAY;
+                        "连接断开".to_string()
+                    }
+                    Session::SubscribeFailed(err) => format!("订阅失败: {}", err),
+                }
+            }
+            Err(err) => format!(
+                "连接 {} 失败: {}",
+                websocket_url,
+                describe_pubsub_error(&err)
+            ),
+        };
+        eprintln!("{}, reconnecting in {}s", reason, delay.as_secs());
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+}
+
+/// PubsubClientError 的 Display 不包含底层的 WebSocket 错误，这里补上具体原因
+fn describe_pubsub_error(err: &PubsubClientError) -> String {
+    match err {
+        PubsubClientError::ConnectionError(cause) | PubsubClientError::WsError(cause) => {
+            format!("{} ({})", err, cause)
+        }
+        _ => err.to_string(),
+    }
+}
+
+/// 上一次输出时的账户状态，用于计算变化量
+struct AccountSnapshot {
+    lamports: u64,
+    /// 代币账户的 mint 与最小单位余额
+    token: Option<(Pubkey, u64)>,
+}
+
+async fn watch_account(
+    address: &Pubkey,
+    websocket_url: &str,
+    output_format: OutputFormat,
+    rpc_client: &RpcClient,
+) -> CliResult<()> {
+    let response = rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?;
+    let mut decimals_cache: Option<(Pubkey, Option<u8>)> = None;
+    let mut previous = None;
+    let update = account_update(
+        address,
+        response.context.slot,
+        response.value.as_ref(),
+        &mut previous,
+        &mut decimals_cache,
+        rpc_client,
+    )
+    .await;
+    emit(output_format, &update)?;
+
+    let config = RpcAccountInfoConfig {
+        // 使用 base64 以便复用本地的代币账户解码器
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(rpc_client.commitment()),
+        ..RpcAccountInfoConfig::default()
+    };
+    subscribe_with_reconnect(websocket_url, async |client| {
+        let (mut stream, _unsubscribe) = match client
+            .account_subscribe(address, Some(config.clone()))
+            .await
+        {
+            Ok(subscription) => subscription,
+            Err(err) => return Ok(Session::SubscribeFailed(describe_pubsub_error(&err))),
+        };
+        while let Some(response) = stream.next().await {
+            let account = response.value.decode::<Account>();
+            // 账户被关闭时推送 lamports 为 0 的空账户
+            let account = account.filter(|account| account.lamports > 0);
+            let update = account_update(
+                address,
+                response.context.slot,
+                account.as_ref(),
+                &mut previous,
+                &mut decimals_cache,
+                rpc_client,
+            )
+            .await;
+            emit(output_format, &update)?;
+        }
+        Ok(Session::Disconnected)
+    })
+    .await
+}
+
+/// 与上一次的状态比较生成账户更新，并记录本次状态
+async fn account_update(
+    address: &Pubkey,
+    slot: u64,
+    account: Option<&Account>,
+    previous: &mut Option<AccountSnapshot>,
+    decimals_cache: &mut Option<(Pubkey, Option<u8>)>,
+    rpc_client: &RpcClient,
+) -> CliAccountUpdate {
+    let lamports = account.map_or(0, |account| account.lamports);
+    let token_account =
+        account.and_then(|account| parse_token_account(&account.owner, &account.data));
+    let token_state = token_account.as_ref().and_then(|token_account| {
+        Some((
+            token_account.mint.parse::<Pubkey>().ok()?,
+            token_account.amount.parse::<u64>().ok()?,
+        ))
+    });
+
+    let token = match token_state {
+        Some((mint, amount)) => {
+            let decimals = match decimals_cache {
+                Some((cached_mint, decimals)) if *cached_mint == mint => *decimals,
+                _ => {
+                    let decimals = mint_decimals(&mint, rpc_client).await;
+                    *decimals_cache = Some((mint, decimals));
+                    decimals
+                }
+            };
+            let ui = |amount: u64| {
+                spl_token_2022::amount_to_ui_amount_string_trimmed(amount, decimals.unwrap_or(0))
+            };
+            let change = previous
+                .as_ref()
+                .and_then(|previous| previous.token)
+                .filter(|(previous_mint, previous_amount)| {
+                    *previous_mint == mint && *previous_amount != amount
+                })
+                .map(|(_, previous_amount)| {
+                    let sign = if amount < previous_amount { "-" } else { "+" };
+                    format!("{}{}", sign, ui(amount.abs_diff(previous_amount)))
+                });
+            Some(CliTokenAmountUpdate {
+                mint: mint.to_string(),
+                amount: amount.to_string(),
+                decimals,
+                ui_amount: ui(amount),
+                change,
+            })
+        }
+        None => None,
+    };
+
+    let update = CliAccountUpdate {
+        address: address.to_string(),
+        slot,
+        owner: account.map(|account| account.owner.to_string()),
+        lamports,
+        lamports_change: previous
+            .as_ref()
+            .map(|previous| lamports as i128 - previous.lamports as i128),
+        data_len: account.map_or(0, |account| account.data.len()),
+        token,
+    };
+    *previous = Some(AccountSnapshot {
+        lamports,
+        token: token_state,
+    });
+    update
+}
+
+/// 获取 mint 的精度，查询失败时返回 None (按最小单位显示余额)，不中断订阅
+async fn mint_decimals(mint: &Pubkey, rpc_client: &RpcClient) -> Option<u8> {
+    let account = rpc_client.get_account(mint).await.ok()?;
+    parse_token_mint(&account.owner, &account.data).map(|mint| mint.decimals)
+}
+
+async fn watch_logs(
+    filter: RpcTransactionLogsFilter,
+    websocket_url: &str,
+    output_format: OutputFormat,
+    rpc_client: &RpcClient,
+) -> CliResult<()> {
+    let config = RpcTransactionLogsConfig {
+        commitment: Some(rpc_client.commitment()),
+    };
+    subscribe_with_reconnect(websocket_url, async |client| {
+        let (mut stream, _unsubscribe) =
+            match client.logs_subscribe(filter.clone(), config.clone()).await {
+                Ok(subscription) => subscription,
+                Err(err) => return Ok(Session::SubscribeFailed(describe_pubsub_error(&err))),
+            };
+        while let Some(response) = stream.next().await {
+            let logs = response.value;
+            emit(
+                output_format,
+                &CliLogsUpdate {
+                    slot: response.context.slot,
+                    signature: logs.signature,
+                    error: logs.err.map(|err| describe_transaction_error(&err.into())),
+                    logs: logs.logs,
+                },
+            )?;
+        }
+        Ok(Session::Disconnected)
+    })
+    .await
+}
+
+async fn watch_signature(
+    signature: &Signature,
+    websocket_url: &str,
+    output_format: OutputFormat,
+    rpc_client: &RpcClient,
+) -> CliResult<()> {
+    let commitment = rpc_client.commitment();
+    let config = RpcSignatureSubscribeConfig {
+        commitment: Some(commitment),
+        enable_received_notification: Some(true),
+    };
+    subscribe_with_reconnect(websocket_url, async |client| {
+        let (mut stream, _unsubscribe) = match client
+            .signature_subscribe(signature, Some(config.clone()))
+            .await
+        {
+            Ok(subscription) => subscription,
+            Err(err) => return Ok(Session::SubscribeFailed(describe_pubsub_error(&err))),
+        };
+        // 订阅之前 (或断线期间) 交易可能已经确认，节点不会再推送，先查询一次当前状态；
+        // 早已确认的交易已不在近期状态缓存中，需要查询完整历史
+        let status = rpc_client
+            .get_signature_statuses_with_history(&[*signature])
+            .await?
+            .value
+            .pop()
+            .flatten();
+        if let Some(status) = status.filter(|status| status.satisfies_commitment(commitment)) {
+            emit(
+                output_format,
+                &CliSignatureUpdate {
+                    signature: signature.to_string(),
+                    slot: status.slot,
+                    status: commitment.commitment.to_string(),
+                    error: status.err.map(|err| describe_transaction_error(&err)),
+                },
+            )?;
+            return Ok(Session::Finished);
+        }
+        while let Some(response) = stream.next().await {
+            let (status, error, finished) = match response.value {
+                RpcSignatureResult::ReceivedSignature(_) => ("received".to_string(), None, false),
+                RpcSignatureResult::ProcessedSignature(result) => (
+                    commitment.commitment.to_string(),
+                    result
+                        .err
+                        .map(|err| describe_transaction_error(&err.into())),
+                    true,
+                ),
+            };
+            emit(
+                output_format,
+                &CliSignatureUpdate {
+                    signature: signature.to_string(),
+                    slot: response.context.slot,
+                    status,
+                    error,
+                },
+            )?;
+            if finished {
+                return Ok(Session::Finished);
+            }
+        }
+        Ok(Session::Disconnected)
+    })
+    .await
+}
+
+async fn watch_slot(websocket_url: &str, output_format: OutputFormat) -> CliResult<()> {
+    subscribe_with_reconnect(websocket_url, async |client| {
+        let (mut stream, _unsubscribe) = match client.slot_subscribe().await {
+            Ok(subscription) => subscription,
+            Err(err) => return Ok(Session::SubscribeFailed(describe_pubsub_error(&err))),
+        };
+        while let Some(slot_info) = stream.next().await {
+            emit(
+                output_format,
+                &CliSlotUpdate {
+                    slot: slot_info.slot,
+                    parent: slot_info.parent,
+                    root: slot_info.root,
+                },
+            )?;
+        }
+        Ok(Session::Disconnected)
+    })
+    .await
+}
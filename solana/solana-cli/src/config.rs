@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use solana_cli_config::{CONFIG_FILE, Config, ConfigInput};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::signer::Signer;
+
+use crate::command::Args;
+use crate::error::{CliError, CliResult};
+use crate::output::OutputFormat;
+use crate::signer::signer_from_source;
+
+/// 合并命令行参数与配置文件后的最终连接配置
+///
+/// 优先级: 命令行参数 > 配置文件 (config.yml) > 系统默认值
+pub struct CliConfig {
+    pub config_file: Option<String>,
+    pub json_rpc_url: String,
+    pub websocket_url: String,
+    pub keypair_path: String,
+    pub commitment: CommitmentConfig,
+    pub output_format: OutputFormat,
+}
+
+impl CliConfig {
+    pub fn load(args: &Args) -> CliResult<Self> {
+        let (config_file, file_config) = load_config_file(args.config.as_deref())?;
+
+        let (_, json_rpc_url) = ConfigInput::compute_json_rpc_url_setting(
+            args.url.as_deref().unwrap_or_default(),
+            &file_config.json_rpc_url,
+        );
+        let (_, websocket_url) = ConfigInput::compute_websocket_url_setting(
+            "",
+            &file_config.websocket_url,
+            args.url.as_deref().unwrap_or_default(),
+            &file_config.json_rpc_url,
+        );
+        let (_, keypair_path) = ConfigInput::compute_keypair_path_setting(
+            args.keypair.as_deref().unwrap_or_default(),
+            &file_config.keypair_path,
+        );
+        let (_, commitment) = ConfigInput::compute_commitment_config(
+            args.commitment.as_deref().unwrap_or_default(),
+            &file_config.commitment,
+        );
+
+        Ok(Self {
+            config_file,
+            json_rpc_url,
+            websocket_url,
+            keypair_path,
+            commitment,
+            output_format: args.output,
+        })
+    }
+
+    pub fn rpc_client(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.json_rpc_url.clone(), self.commitment)
+    }
+
+    /// 读取默认签名账户 (--keypair 或配置文件中的 keypair_path)，支持与 --from 相同的来源语法
+    pub fn default_signer(&self) -> CliResult<Box<dyn Signer>> {
+        signer_from_source(&self.keypair_path, "keypair")
+    }
+
+    /// 优先使用命令行显式指定的签名者，否则退回到默认签名账户
+    pub fn signer_or_default(
+        &self,
+        source: Option<&str>,
+        keypair_name: &str,
+    ) -> CliResult<Box<dyn Signer>> {
+        match source {
+            Some(source) => signer_from_source(source, keypair_name),
+            None => self.default_signer(),
+        }
+    }
+}
+
+/// 显式指定的配置文件必须存在；默认路径不存在时退回到内置默认配置
+fn load_config_file(explicit: Option<&str>) -> CliResult<(Option<String>, Config)> {
+    if let Some(path) = explicit {
+        let config = Config::load(path)
+            .map_err(|err| CliError::Config(format!("无法读取配置文件 {}: {}", path, err)))?;
+        return Ok((Some(path.to_string()), config));
+    }
+
+    match CONFIG_FILE.as_ref() {
+        Some(path) if Path::new(path).exists() => {
+            let config = Config::load(path)
+                .map_err(|err| CliError::Config(format!("无法读取配置文件 {}: {}", path, err)))?;
+            Ok((Some(path.clone()), config))
+        }
+        _ => Ok((None, Config::default())),
+    }
+}
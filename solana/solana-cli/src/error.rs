@@ -0,0 +1,172 @@
+use std::process::ExitCode;
+
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::SignerError;
+use solana_sdk::transaction::TransactionError;
+use thiserror::Error;
+
+pub type CliResult<T> = Result<T, CliError>;
+
+/// 所有子命令统一返回的错误类型，每种错误对应不同的进程退出码
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("参数错误: {0}")]
+    InvalidInput(String),
+    #[error("配置错误: {0}")]
+    Config(String),
+    #[error("签名者错误: {0}")]
+    Signer(String),
+    #[error("RPC 请求失败: {0}")]
+    Rpc(#[source] Box<ClientError>),
+    #[error("交易模拟失败: {}", describe_transaction_error(.error))]
+    SimulationFailed {
+        error: TransactionError,
+        logs: Vec<String>,
+    },
+    #[error("交易 {signature} 执行失败: {}", describe_transaction_error(.error))]
+    TransactionFailed {
+        signature: Signature,
+        error: TransactionError,
+    },
+    #[error("交易 {0} 的区块哈希 (或 nonce) 已过期且未上链，可以安全地重新发送")]
+    BlockhashExpired(Signature),
+    #[error("等待交易 {0} 确认超时，交易状态未知，请稍后查询该签名的状态")]
+    ConfirmationTimeout(Signature),
+    #[error("余额不足: {0}")]
+    InsufficientFunds(String),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl CliError {
+    /// 进程退出码，供自动化脚本区分失败原因 (2 与 clap 的参数错误保持一致)
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Other(_) => 1,
+            CliError::InvalidInput(_) => 2,
+            CliError::Config(_) => 3,
+            CliError::Signer(_) => 4,
+            CliError::Rpc(_) => 5,
+            CliError::SimulationFailed { .. } => 6,
+            CliError::TransactionFailed { .. } => 7,
+            CliError::BlockhashExpired(_) => 8,
+            CliError::InsufficientFunds(_) => 9,
+            CliError::ConfirmationTimeout(_) => 10,
+        }
+    }
+
+    /// 打印错误详情 (含程序日志) 到 stderr，并返回对应的退出码
+    pub fn report(&self) -> ExitCode {
+        eprintln!("Error: {}", self);
+        match self {
+            CliError::SimulationFailed { logs, .. } if !logs.is_empty() => {
+                eprintln!("Program logs:");
+                for log in logs {
+                    eprintln!("  {}", log);
+                }
+            }
+            CliError::Other(err) => {
+                for cause in err.chain().skip(1) {
+                    eprintln!("Caused by: {}", cause);
+                }
+            }
+            _ => {}
+        }
+        ExitCode::from(self.exit_code())
+    }
+
+    /// 将 sendTransaction / send_and_confirm_transaction 的错误解码为具体的失败原因
+    pub fn from_send_error(err: ClientError, signature: Signature) -> Self {
+        if let ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) = err.kind()
+            && let Some(error) = result.err.clone()
+        {
+            return Self::from_transaction_error(
+                error.into(),
+                result.logs.clone().unwrap_or_default(),
+            );
+        }
+        match err.kind() {
+            ClientErrorKind::TransactionError(error) => {
+                Self::from_executed_error(error.clone(), signature)
+            }
+            ClientErrorKind::RpcError(RpcError::ForUser(message))
+                if message.starts_with("unable to confirm transaction") =>
+            {
+                CliError::BlockhashExpired(signature)
+            }
+            _ => CliError::from(err),
+        }
+    }
+
+    /// 交易上链后执行失败 (签名状态中的错误)
+    pub fn from_executed_error(error: TransactionError, signature: Signature) -> Self {
+        match error {
+            TransactionError::InsufficientFundsForFee
+            | TransactionError::InsufficientFundsForRent { .. } => {
+                CliError::InsufficientFunds(describe_transaction_error(&error))
+            }
+            error => CliError::TransactionFailed { signature, error },
+        }
+    }
+
+    /// 预检/模拟阶段返回的交易错误
+    pub fn from_transaction_error(error: TransactionError, logs: Vec<String>) -> Self {
+        match error {
+            TransactionError::InsufficientFundsForFee
+            | TransactionError::InsufficientFundsForRent { .. } => {
+                CliError::InsufficientFunds(describe_transaction_error(&error))
+            }
+            TransactionError::InstructionError(
+                _,
+                InstructionError::Custom(1) | InstructionError::InsufficientFunds,
+            ) => match logs
+                .iter()
+                .find(|log| log.contains("insufficient lamports"))
+            {
+                Some(log) => CliError::InsufficientFunds(format!(
+                    "{} ({})",
+                    describe_transaction_error(&error),
+                    log
+                )),
+                None => CliError::SimulationFailed { error, logs },
+            },
+            error => CliError::SimulationFailed { error, logs },
+        }
+    }
+}
+
+impl From<ClientError> for CliError {
+    fn from(err: ClientError) -> Self {
+        CliError::Rpc(Box::new(err))
+    }
+}
+
+impl From<SignerError> for CliError {
+    fn from(err: SignerError) -> Self {
+        CliError::Signer(err.to_string())
+    }
+}
+
+/// 将 TransactionError 解码为可读文本，指令错误会标明出错的指令序号
+pub fn describe_transaction_error(error: &TransactionError) -> String {
+    match error {
+        TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+            format!("指令 #{} 失败: 自定义程序错误 0x{:x}", index, code)
+        }
+        TransactionError::InstructionError(index, instruction_error) => {
+            format!("指令 #{} 失败: {}", index, instruction_error)
+        }
+        TransactionError::InsufficientFundsForFee => "手续费支付账户余额不足".to_string(),
+        TransactionError::InsufficientFundsForRent { account_index } => {
+            format!("账户 #{} 余额不足以满足租金豁免", account_index)
+        }
+        TransactionError::BlockhashNotFound => "区块哈希不存在或已过期".to_string(),
+        error => error.to_string(),
+    }
+}
@@ -1,44 +1,1047 @@
+use std::collections::HashSet;
+use std::process::ExitCode;
+
 use clap::Parser;
-use solana_cli_config::{CONFIG_FILE, Config};
-use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_commitment_config::CommitmentConfig;
-use solana_sdk::signature::Signer;
-use solana_sdk::{signature::Keypair, signer::keypair::read_keypair_file};
+use solana_sdk::signer::Signer;
+
+use crate::error::CliResult;
 
 pub mod command;
+pub mod config;
+pub mod error;
+pub mod output;
+pub mod parser;
+pub mod signer;
+pub mod transaction;
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let config = Config::load(CONFIG_FILE.as_ref().unwrap())?;
-    println!("Welcome to Solana CLI!");
-    println!("Connect to {}", config.json_rpc_url);
-    println!("Keypair: {}", config.keypair_path);
-    let client = RpcClient::new_with_commitment(config.json_rpc_url, CommitmentConfig::confirmed());
-
+async fn main() -> ExitCode {
     let args = command::Args::parse();
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => err.report(),
+    }
+}
+
+async fn run(args: command::Args) -> CliResult<()> {
+    let config = config::CliConfig::load(&args)?;
+    if config.output_format.is_display() {
+        println!("Welcome to Solana CLI!");
+        if let Some(config_file) = &config.config_file {
+            println!("Config File: {}", config_file);
+        }
+        println!("Connect to {}", config.json_rpc_url);
+        println!("Keypair: {}", config.keypair_path);
+    }
+    let client = config.rpc_client();
 
-    match args.command {
-        command::Command::Transfer { to, amount } => {
-            let from = read_keypair_file(config.keypair_path)
-                .map_err(|err| anyhow::anyhow!("Failed to read keypair file: {}", err))?;
-            command::transfer::transfer(&from, &to, amount, &client).await?;
-        }
-        command::Command::Account { address } => {
-            { command::accountinfo::account_info(&address, &client).await }?
-        }
-        command::Command::Balance { address } => {
-            command::balance::balance(&address, &client).await?
-        }
-        command::Command::MintToken => {
-            let funding_account = read_keypair_file(config.keypair_path)
-                .map_err(|err| anyhow::anyhow!("Failed to read keypair file: {}", err))?;
-            let mint_account = Keypair::new();
-            println!(
-                "Mint account({:?}) private key: {:?}",
-                mint_account.pubkey(),
-                mint_account.to_base58_string()
+    let output = match args.command {
+        command::Command::Transfer {
+            to,
+            amount,
+            lamports,
+            from,
+            fee_payer,
+            nonce,
+            tx,
+        } => {
+            let amount = command::transfer::SpendAmount::parse(&amount, lamports)?;
+            let from = config.signer_or_default(from.as_deref(), "from")?;
+            let fee_payer = signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+            let fee_payer = fee_payer.as_deref().unwrap_or(from.as_ref());
+            let nonce_authority = nonce.authority_signer()?;
+            let durable_nonce =
+                nonce.durable_nonce(nonce_authority.as_deref().unwrap_or(fee_payer));
+            let result = command::transfer::transfer(
+                from.as_ref(),
+                fee_payer,
+                &to,
+                amount,
+                durable_nonce.as_ref(),
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::Distribute {
+            csv,
+            mint,
+            ledger,
+            batch_size,
+            from,
+            fee_payer,
+            fund_recipient,
+            tx,
+        } => {
+            let from = config.signer_or_default(from.as_deref(), "from")?;
+            let fee_payer = signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+            let result = command::distribute::distribute(
+                fee_payer.as_deref().unwrap_or(from.as_ref()),
+                from.as_ref(),
+                &csv,
+                mint,
+                ledger,
+                batch_size,
+                fund_recipient,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::Account {
+            address,
+            encoding,
+            output_file,
+            derivation,
+        } => {
+            let result = command::accountinfo::account_info(
+                &address,
+                encoding,
+                output_file.as_deref(),
+                &derivation,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::FindPda { program, seeds } => {
+            let result = command::address::find_pda(&program, &seeds)?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::CreateWithSeed { seed, owner, base } => {
+            let base = match base {
+                Some(base) => base,
+                None => config.default_signer()?.pubkey(),
+            };
+            let result = command::address::create_with_seed(&base, &seed, &owner)?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::Balance {
+            mut addresses,
+            addresses_file,
+            include_tokens,
+        } => {
+            if let Some(path) = &addresses_file {
+                addresses.extend(command::balance::read_addresses_file(path)?);
+            }
+            let mut seen = HashSet::new();
+            addresses.retain(|address| seen.insert(*address));
+            match addresses.as_slice() {
+                [address] if addresses_file.is_none() && !include_tokens => {
+                    let result = command::balance::balance(address, &client).await?;
+                    config.output_format.formatted_string(&result)?
+                }
+                [] => {
+                    return Err(error::CliError::InvalidInput(
+                        "地址文件中没有任何地址".to_string(),
+                    ));
+                }
+                _ => {
+                    let result =
+                        command::balance::balances(&addresses, include_tokens, &client).await?;
+                    config.output_format.formatted_string(&result)?
+                }
+            }
+        }
+        command::Command::Confirm { signature } => {
+            let result = command::confirm::confirm(&signature, &client).await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::History {
+            address,
+            before,
+            until,
+            limit,
+            summary,
+            export,
+        } => {
+            let result = command::history::history(
+                &address,
+                before,
+                until,
+                limit,
+                summary,
+                export.as_deref(),
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::Keygen { command } => match command {
+            command::keygen::KeygenCommand::New {
+                outfile,
+                no_outfile,
+                mnemonic,
+                word_count,
+                no_passphrase,
+                derivation_path,
+            } => {
+                let outfile = if no_outfile {
+                    None
+                } else {
+                    Some(outfile.resolve(&config.keypair_path)?)
+                };
+                let result = command::keygen::new_keypair(
+                    outfile,
+                    mnemonic,
+                    word_count,
+                    no_passphrase,
+                    derivation_path.as_deref(),
+                )?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::keygen::KeygenCommand::Pubkey { signer } => {
+                let signer = config.signer_or_default(signer.as_deref(), "keypair")?;
+                config
+                    .output_format
+                    .formatted_string(&signer.pubkey().to_string())?
+            }
+            command::keygen::KeygenCommand::Recover { source, outfile } => {
+                let outfile = outfile.resolve(&config.keypair_path)?;
+                let result = command::keygen::recover(&source, outfile)?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::keygen::KeygenCommand::Verify { pubkey, signer } => {
+                let signer = signer.as_deref().unwrap_or(&config.keypair_path);
+                let result = command::keygen::verify(&pubkey, signer)?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::keygen::KeygenCommand::Grind {
+                starts_with,
+                ends_with,
+                starts_and_ends_with,
+                ignore_case,
+                num_threads,
+            } => {
+                let result = command::keygen::grind(
+                    &starts_with,
+                    &ends_with,
+                    &starts_and_ends_with,
+                    ignore_case,
+                    num_threads,
+                )?;
+                config.output_format.formatted_string(&result)?
+            }
+        },
+        command::Command::Watch { target } => {
+            // 更新在订阅过程中逐条输出，没有最终结果
+            return command::watch::watch(
+                &target,
+                &config.websocket_url,
+                config.output_format,
+                &client,
+            )
+            .await;
+        }
+        command::Command::ClusterVersion => {
+            let result = command::cluster::cluster_version(&client).await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::EpochInfo => {
+            let result = command::cluster::epoch_info(&client).await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::Slot => {
+            let slot = client.get_slot().await?;
+            config.output_format.formatted_string(&slot)?
+        }
+        command::Command::BlockHeight => {
+            let block_height = client.get_block_height().await?;
+            config.output_format.formatted_string(&block_height)?
+        }
+        command::Command::Validators {
+            sort,
+            reverse,
+            delinquent,
+            keep_unstaked_delinquents,
+        } => {
+            let result = command::cluster::validators(
+                sort,
+                reverse,
+                delinquent,
+                keep_unstaked_delinquents,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::LeaderSchedule { epoch, identity } => {
+            let result =
+                command::cluster::leader_schedule(epoch, identity.as_ref(), &client).await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::BlockProduction { epoch, identity } => {
+            let result =
+                command::cluster::block_production(epoch, identity.as_ref(), &client).await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::MintToken {
+            decimals,
+            mint_authority,
+            freeze_authority,
+            no_freeze_authority,
+            mint_keypair,
+            grind_prefix,
+            outfile,
+            extensions,
+            nonce,
+            tx,
+        } => {
+            let funding_account = config.default_signer()?;
+            let nonce_authority = nonce.authority_signer()?;
+            let durable_nonce = nonce.durable_nonce(
+                nonce_authority
+                    .as_deref()
+                    .unwrap_or(funding_account.as_ref()),
             );
-            command::mint_token::mint_token(&mint_account, &funding_account, &client).await?;
+            let (mint_account, keypair_file): (Box<dyn Signer>, _) = match mint_keypair {
+                Some(source) => (signer::signer_from_source(&source, "mint-keypair")?, None),
+                None => {
+                    let (keypair, keypair_file) = command::mint_token::generate_mint_keypair(
+                        grind_prefix.as_deref(),
+                        outfile,
+                        !tx.dry_run,
+                    )?;
+                    (Box::new(keypair), keypair_file)
+                }
+            };
+            let mint_authority = mint_authority.unwrap_or_else(|| funding_account.pubkey());
+            let freeze_authority = match (freeze_authority, no_freeze_authority) {
+                (_, true) => None,
+                (Some(freeze_authority), false) => Some(freeze_authority),
+                (None, false) => Some(mint_authority),
+            };
+            let result = command::mint_token::mint_token(
+                mint_account.as_ref(),
+                keypair_file,
+                funding_account.as_ref(),
+                decimals,
+                &mint_authority,
+                freeze_authority.as_ref(),
+                &extensions,
+                durable_nonce.as_ref(),
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::CreateNonceAccount {
+            nonce_account,
+            amount,
+            lamports,
+            nonce_authority,
+            tx,
+        } => {
+            let amount = command::transfer::SpendAmount::parse(&amount, lamports)?;
+            let funding_account = config.default_signer()?;
+            let nonce_account = signer::signer_from_source(&nonce_account, "nonce-account")?;
+            let result = command::nonce::create_nonce_account(
+                funding_account.as_ref(),
+                nonce_account.as_ref(),
+                nonce_authority,
+                amount,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::Nonce { nonce_account } => {
+            let result = command::nonce::get_nonce(&nonce_account, &client).await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::NewNonce {
+            nonce_account,
+            nonce_authority,
+            tx,
+        } => {
+            let fee_payer = config.default_signer()?;
+            let authority =
+                signer::optional_signer_from_source(nonce_authority.as_deref(), "nonce-authority")?;
+            let result = command::nonce::new_nonce(
+                fee_payer.as_ref(),
+                &nonce_account,
+                authority.as_deref().unwrap_or(fee_payer.as_ref()),
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::WithdrawFromNonceAccount {
+            nonce_account,
+            destination,
+            amount,
+            lamports,
+            nonce_authority,
+            tx,
+        } => {
+            let amount = command::transfer::SpendAmount::parse(&amount, lamports)?;
+            let fee_payer = config.default_signer()?;
+            let authority =
+                signer::optional_signer_from_source(nonce_authority.as_deref(), "nonce-authority")?;
+            let result = command::nonce::withdraw_from_nonce_account(
+                fee_payer.as_ref(),
+                &nonce_account,
+                authority.as_deref().unwrap_or(fee_payer.as_ref()),
+                &destination,
+                amount,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::AuthorizeNonceAccount {
+            nonce_account,
+            new_authority,
+            nonce_authority,
+            tx,
+        } => {
+            let fee_payer = config.default_signer()?;
+            let authority =
+                signer::optional_signer_from_source(nonce_authority.as_deref(), "nonce-authority")?;
+            let result = command::nonce::authorize_nonce_account(
+                fee_payer.as_ref(),
+                &nonce_account,
+                authority.as_deref().unwrap_or(fee_payer.as_ref()),
+                &new_authority,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::CreateStakeAccount {
+            stake_account,
+            amount,
+            lamports,
+            seed,
+            stake_authority,
+            withdraw_authority,
+            lockup,
+            from,
+            fee_payer,
+            nonce,
+            tx,
+        } => {
+            let amount = command::transfer::SpendAmount::parse(&amount, lamports)?;
+            let from = config.signer_or_default(from.as_deref(), "from")?;
+            let fee_payer = signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+            let fee_payer = fee_payer.as_deref().unwrap_or(from.as_ref());
+            let stake_account = signer::signer_from_source(&stake_account, "stake-account")?;
+            let nonce_authority = nonce.authority_signer()?;
+            let durable_nonce =
+                nonce.durable_nonce(nonce_authority.as_deref().unwrap_or(fee_payer));
+            let result = command::stake::create_stake_account(
+                from.as_ref(),
+                fee_payer,
+                stake_account.as_ref(),
+                seed.as_deref(),
+                amount,
+                stake_authority,
+                withdraw_authority,
+                &lockup,
+                durable_nonce.as_ref(),
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::DelegateStake {
+            stake_account,
+            vote_account,
+            stake_authority,
+            fee_payer,
+            nonce,
+            tx,
+        } => {
+            let keypair = config.default_signer()?;
+            let fee_payer = signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+            let fee_payer = fee_payer.as_deref().unwrap_or(keypair.as_ref());
+            let authority =
+                signer::optional_signer_from_source(stake_authority.as_deref(), "stake-authority")?;
+            let nonce_authority = nonce.authority_signer()?;
+            let durable_nonce =
+                nonce.durable_nonce(nonce_authority.as_deref().unwrap_or(fee_payer));
+            let result = command::stake::delegate_stake(
+                fee_payer,
+                &stake_account,
+                &vote_account,
+                authority.as_deref().unwrap_or(keypair.as_ref()),
+                durable_nonce.as_ref(),
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::DeactivateStake {
+            stake_account,
+            stake_authority,
+            fee_payer,
+            nonce,
+            tx,
+        } => {
+            let keypair = config.default_signer()?;
+            let fee_payer = signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+            let fee_payer = fee_payer.as_deref().unwrap_or(keypair.as_ref());
+            let authority =
+                signer::optional_signer_from_source(stake_authority.as_deref(), "stake-authority")?;
+            let nonce_authority = nonce.authority_signer()?;
+            let durable_nonce =
+                nonce.durable_nonce(nonce_authority.as_deref().unwrap_or(fee_payer));
+            let result = command::stake::deactivate_stake(
+                fee_payer,
+                &stake_account,
+                authority.as_deref().unwrap_or(keypair.as_ref()),
+                durable_nonce.as_ref(),
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::WithdrawStake {
+            stake_account,
+            destination,
+            amount,
+            lamports,
+            withdraw_authority,
+            custodian,
+            fee_payer,
+            nonce,
+            tx,
+        } => {
+            let amount = command::transfer::SpendAmount::parse(&amount, lamports)?;
+            let keypair = config.default_signer()?;
+            let fee_payer = signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+            let fee_payer = fee_payer.as_deref().unwrap_or(keypair.as_ref());
+            let authority = signer::optional_signer_from_source(
+                withdraw_authority.as_deref(),
+                "withdraw-authority",
+            )?;
+            let custodian = signer::optional_signer_from_source(custodian.as_deref(), "custodian")?;
+            let nonce_authority = nonce.authority_signer()?;
+            let durable_nonce =
+                nonce.durable_nonce(nonce_authority.as_deref().unwrap_or(fee_payer));
+            let result = command::stake::withdraw_stake(
+                fee_payer,
+                &stake_account,
+                &destination,
+                amount,
+                authority.as_deref().unwrap_or(keypair.as_ref()),
+                custodian.as_deref(),
+                durable_nonce.as_ref(),
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::SplitStake {
+            stake_account,
+            split_stake_account,
+            amount,
+            lamports,
+            seed,
+            stake_authority,
+            fee_payer,
+            nonce,
+            tx,
+        } => {
+            let amount = command::transfer::SpendAmount::parse(&amount, lamports)?;
+            let keypair = config.default_signer()?;
+            let fee_payer = signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+            let fee_payer = fee_payer.as_deref().unwrap_or(keypair.as_ref());
+            let split_stake_account =
+                signer::signer_from_source(&split_stake_account, "split-stake-account")?;
+            let authority =
+                signer::optional_signer_from_source(stake_authority.as_deref(), "stake-authority")?;
+            let nonce_authority = nonce.authority_signer()?;
+            let durable_nonce =
+                nonce.durable_nonce(nonce_authority.as_deref().unwrap_or(fee_payer));
+            let result = command::stake::split_stake(
+                fee_payer,
+                &stake_account,
+                split_stake_account.as_ref(),
+                seed.as_deref(),
+                amount,
+                authority.as_deref().unwrap_or(keypair.as_ref()),
+                durable_nonce.as_ref(),
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::MergeStake {
+            stake_account,
+            source_stake_account,
+            stake_authority,
+            fee_payer,
+            nonce,
+            tx,
+        } => {
+            let keypair = config.default_signer()?;
+            let fee_payer = signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+            let fee_payer = fee_payer.as_deref().unwrap_or(keypair.as_ref());
+            let authority =
+                signer::optional_signer_from_source(stake_authority.as_deref(), "stake-authority")?;
+            let nonce_authority = nonce.authority_signer()?;
+            let durable_nonce =
+                nonce.durable_nonce(nonce_authority.as_deref().unwrap_or(fee_payer));
+            let result = command::stake::merge_stake(
+                fee_payer,
+                &stake_account,
+                &source_stake_account,
+                authority.as_deref().unwrap_or(keypair.as_ref()),
+                durable_nonce.as_ref(),
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::StakeAccount {
+            stake_account,
+            with_rewards,
+            num_rewards_epochs,
+        } => {
+            let num_rewards_epochs = if with_rewards { num_rewards_epochs } else { 0 };
+            let result =
+                command::stake::show_stake_account(&stake_account, num_rewards_epochs, &client)
+                    .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::LookupTable { command } => match command {
+            command::lookup_table::LookupTableCommand::Create {
+                authority,
+                recent_slot,
+                fee_payer,
+                tx,
+            } => {
+                let keypair = config.default_signer()?;
+                let fee_payer =
+                    signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+                let result = command::lookup_table::create_lookup_table(
+                    fee_payer.as_deref().unwrap_or(keypair.as_ref()),
+                    authority,
+                    recent_slot,
+                    &tx,
+                    &client,
+                )
+                .await?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::lookup_table::LookupTableCommand::Extend {
+                lookup_table,
+                addresses,
+                authority,
+                fee_payer,
+                tx,
+            } => {
+                let keypair = config.default_signer()?;
+                let fee_payer =
+                    signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+                let authority =
+                    signer::optional_signer_from_source(authority.as_deref(), "authority")?;
+                let result = command::lookup_table::extend_lookup_table(
+                    fee_payer.as_deref().unwrap_or(keypair.as_ref()),
+                    &lookup_table,
+                    &addresses,
+                    authority.as_deref().unwrap_or(keypair.as_ref()),
+                    &tx,
+                    &client,
+                )
+                .await?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::lookup_table::LookupTableCommand::Freeze {
+                lookup_table,
+                authority,
+                fee_payer,
+                tx,
+            } => {
+                let keypair = config.default_signer()?;
+                let fee_payer =
+                    signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+                let authority =
+                    signer::optional_signer_from_source(authority.as_deref(), "authority")?;
+                let result = command::lookup_table::freeze_lookup_table(
+                    fee_payer.as_deref().unwrap_or(keypair.as_ref()),
+                    &lookup_table,
+                    authority.as_deref().unwrap_or(keypair.as_ref()),
+                    &tx,
+                    &client,
+                )
+                .await?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::lookup_table::LookupTableCommand::Deactivate {
+                lookup_table,
+                authority,
+                fee_payer,
+                tx,
+            } => {
+                let keypair = config.default_signer()?;
+                let fee_payer =
+                    signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+                let authority =
+                    signer::optional_signer_from_source(authority.as_deref(), "authority")?;
+                let result = command::lookup_table::deactivate_lookup_table(
+                    fee_payer.as_deref().unwrap_or(keypair.as_ref()),
+                    &lookup_table,
+                    authority.as_deref().unwrap_or(keypair.as_ref()),
+                    &tx,
+                    &client,
+                )
+                .await?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::lookup_table::LookupTableCommand::Close {
+                lookup_table,
+                recipient,
+                authority,
+                fee_payer,
+                tx,
+            } => {
+                let keypair = config.default_signer()?;
+                let fee_payer =
+                    signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+                let fee_payer = fee_payer.as_deref().unwrap_or(keypair.as_ref());
+                let authority =
+                    signer::optional_signer_from_source(authority.as_deref(), "authority")?;
+                let result = command::lookup_table::close_lookup_table(
+                    fee_payer,
+                    &lookup_table,
+                    &recipient.unwrap_or_else(|| fee_payer.pubkey()),
+                    authority.as_deref().unwrap_or(keypair.as_ref()),
+                    &tx,
+                    &client,
+                )
+                .await?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::lookup_table::LookupTableCommand::Show { lookup_table } => {
+                let result =
+                    command::lookup_table::show_lookup_table(&lookup_table, &client).await?;
+                config.output_format.formatted_string(&result)?
+            }
+        },
+        command::Command::Program { command } => match command {
+            command::program::ProgramCommand::Deploy {
+                program_path,
+                program_id,
+                upgrade_authority,
+                max_len,
+                is_final,
+                buffer,
+                fee_payer,
+                tx,
+            } => {
+                let keypair = config.default_signer()?;
+                let fee_payer =
+                    signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+                let program_keypair = signer::signer_from_source(
+                    &command::program::program_keypair_source(
+                        &program_path,
+                        program_id.as_deref(),
+                    )?,
+                    "program-id",
+                )?;
+                let upgrade_authority = signer::optional_signer_from_source(
+                    upgrade_authority.as_deref(),
+                    "upgrade-authority",
+                )?;
+                let result = command::program::deploy(
+                    fee_payer.as_deref().unwrap_or(keypair.as_ref()),
+                    &program_path,
+                    program_keypair.as_ref(),
+                    upgrade_authority.as_deref().unwrap_or(keypair.as_ref()),
+                    max_len,
+                    is_final,
+                    buffer,
+                    &tx,
+                    &client,
+                )
+                .await?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::program::ProgramCommand::Upgrade {
+                program_path,
+                program_id,
+                upgrade_authority,
+                buffer,
+                fee_payer,
+                tx,
+            } => {
+                let keypair = config.default_signer()?;
+                let fee_payer =
+                    signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+                let upgrade_authority = signer::optional_signer_from_source(
+                    upgrade_authority.as_deref(),
+                    "upgrade-authority",
+                )?;
+                let result = command::program::upgrade(
+                    fee_payer.as_deref().unwrap_or(keypair.as_ref()),
+                    &program_path,
+                    &program_id,
+                    upgrade_authority.as_deref().unwrap_or(keypair.as_ref()),
+                    buffer,
+                    &tx,
+                    &client,
+                )
+                .await?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::program::ProgramCommand::SetUpgradeAuthority {
+                program_id,
+                upgrade_authority,
+                new_upgrade_authority,
+                skip_new_upgrade_authority_signer_check,
+                is_final: _,
+                fee_payer,
+                tx,
+            } => {
+                let keypair = config.default_signer()?;
+                let fee_payer =
+                    signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+                let upgrade_authority = signer::optional_signer_from_source(
+                    upgrade_authority.as_deref(),
+                    "upgrade-authority",
+                )?;
+                let new_upgrade_authority = signer::optional_signer_from_source(
+                    new_upgrade_authority.as_deref(),
+                    "new-upgrade-authority",
+                )?;
+                let result = command::program::set_upgrade_authority(
+                    fee_payer.as_deref().unwrap_or(keypair.as_ref()),
+                    &program_id,
+                    upgrade_authority.as_deref().unwrap_or(keypair.as_ref()),
+                    new_upgrade_authority.as_deref(),
+                    skip_new_upgrade_authority_signer_check,
+                    &tx,
+                    &client,
+                )
+                .await?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::program::ProgramCommand::Show {
+                program_id,
+                buffers: _,
+                buffer_authority,
+            } => match program_id {
+                Some(program_id) => {
+                    let result = command::program::show_program(&program_id, &client).await?;
+                    config.output_format.formatted_string(&result)?
+                }
+                None => {
+                    let authority = match buffer_authority {
+                        Some(authority) => authority,
+                        None => config.default_signer()?.pubkey(),
+                    };
+                    let result = command::program::show_buffers(&authority, &client).await?;
+                    config.output_format.formatted_string(&result)?
+                }
+            },
+            command::program::ProgramCommand::Dump {
+                address,
+                output_file,
+            } => {
+                let result = command::program::dump(&address, &output_file, &client).await?;
+                config.output_format.formatted_string(&result)?
+            }
+            command::program::ProgramCommand::Close {
+                buffer,
+                buffers: _,
+                recipient,
+                buffer_authority,
+                fee_payer,
+                tx,
+            } => {
+                let keypair = config.default_signer()?;
+                let fee_payer =
+                    signer::optional_signer_from_source(fee_payer.as_deref(), "fee-payer")?;
+                let fee_payer = fee_payer.as_deref().unwrap_or(keypair.as_ref());
+                let buffer_authority = signer::optional_signer_from_source(
+                    buffer_authority.as_deref(),
+                    "buffer-authority",
+                )?;
+                let result = command::program::close_buffers(
+                    fee_payer,
+                    buffer,
+                    &recipient.unwrap_or_else(|| fee_payer.pubkey()),
+                    buffer_authority.as_deref().unwrap_or(keypair.as_ref()),
+                    &tx,
+                    &client,
+                )
+                .await?;
+                config.output_format.formatted_string(&result)?
+            }
+        },
+        command::Command::CreateTokenAccount {
+            mint,
+            owner,
+            program_2022,
+            tx,
+        } => {
+            let fee_payer = config.default_signer()?;
+            let owner = owner.unwrap_or_else(|| fee_payer.pubkey());
+            let result = command::token::create_token_account(
+                fee_payer.as_ref(),
+                &mint,
+                &owner,
+                program_2022,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::MintTo {
+            mint,
+            amount,
+            recipient,
+            mint_authority,
+            mint_args,
+            fund_recipient,
+            tx,
+        } => {
+            let amount = command::token::TokenAmount::parse(&amount);
+            let fee_payer = config.default_signer()?;
+            let mint_authority =
+                signer::optional_signer_from_source(mint_authority.as_deref(), "mint-authority")?;
+            let recipient = recipient.unwrap_or_else(|| fee_payer.pubkey());
+            let result = command::token::mint_to(
+                fee_payer.as_ref(),
+                mint_authority.as_deref().unwrap_or(fee_payer.as_ref()),
+                &mint,
+                &recipient,
+                &amount,
+                &mint_args,
+                fund_recipient,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::TransferToken {
+            mint,
+            amount,
+            recipient,
+            owner,
+            mint_args,
+            fund_recipient,
+            tx,
+        } => {
+            let amount = command::token::TokenAmount::parse(&amount);
+            let fee_payer = config.default_signer()?;
+            let owner = signer::optional_signer_from_source(owner.as_deref(), "owner")?;
+            let result = command::token::transfer_token(
+                fee_payer.as_ref(),
+                owner.as_deref().unwrap_or(fee_payer.as_ref()),
+                &mint,
+                &recipient,
+                &amount,
+                &mint_args,
+                fund_recipient,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::Burn {
+            mint,
+            amount,
+            owner,
+            mint_args,
+            tx,
+        } => {
+            let amount = command::token::TokenAmount::parse(&amount);
+            let fee_payer = config.default_signer()?;
+            let owner = signer::optional_signer_from_source(owner.as_deref(), "owner")?;
+            let result = command::token::burn(
+                fee_payer.as_ref(),
+                owner.as_deref().unwrap_or(fee_payer.as_ref()),
+                &mint,
+                &amount,
+                &mint_args,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::CloseTokenAccount {
+            mint,
+            owner,
+            destination,
+            program_2022,
+            tx,
+        } => {
+            let fee_payer = config.default_signer()?;
+            let owner = signer::optional_signer_from_source(owner.as_deref(), "owner")?;
+            let result = command::token::close_token_account(
+                fee_payer.as_ref(),
+                owner.as_deref().unwrap_or(fee_payer.as_ref()),
+                &mint,
+                destination,
+                program_2022,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::Approve {
+            mint,
+            delegate,
+            amount,
+            owner,
+            mint_args,
+            tx,
+        } => {
+            let amount = command::token::TokenAmount::parse(&amount);
+            let fee_payer = config.default_signer()?;
+            let owner = signer::optional_signer_from_source(owner.as_deref(), "owner")?;
+            let result = command::token::approve(
+                fee_payer.as_ref(),
+                owner.as_deref().unwrap_or(fee_payer.as_ref()),
+                &mint,
+                &delegate,
+                &amount,
+                &mint_args,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
+        }
+        command::Command::Revoke {
+            mint,
+            owner,
+            program_2022,
+            tx,
+        } => {
+            let fee_payer = config.default_signer()?;
+            let owner = signer::optional_signer_from_source(owner.as_deref(), "owner")?;
+            let result = command::token::revoke(
+                fee_payer.as_ref(),
+                owner.as_deref().unwrap_or(fee_payer.as_ref()),
+                &mint,
+                program_2022,
+                &tx,
+                &client,
+            )
+            .await?;
+            config.output_format.formatted_string(&result)?
         }
         command::Command::TokenAnalysis {
             mint,
@@ -50,7 +1053,7 @@ async fn main() -> anyhow::Result<()> {
             transfer_limit,
             holders_only,
         } => {
-            command::token_analysis::analyze_token(
+            let result = command::token_analysis::analyze_token(
                 &mint,
                 api_key,
                 page,
@@ -62,8 +1065,10 @@ async fn main() -> anyhow::Result<()> {
                 &client,
             )
             .await?;
+            config.output_format.formatted_string(&result)?
         }
     };
+    println!("{}", output);
 
     Ok(())
 }
@@ -0,0 +1,2583 @@
+use std::fmt;
+
+use anyhow::Result;
+use base64::{Engine, prelude::BASE64_STANDARD};
+use clap::ValueEnum;
+use serde::Serialize;
+use solana_account::Account;
+use solana_nonce::state::Data as NonceData;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, native_token::Sol, pubkey::Pubkey};
+
+/// 全局 --output 参数：人类可读文本或 JSON
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Display,
+    Json,
+    JsonCompact,
+}
+
+impl OutputFormat {
+    pub fn formatted_string<T>(&self, item: &T) -> Result<String>
+    where
+        T: Serialize + fmt::Display,
+    {
+        Ok(match self {
+            OutputFormat::Display => item.to_string(),
+            OutputFormat::Json => serde_json::to_string_pretty(item)?,
+            OutputFormat::JsonCompact => serde_json::to_string(item)?,
+        })
+    }
+
+    pub fn is_display(&self) -> bool {
+        matches!(self, OutputFormat::Display)
+    }
+}
+
+/// 以字符串形式输出 SOL 数量，避免浮点误差
+pub fn lamports_to_sol_string(lamports: u64) -> String {
+    format!(
+        "{}.{:09}",
+        lamports / LAMPORTS_PER_SOL,
+        lamports % LAMPORTS_PER_SOL
+    )
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliBalance {
+    pub address: String,
+    pub lamports: u64,
+    pub sol: String,
+}
+
+impl CliBalance {
+    pub fn new(address: &Pubkey, lamports: u64) -> Self {
+        Self {
+            address: address.to_string(),
+            lamports,
+            sol: lamports_to_sol_string(lamports),
+        }
+    }
+}
+
+impl fmt::Display for CliBalance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.address, Sol(self.lamports))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTokenBalance {
+    /// 代币账户地址
+    pub address: String,
+    pub mint: String,
+    pub program: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    pub amount: String,
+    pub decimals: u8,
+    pub ui_amount: String,
+}
+
+impl fmt::Display for CliTokenBalance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({})",
+            self.ui_amount,
+            self.symbol.as_deref().unwrap_or(&self.mint),
+            self.address
+        )
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAddressBalance {
+    #[serde(flatten)]
+    pub balance: CliBalance,
+    /// 仅在 --include-tokens 时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<Vec<CliTokenBalance>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTokenTotal {
+    pub mint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    pub amount: String,
+    pub decimals: u8,
+    pub ui_amount: String,
+    /// 持有该代币的代币账户数量
+    pub accounts: usize,
+}
+
+/// 多地址 balance 的输出，附带 SOL 与各代币的合计
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliBalances {
+    pub balances: Vec<CliAddressBalance>,
+    pub total_lamports: u64,
+    pub total_sol: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub token_totals: Vec<CliTokenTotal>,
+}
+
+impl fmt::Display for CliBalances {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for address in &self.balances {
+            writeln!(f, "{}", address.balance)?;
+            match &address.tokens {
+                Some(tokens) if tokens.is_empty() => writeln!(f, "  (no token accounts)")?,
+                Some(tokens) => {
+                    for token in tokens {
+                        writeln!(f, "  {}", token)?;
+                    }
+                }
+                None => {}
+            }
+        }
+        write!(
+            f,
+            "Total: {} ({} lamports) across {} addresses",
+            Sol(self.total_lamports),
+            self.total_lamports,
+            self.balances.len()
+        )?;
+        if !self.token_totals.is_empty() {
+            write!(f, "\nToken Totals:")?;
+            for total in &self.token_totals {
+                write!(
+                    f,
+                    "\n  {} {} ({}, {} accounts)",
+                    total.ui_amount,
+                    total.symbol.as_deref().unwrap_or(&total.mint),
+                    total.mint,
+                    total.accounts
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// account 命令输出的数据编码
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "camelCase")]
+pub enum CliAccountEncoding {
+    #[default]
+    Base64,
+    Base58,
+    /// 十六进制，文本输出时按每行 16 字节排版
+    Hex,
+}
+
+impl fmt::Display for CliAccountEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliAccountEncoding::Base64 => write!(f, "base64"),
+            CliAccountEncoding::Base58 => write!(f, "base58"),
+            CliAccountEncoding::Hex => write!(f, "hex"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAccount {
+    pub address: String,
+    pub lamports: u64,
+    pub sol: String,
+    pub owner: String,
+    pub executable: bool,
+    pub rent_epoch: u64,
+    pub space: usize,
+    /// 与 RPC `getAccountInfo` 一致的 [数据, 编码] 二元组
+    pub data: (String, CliAccountEncoding),
+    /// 可识别的程序账户 (如 SPL Token) 解码后的内容
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parsed: Option<CliParsedAccount>,
+    /// --output-file 保存原始数据的路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_file: Option<String>,
+    /// 指定 --seed 时地址的派生方式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation: Option<CliDerivation>,
+}
+
+impl CliAccount {
+    pub fn new(address: &Pubkey, account: &Account, encoding: CliAccountEncoding) -> Self {
+        let data = match encoding {
+            CliAccountEncoding::Base64 => BASE64_STANDARD.encode(&account.data),
+            CliAccountEncoding::Base58 => bs58::encode(&account.data).into_string(),
+            CliAccountEncoding::Hex => account
+                .data
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+        };
+        Self {
+            address: address.to_string(),
+            lamports: account.lamports,
+            sol: lamports_to_sol_string(account.lamports),
+            owner: account.owner.to_string(),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+            space: account.data.len(),
+            data: (data, encoding),
+            parsed: None,
+            output_file: None,
+            derivation: None,
+        }
+    }
+}
+
+impl fmt::Display for CliAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Public Key: {}", self.address)?;
+        writeln!(
+            f,
+            "Balance: {} ({} lamports)",
+            Sol(self.lamports),
+            self.lamports
+        )?;
+        writeln!(f, "Owner: {}", self.owner)?;
+        writeln!(f, "Executable: {}", self.executable)?;
+        writeln!(f, "Rent Epoch: {}", self.rent_epoch)?;
+        writeln!(f, "Length: {} bytes", self.space)?;
+        // 能够解码时只显示解码结果，否则回退为原始数据
+        if let Some(parsed) = &self.parsed {
+            write!(f, "{}", parsed)?;
+        } else if self.data.1 == CliAccountEncoding::Hex {
+            write!(f, "Data (hex):")?;
+            for (line, chunk) in self.data.0.as_bytes().chunks(32).enumerate() {
+                let bytes = chunk
+                    .chunks(2)
+                    .map(String::from_utf8_lossy)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                write!(f, "\n  {:08x}: {}", line * 16, bytes)?;
+            }
+        } else if !self.data.0.is_empty() {
+            write!(f, "Data ({}): {}", self.data.1, self.data.0)?;
+        }
+        if let Some(output_file) = &self.output_file {
+            write!(f, "\nWrote account data to {}", output_file)?;
+        }
+        if let Some(derivation) = &self.derivation {
+            write!(f, "\n{}", derivation)?;
+        }
+        Ok(())
+    }
+}
+
+/// account --seed 的检查结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliDerivation {
+    /// program-address 或 create-with-seed；给出的 seed 无法派生出该地址时为 None
+    pub method: Option<String>,
+    pub program_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    pub seeds: Vec<String>,
+    /// 匹配的 bump；seed 中已包含 bump 时为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bump: Option<u8>,
+    /// find_program_address 找到的规范 bump
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical_bump: Option<u8>,
+}
+
+impl fmt::Display for CliDerivation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let seeds = self.seeds.join(", ");
+        match (self.method.as_deref(), &self.base) {
+            (Some("create-with-seed"), Some(base)) => write!(
+                f,
+                "Derivation: create_with_seed(base {}, seed {}, owner {})",
+                base, seeds, self.program_id
+            ),
+            (Some(_), _) => {
+                write!(
+                    f,
+                    "Derivation: PDA of {} with seeds [{}]",
+                    self.program_id, seeds
+                )?;
+                match (self.bump, self.canonical_bump) {
+                    (Some(bump), Some(canonical)) if bump == canonical => {
+                        write!(f, ", bump {} (canonical)", bump)
+                    }
+                    (Some(bump), Some(canonical)) => write!(
+                        f,
+                        ", bump {} (not canonical, canonical bump is {})",
+                        bump, canonical
+                    ),
+                    (Some(bump), None) => write!(f, ", bump {}", bump),
+                    (None, _) => Ok(()),
+                }
+            }
+            (None, _) => write!(
+                f,
+                "Derivation: not derived from seeds [{}] under program {}",
+                seeds, self.program_id
+            ),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSeed {
+    /// 命令行中的写法，如 str:vault
+    pub seed: String,
+    pub hex: String,
+}
+
+/// find-pda 的结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliPda {
+    pub address: String,
+    /// 规范 bump
+    pub bump: u8,
+    pub program_id: String,
+    pub seeds: Vec<CliSeed>,
+}
+
+impl fmt::Display for CliPda {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Address: {}", self.address)?;
+        writeln!(f, "Bump: {}", self.bump)?;
+        write!(f, "Program Id: {}", self.program_id)?;
+        if !self.seeds.is_empty() {
+            write!(f, "\nSeeds:")?;
+            let width = self
+                .seeds
+                .iter()
+                .map(|seed| seed.seed.len())
+                .max()
+                .unwrap_or(0);
+            for seed in &self.seeds {
+                write!(
+                    f,
+                    "\n  {:<width$}  0x{}",
+                    seed.seed,
+                    seed.hex,
+                    width = width
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// create-with-seed 的结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSeedAddress {
+    pub address: String,
+    pub base: String,
+    pub seed: String,
+    pub owner: String,
+}
+
+impl fmt::Display for CliSeedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Address: {}", self.address)?;
+        writeln!(f, "Base: {}", self.base)?;
+        writeln!(f, "Seed: {}", self.seed)?;
+        write!(f, "Owner: {}", self.owner)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliBalanceChange {
+    pub address: String,
+    pub pre: u64,
+    pub post: u64,
+    pub delta: i128,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSimulation {
+    pub units_consumed: Option<u64>,
+    pub fee: u64,
+    pub logs: Vec<String>,
+    pub balance_changes: Vec<CliBalanceChange>,
+}
+
+impl fmt::Display for CliSimulation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Simulation:")?;
+        if let Some(units) = self.units_consumed {
+            writeln!(f, "  Compute Units Consumed: {}", units)?;
+        }
+        writeln!(f, "  Fee: {} ({} lamports)", Sol(self.fee), self.fee)?;
+        if !self.balance_changes.is_empty() {
+            writeln!(f, "  Balance Changes:")?;
+            for change in &self.balance_changes {
+                let sign = if change.delta < 0 { "-" } else { "+" };
+                writeln!(
+                    f,
+                    "    {}: {} -> {} ({}{})",
+                    change.address,
+                    Sol(change.pre),
+                    Sol(change.post),
+                    sign,
+                    Sol(change.delta.unsigned_abs() as u64)
+                )?;
+            }
+        }
+        if !self.logs.is_empty() {
+            writeln!(f, "  Program Logs:")?;
+            for log in &self.logs {
+                writeln!(f, "    {}", log)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAccountMeta {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliInstruction {
+    pub program_id: String,
+    pub accounts: Vec<CliAccountMeta>,
+    /// base58 编码的指令数据
+    pub data: String,
+}
+
+/// 待审阅的交易内容 (--dry-run)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTransaction {
+    /// 交易版本：legacy 或 0
+    pub version: String,
+    pub fee_payer: String,
+    pub recent_blockhash: String,
+    pub signers: Vec<String>,
+    /// v0 交易引用的地址查找表
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub lookup_tables: Vec<String>,
+    pub instructions: Vec<CliInstruction>,
+    /// base64 编码的完整交易 (bincode)
+    pub encoded: String,
+}
+
+impl fmt::Display for CliTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Transaction:")?;
+        writeln!(f, "  Version: {}", self.version)?;
+        writeln!(f, "  Fee Payer: {}", self.fee_payer)?;
+        writeln!(f, "  Recent Blockhash: {}", self.recent_blockhash)?;
+        writeln!(f, "  Signers: {}", self.signers.join(", "))?;
+        if !self.lookup_tables.is_empty() {
+            writeln!(
+                f,
+                "  Address Lookup Tables: {}",
+                self.lookup_tables.join(", ")
+            )?;
+        }
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            writeln!(f, "  Instruction #{}: {}", index, instruction.program_id)?;
+            for account in &instruction.accounts {
+                let mut flags = Vec::new();
+                if account.is_signer {
+                    flags.push("signer");
+                }
+                if account.is_writable {
+                    flags.push("writable");
+                }
+                writeln!(f, "    {} [{}]", account.pubkey, flags.join(", "))?;
+            }
+            writeln!(f, "    Data: {}", instruction.data)?;
+        }
+        write!(f, "  Encoded (base64): {}", self.encoded)
+    }
+}
+
+/// 解码后的指令：名称与按顺序排列的字段 (账户与参数)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedInstruction {
+    pub name: String,
+    #[serde(serialize_with = "serialize_fields")]
+    pub info: Vec<(String, String)>,
+}
+
+/// 将有序的字段列表序列化为 JSON 对象，保持字段顺序
+fn serialize_fields<S: serde::Serializer>(
+    fields: &[(String, String)],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_map(fields.iter().map(|(name, value)| (name, value)))
+}
+
+/// 已上链交易中的一条指令 (含其触发的内部指令)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliConfirmedInstruction {
+    pub program_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parsed: Option<CliParsedInstruction>,
+    pub accounts: Vec<CliAccountMeta>,
+    /// base58 编码的指令数据
+    pub data: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub inner_instructions: Vec<CliConfirmedInstruction>,
+}
+
+impl CliConfirmedInstruction {
+    fn write(&self, f: &mut fmt::Formatter, label: &str, indent: usize) -> fmt::Result {
+        let pad = " ".repeat(indent);
+        write!(f, "\n{}{}: ", pad, label)?;
+        match &self.program {
+            Some(program) => write!(f, "{} ({})", program, self.program_id)?,
+            None => write!(f, "{}", self.program_id)?,
+        }
+        match &self.parsed {
+            Some(parsed) => {
+                write!(f, " {}", parsed.name)?;
+                for (name, value) in &parsed.info {
+                    write!(f, "\n{}  {}: {}", pad, name, value)?;
+                }
+            }
+            None => {
+                for account in &self.accounts {
+                    let mut flags = Vec::new();
+                    if account.is_signer {
+                        flags.push("signer");
+                    }
+                    if account.is_writable {
+                        flags.push("writable");
+                    }
+                    write!(f, "\n{}  {} [{}]", pad, account.pubkey, flags.join(", "))?;
+                }
+                write!(f, "\n{}  Data: {}", pad, self.data)?;
+            }
+        }
+        for (index, inner) in self.inner_instructions.iter().enumerate() {
+            inner.write(f, &format!("{}.{}", label, index), indent + 2)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTokenBalanceChange {
+    pub account: String,
+    pub mint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    pub decimals: u8,
+    pub pre: String,
+    pub post: String,
+    pub delta: String,
+}
+
+/// confirm / tx 命令的输出：已上链交易的解码结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliConfirmedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_status: Option<String>,
+    /// legacy 或 v0
+    pub version: String,
+    /// 交易执行失败时的错误
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub fee: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_units_consumed: Option<u64>,
+    pub recent_blockhash: String,
+    pub signers: Vec<String>,
+    pub instructions: Vec<CliConfirmedInstruction>,
+    pub logs: Vec<String>,
+    pub balance_changes: Vec<CliBalanceChange>,
+    pub token_balance_changes: Vec<CliTokenBalanceChange>,
+}
+
+impl fmt::Display for CliConfirmedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Transaction: {}", self.signature)?;
+        match &self.confirmation_status {
+            Some(status) => write!(f, "\n  Slot: {} ({})", self.slot, status)?,
+            None => write!(f, "\n  Slot: {}", self.slot)?,
+        }
+        if let Some(block_time) = self.block_time {
+            write!(f, "\n  Block Time: {} (unix timestamp)", block_time)?;
+        }
+        write!(f, "\n  Version: {}", self.version)?;
+        match &self.error {
+            Some(error) => write!(f, "\n  Status: Failed ({})", error)?,
+            None => write!(f, "\n  Status: Success")?,
+        }
+        write!(f, "\n  Fee: {} ({} lamports)", Sol(self.fee), self.fee)?;
+        if let Some(units) = self.compute_units_consumed {
+            write!(f, "\n  Compute Units Consumed: {}", units)?;
+        }
+        write!(f, "\n  Recent Blockhash: {}", self.recent_blockhash)?;
+        write!(f, "\n  Signers: {}", self.signers.join(", "))?;
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            instruction.write(f, &format!("Instruction #{}", index), 2)?;
+        }
+        if !self.balance_changes.is_empty() {
+            write!(f, "\n  Balance Changes:")?;
+            for change in &self.balance_changes {
+                let sign = if change.delta < 0 { "-" } else { "+" };
+                writeln!(
+                    f,
+                    "    {}: {} -> {} ({}{})",
+                    change.address,
+                    Sol(change.pre),
+                    Sol(change.post),
+                    sign,
+                    Sol(change.delta.unsigned_abs() as u64)
+                )?;
+            }
+        }
+        if !self.token_balance_changes.is_empty() {
+            write!(f, "\n  Token Balance Changes:")?;
+            for change in &self.token_balance_changes {
+                writeln!(
+                    f,
+                    "    {} (mint {}): {} -> {} ({})",
+                    change.account, change.mint, change.pre, change.post, change.delta
+                )?;
+            }
+        }
+        if !self.logs.is_empty() {
+            write!(f, "\n  Program Logs:")?;
+            for log in &self.logs {
+                write!(f, "\n    {}", log)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// history --summary 中一笔交易对所查询地址的影响
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliHistorySummary {
+    pub fee: u64,
+    pub lamports_change: i128,
+    /// `数量变化 mint`，如 `-1.5 <MINT>`
+    pub token_changes: Vec<String>,
+    /// 调用的顶层指令，如 `System Program: transfer`
+    pub instructions: Vec<String>,
+}
+
+impl CliHistorySummary {
+    /// 带符号的 SOL 变化量
+    pub fn sol_change(&self) -> String {
+        signed_sol_string(self.lamports_change)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliHistoryEntry {
+    pub signature: String,
+    pub slot: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<CliHistorySummary>,
+}
+
+impl CliHistoryEntry {
+    pub fn status(&self) -> &'static str {
+        if self.error.is_some() {
+            "failed"
+        } else {
+            "success"
+        }
+    }
+}
+
+/// history 命令的输出，从新到旧排列
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliHistory {
+    pub address: String,
+    pub entries: Vec<CliHistoryEntry>,
+    /// 可能还有更早的交易时，继续翻页使用的 --before
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export_file: Option<String>,
+}
+
+impl fmt::Display for CliHistory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Transaction history of {} ({} transactions):",
+            self.address,
+            self.entries.len()
+        )?;
+        for entry in &self.entries {
+            write!(
+                f,
+                "\n  {}  slot {}  {}",
+                entry.signature,
+                entry.slot,
+                entry.status()
+            )?;
+            if let Some(block_time) = entry.block_time {
+                write!(f, "  time {}", block_time)?;
+            }
+            if let Some(summary) = &entry.summary {
+                write!(f, "  SOL {}", summary.sol_change())?;
+                if !summary.token_changes.is_empty() {
+                    write!(f, "  tokens {}", summary.token_changes.join(", "))?;
+                }
+                if !summary.instructions.is_empty() {
+                    write!(f, "  [{}]", summary.instructions.join(", "))?;
+                }
+            }
+            if let Some(error) = &entry.error {
+                write!(f, "  ({})", error)?;
+            }
+            if let Some(memo) = &entry.memo {
+                write!(f, "  memo: {}", memo)?;
+            }
+        }
+        if let Some(next_before) = &self.next_before {
+            write!(
+                f,
+                "\nMore transactions may exist, continue with --before {}",
+                next_before
+            )?;
+        }
+        if let Some(export_file) = &self.export_file {
+            write!(
+                f,
+                "\nWrote {} entries to {}",
+                self.entries.len(),
+                export_file
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 带符号的 SOL 变化量
+fn signed_sol_string(lamports_change: i128) -> String {
+    let sign = if lamports_change < 0 { "-" } else { "+" };
+    format!(
+        "{}{}",
+        sign,
+        lamports_to_sol_string(lamports_change.unsigned_abs() as u64)
+    )
+}
+
+/// watch account 推送的一次账户更新，第一条为订阅前查询到的初始状态
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAccountUpdate {
+    pub address: String,
+    pub slot: u64,
+    /// 账户不存在 (或已关闭) 时为 None
+    pub owner: Option<String>,
+    pub lamports: u64,
+    /// 相对上一次更新的 lamports 变化，初始状态为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lamports_change: Option<i128>,
+    pub data_len: usize,
+    /// 代币账户的余额
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<CliTokenAmountUpdate>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTokenAmountUpdate {
+    pub mint: String,
+    /// 最小单位的余额
+    pub amount: String,
+    /// 无法获取 mint 精度时为 None，此时按最小单位显示
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+    pub ui_amount: String,
+    /// 相对上一次更新的带符号变化量 (按精度换算)，初始状态或无变化时为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change: Option<String>,
+}
+
+impl fmt::Display for CliAccountUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[slot {}] {}: ", self.slot, self.address)?;
+        let lamports_change = self.lamports_change.filter(|change| *change != 0);
+        let Some(owner) = &self.owner else {
+            write!(f, "account does not exist")?;
+            if let Some(change) = lamports_change {
+                write!(f, " ({} SOL)", signed_sol_string(change))?;
+            }
+            return Ok(());
+        };
+        write!(f, "{} SOL", lamports_to_sol_string(self.lamports))?;
+        if let Some(change) = lamports_change {
+            write!(f, " ({})", signed_sol_string(change))?;
+        }
+        if let Some(token) = &self.token {
+            write!(f, ", token {}", token.ui_amount)?;
+            if let Some(change) = &token.change {
+                write!(f, " ({})", change)?;
+            }
+            write!(f, " of mint {}", token.mint)?;
+        }
+        write!(f, ", owner {}, {} bytes", owner, self.data_len)
+    }
+}
+
+/// watch logs 推送的一笔交易的程序日志
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliLogsUpdate {
+    pub slot: u64,
+    pub signature: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+}
+
+impl fmt::Display for CliLogsUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[slot {}] {}", self.slot, self.signature)?;
+        match &self.error {
+            Some(error) => write!(f, " Failed ({})", error)?,
+            None => write!(f, " Success")?,
+        }
+        for log in &self.logs {
+            write!(f, "\n  {}", log)?;
+        }
+        Ok(())
+    }
+}
+
+/// watch signature 推送的交易状态
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSignatureUpdate {
+    pub signature: String,
+    pub slot: u64,
+    /// received 表示节点已收到交易，否则为达到的提交级别
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl fmt::Display for CliSignatureUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[slot {}] Transaction {}: {}",
+            self.slot, self.signature, self.status
+        )?;
+        if let Some(error) = &self.error {
+            write!(f, ", failed ({})", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// watch slot 推送的 slot 变化
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSlotUpdate {
+    pub slot: u64,
+    pub parent: u64,
+    pub root: u64,
+}
+
+impl fmt::Display for CliSlotUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Slot {} (parent {}, root {})",
+            self.slot, self.parent, self.root
+        )
+    }
+}
+
+/// lookup-table create 的结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliNewLookupTable {
+    pub lookup_table: String,
+    #[serde(flatten)]
+    pub result: CliTxResult,
+}
+
+impl fmt::Display for CliNewLookupTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Lookup Table: {}", self.lookup_table)?;
+        write!(f, "{}", self.result)
+    }
+}
+
+/// lookup-table extend 的结果，地址较多时包含多笔交易
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliLookupTableExtend {
+    pub lookup_table: String,
+    pub added: Vec<String>,
+    /// 已在表中而被跳过的地址
+    pub skipped: Vec<String>,
+    pub transactions: Vec<CliTxResult>,
+}
+
+impl fmt::Display for CliLookupTableExtend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Lookup Table: {}", self.lookup_table)?;
+        write!(
+            f,
+            "Added {} address(es), skipped {} already in the table",
+            self.added.len(),
+            self.skipped.len()
+        )?;
+        for (index, result) in self.transactions.iter().enumerate() {
+            write!(
+                f,
+                "\n\nTransaction {}/{}:\n{}",
+                index + 1,
+                self.transactions.len(),
+                result
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// lookup-table show 的结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliLookupTable {
+    pub address: String,
+    /// active、deactivating 或 deactivated (按当前 slot 估算)
+    pub status: String,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub table: CliParsedLookupTable,
+}
+
+impl fmt::Display for CliLookupTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Lookup Table: {}", self.address)?;
+        writeln!(f, "Status: {}", self.status)?;
+        writeln!(f, "Balance: {}", Sol(self.lamports))?;
+        write!(f, "{}", self.table)
+    }
+}
+
+/// program deploy / upgrade 的结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliProgramDeploy {
+    pub program_id: String,
+    pub buffer: String,
+    pub program_len: usize,
+    /// 本次发送 (dry-run 时为需要发送) 的写入交易数
+    pub write_transactions: usize,
+    #[serde(flatten)]
+    pub result: CliTxResult,
+}
+
+impl fmt::Display for CliProgramDeploy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Program Id: {}", self.program_id)?;
+        writeln!(f, "Buffer: {}", self.buffer)?;
+        writeln!(
+            f,
+            "Program Size: {} bytes ({} write transactions)",
+            self.program_len, self.write_transactions
+        )?;
+        write!(f, "{}", self.result)
+    }
+}
+
+/// program show 的结果；非 upgradeable 程序没有 programdata 与升级授权
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliProgram {
+    pub program_id: String,
+    pub owner: String,
+    pub program_data: Option<String>,
+    /// 为 None 时程序不可升级
+    pub authority: Option<String>,
+    pub last_deploy_slot: Option<u64>,
+    pub data_len: usize,
+    /// programdata (非 upgradeable 程序为程序账户) 的余额
+    pub lamports: u64,
+}
+
+impl fmt::Display for CliProgram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Program Id: {}", self.program_id)?;
+        writeln!(f, "Owner: {}", self.owner)?;
+        if let Some(program_data) = &self.program_data {
+            writeln!(f, "ProgramData Address: {}", program_data)?;
+        }
+        writeln!(
+            f,
+            "Authority: {}",
+            self.authority.as_deref().unwrap_or("(none, immutable)")
+        )?;
+        if let Some(slot) = self.last_deploy_slot {
+            writeln!(f, "Last Deployed In Slot: {}", slot)?;
+        }
+        writeln!(f, "Data Length: {} bytes", self.data_len)?;
+        write!(f, "Balance: {}", Sol(self.lamports))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliProgramBuffer {
+    pub address: String,
+    pub authority: Option<String>,
+    pub lamports: u64,
+}
+
+/// program show --buffers 的结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliProgramBuffers {
+    pub authority: String,
+    pub buffers: Vec<CliProgramBuffer>,
+}
+
+impl fmt::Display for CliProgramBuffers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.buffers.is_empty() {
+            return write!(
+                f,
+                "No buffer accounts found for authority {}",
+                self.authority
+            );
+        }
+        writeln!(f, "Buffers for authority {}:", self.authority)?;
+        writeln!(f, "{:<44}  {:>20}", "Buffer Address", "Balance")?;
+        for buffer in &self.buffers {
+            writeln!(
+                f,
+                "{:<44}  {:>20}",
+                buffer.address,
+                Sol(buffer.lamports).to_string()
+            )?;
+        }
+        write!(
+            f,
+            "Total: {}",
+            Sol(self.buffers.iter().map(|buffer| buffer.lamports).sum())
+        )
+    }
+}
+
+/// program dump 的结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliProgramDump {
+    pub address: String,
+    pub output_file: String,
+    pub data_len: usize,
+}
+
+impl fmt::Display for CliProgramDump {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Wrote {} bytes of {} to {}",
+            self.data_len, self.address, self.output_file
+        )
+    }
+}
+
+/// program close 的结果，buffer 较多时包含多笔交易
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliProgramClose {
+    pub recipient: String,
+    pub buffers: Vec<String>,
+    /// 回收的租金 (离线签名时为 0)
+    pub lamports: u64,
+    pub transactions: Vec<CliTxResult>,
+}
+
+impl fmt::Display for CliProgramClose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.buffers.is_empty() {
+            return write!(f, "No buffer accounts to close");
+        }
+        write!(
+            f,
+            "Closed {} buffer account(s), reclaimed {} to {}",
+            self.buffers.len(),
+            Sol(self.lamports),
+            self.recipient
+        )?;
+        for (index, result) in self.transactions.iter().enumerate() {
+            write!(
+                f,
+                "\n\nTransaction {}/{}:\n{}",
+                index + 1,
+                self.transactions.len(),
+                result
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliClusterVersion {
+    pub solana_core: String,
+    /// FeatureSet 标识的前 4 字节，同一特性集的节点相同
+    pub feature_set: Option<u32>,
+}
+
+impl fmt::Display for CliClusterVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Solana Core: {}", self.solana_core)?;
+        if let Some(feature_set) = self.feature_set {
+            write!(f, "\nFeature Set: {}", feature_set)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliEpochInfo {
+    pub epoch: u64,
+    pub absolute_slot: u64,
+    pub block_height: u64,
+    pub slot_index: u64,
+    pub slots_in_epoch: u64,
+    pub first_slot: u64,
+    pub transaction_count: Option<u64>,
+    pub completed_percent: f64,
+    /// 按最近性能样本估算的平均 slot 时长
+    pub ms_per_slot: u64,
+    /// 距离 epoch 结束的估算秒数
+    pub remaining_secs: u64,
+}
+
+impl fmt::Display for CliEpochInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Block height: {}", self.block_height)?;
+        writeln!(f, "Slot: {}", self.absolute_slot)?;
+        writeln!(f, "Epoch: {}", self.epoch)?;
+        if let Some(count) = self.transaction_count {
+            writeln!(f, "Transaction Count: {}", count)?;
+        }
+        writeln!(
+            f,
+            "Epoch Slot Range: [{}..{})",
+            self.first_slot,
+            self.first_slot + self.slots_in_epoch
+        )?;
+        writeln!(f, "Epoch Completed Percent: {:.3}%", self.completed_percent)?;
+        writeln!(
+            f,
+            "Epoch Completed Slots: {}/{} ({} remaining)",
+            self.slot_index,
+            self.slots_in_epoch,
+            self.slots_in_epoch.saturating_sub(self.slot_index)
+        )?;
+        write!(
+            f,
+            "Epoch Remaining Time: ~{} ({} ms per slot)",
+            duration_string(self.remaining_secs),
+            self.ms_per_slot
+        )
+    }
+}
+
+/// 以 `1d 2h 3m 4s` 的形式输出时长，省略前导的零
+fn duration_string(secs: u64) -> String {
+    let (days, hours, minutes, secs) = (
+        secs / 86_400,
+        secs % 86_400 / 3_600,
+        secs % 3_600 / 60,
+        secs % 60,
+    );
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", secs),
+        (0, 0, _) => format!("{}m {}s", minutes, secs),
+        (0, _, _) => format!("{}h {}m {}s", hours, minutes, secs),
+        _ => format!("{}d {}h {}m {}s", days, hours, minutes, secs),
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliValidator {
+    pub identity: String,
+    pub vote_account: String,
+    pub commission: u8,
+    pub last_vote: u64,
+    pub root_slot: u64,
+    pub activated_stake: u64,
+    pub stake_percent: f64,
+    /// 最近一个 epoch 获得的投票积分
+    pub epoch_credits: u64,
+    /// 当前 epoch 的跳块率 (百分比)，尚无 leader slot 时为 None
+    pub skip_rate: Option<f64>,
+    pub delinquent: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliValidators {
+    pub total_active_stake: u64,
+    pub total_current_stake: u64,
+    pub total_delinquent_stake: u64,
+    pub current_count: usize,
+    pub delinquent_count: usize,
+    pub validators: Vec<CliValidator>,
+}
+
+impl fmt::Display for CliValidators {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "  {:<44}  {:<44}  {:>10}  {:>10}  {:>10}  {:>9}  {:>8}  Active Stake",
+            "Identity",
+            "Vote Account",
+            "Commission",
+            "Last Vote",
+            "Root Slot",
+            "Skip Rate",
+            "Credits"
+        )?;
+        for validator in &self.validators {
+            writeln!(
+                f,
+                "{} {:<44}  {:<44}  {:>9}%  {:>10}  {:>10}  {:>9}  {:>8}  {} ({:.2}%)",
+                if validator.delinquent { "!" } else { " " },
+                validator.identity,
+                validator.vote_account,
+                validator.commission,
+                validator.last_vote,
+                validator.root_slot,
+                validator
+                    .skip_rate
+                    .map_or_else(|| "-".to_string(), |rate| format!("{:.2}%", rate)),
+                validator.epoch_credits,
+                Sol(validator.activated_stake),
+                validator.stake_percent
+            )?;
+        }
+        let stake_percent = |stake: u64| {
+            if self.total_active_stake == 0 {
+                0.0
+            } else {
+                stake as f64 * 100.0 / self.total_active_stake as f64
+            }
+        };
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Validators: {} current, {} delinquent (marked with !)",
+            self.current_count, self.delinquent_count
+        )?;
+        writeln!(f, "Active Stake: {}", Sol(self.total_active_stake))?;
+        writeln!(
+            f,
+            "Current Stake: {} ({:.2}%)",
+            Sol(self.total_current_stake),
+            stake_percent(self.total_current_stake)
+        )?;
+        write!(
+            f,
+            "Delinquent Stake: {} ({:.2}%)",
+            Sol(self.total_delinquent_stake),
+            stake_percent(self.total_delinquent_stake)
+        )
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliLeaderSlot {
+    pub slot: u64,
+    pub leader: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliLeaderSchedule {
+    pub epoch: u64,
+    pub first_slot: u64,
+    pub leaders: Vec<CliLeaderSlot>,
+}
+
+impl fmt::Display for CliLeaderSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Leader Schedule for epoch {} (first slot {}):",
+            self.epoch, self.first_slot
+        )?;
+        for leader in &self.leaders {
+            write!(f, "\n  {:<12} {}", leader.slot, leader.leader)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliBlockProductionEntry {
+    pub identity: String,
+    pub leader_slots: u64,
+    pub blocks_produced: u64,
+    pub skipped_slots: u64,
+    pub skip_rate: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliBlockProduction {
+    pub epoch: u64,
+    pub first_slot: u64,
+    pub last_slot: u64,
+    pub total_slots: u64,
+    pub total_blocks_produced: u64,
+    pub total_slots_skipped: u64,
+    pub skip_rate: f64,
+    pub leaders: Vec<CliBlockProductionEntry>,
+}
+
+impl fmt::Display for CliBlockProduction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Block Production for epoch {} (slots {}..={}):",
+            self.epoch, self.first_slot, self.last_slot
+        )?;
+        writeln!(
+            f,
+            "  {:<44}  {:>12}  {:>15}  {:>13}  {:>9}",
+            "Identity", "Leader Slots", "Blocks Produced", "Skipped Slots", "Skip Rate"
+        )?;
+        for leader in &self.leaders {
+            writeln!(
+                f,
+                "  {:<44}  {:>12}  {:>15}  {:>13}  {:>8.2}%",
+                leader.identity,
+                leader.leader_slots,
+                leader.blocks_produced,
+                leader.skipped_slots,
+                leader.skip_rate
+            )?;
+        }
+        write!(
+            f,
+            "  {:<44}  {:>12}  {:>15}  {:>13}  {:>8.2}%",
+            "Total",
+            self.total_slots,
+            self.total_blocks_produced,
+            self.total_slots_skipped,
+            self.skip_rate
+        )
+    }
+}
+
+/// keygen new / recover 的输出
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliKeypair {
+    pub pubkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outfile: Option<String>,
+    /// --no-outfile 时输出的 keypair (JSON 字节数组)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub has_passphrase: bool,
+}
+
+impl fmt::Display for CliKeypair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.outfile {
+            Some(outfile) => write!(f, "Wrote keypair to {}", outfile)?,
+            None => write!(
+                f,
+                "Keypair (not saved): {}",
+                self.keypair.as_deref().unwrap_or_default()
+            )?,
+        }
+        write!(f, "\nPubkey: {}", self.pubkey)?;
+        if let Some(mnemonic) = &self.mnemonic {
+            if let Some(derivation_path) = &self.derivation_path {
+                write!(f, "\nDerivation Path: {}", derivation_path)?;
+            }
+            let passphrase = if self.has_passphrase {
+                " and your BIP39 passphrase"
+            } else {
+                ""
+            };
+            write!(
+                f,
+                "\nSave this seed phrase{} to recover your keypair:\n{}",
+                passphrase, mnemonic
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// keygen verify 的输出，验证失败时返回错误
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliKeypairVerification {
+    pub pubkey: String,
+}
+
+impl fmt::Display for CliKeypairVerification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Verification for public key {}: Success", self.pubkey)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliGrindKeypair {
+    pub pubkey: String,
+    pub outfile: String,
+    /// 匹配的条件
+    pub pattern: String,
+}
+
+/// keygen grind 的输出
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliGrind {
+    pub keypairs: Vec<CliGrindKeypair>,
+    /// 尝试生成的 keypair 总数
+    pub attempts: u64,
+    pub elapsed_secs: f64,
+}
+
+impl fmt::Display for CliGrind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Found {} keypair(s) after {} attempts in {:.1}s:",
+            self.keypairs.len(),
+            self.attempts,
+            self.elapsed_secs
+        )?;
+        for keypair in &self.keypairs {
+            write!(
+                f,
+                "\n  {}  ({})  -> {}",
+                keypair.pubkey, keypair.pattern, keypair.outfile
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// --sign-only 的输出，可在联网机器上通过 `--blockhash` 与 `--signer PUBKEY=SIGNATURE` 组装交易
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSignOnly {
+    pub blockhash: String,
+    /// 已完成的签名，格式为 `PUBKEY=SIGNATURE`
+    pub signers: Vec<String>,
+    /// 尚未签名的账户
+    pub absent: Vec<String>,
+    /// 签名校验失败的账户
+    pub bad_signatures: Vec<String>,
+    /// base64 编码的序列化消息 (即各签名者签署的内容)
+    pub message: String,
+}
+
+impl fmt::Display for CliSignOnly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Blockhash: {}", self.blockhash)?;
+        if !self.signers.is_empty() {
+            writeln!(f, "Signers (Pubkey=Signature):")?;
+            for signer in &self.signers {
+                writeln!(f, "  {}", signer)?;
+            }
+        }
+        if !self.absent.is_empty() {
+            writeln!(f, "Absent Signers (Pubkey):")?;
+            for pubkey in &self.absent {
+                writeln!(f, "  {}", pubkey)?;
+            }
+        }
+        if !self.bad_signatures.is_empty() {
+            writeln!(f, "Bad Signatures (Pubkey):")?;
+            for pubkey in &self.bad_signatures {
+                writeln!(f, "  {}", pubkey)?;
+            }
+        }
+        writeln!(f, "Message (base64): {}", self.message)?;
+        write!(
+            f,
+            "\nSign only: 交易未发送，请在联网机器上使用相同参数并附加 --blockhash {} 与上述 --signer 组装广播",
+            self.blockhash
+        )
+    }
+}
+
+/// 状态变更类命令的通用结果：模拟结果、(dry-run 时) 交易内容、(发送后) 交易签名、(离线签名时) 签名结果
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTxResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simulation: Option<CliSimulation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<CliTransaction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_only: Option<CliSignOnly>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl fmt::Display for CliTxResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(sign_only) = &self.sign_only {
+            return write!(f, "{}", sign_only);
+        }
+        if let Some(simulation) = &self.simulation {
+            write!(f, "{}", simulation)?;
+        }
+        if let Some(transaction) = &self.transaction {
+            write!(f, "{}", transaction)?;
+        }
+        match &self.signature {
+            Some(signature) => write!(f, "Transaction Signature: {}", signature),
+            None => write!(f, "\nDry run: 交易未发送"),
+        }
+    }
+}
+
+/// distribute 中单笔交易的结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliDistributeTransaction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    pub recipients: usize,
+    pub amount: String,
+    /// confirmed、failed、sent (状态未知，重新运行时核对) 或 simulated (dry-run)
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 尚未确认到账的 CSV 行
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliDistributeEntry {
+    pub line: usize,
+    pub recipient: String,
+    pub amount: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// distribute 的对账报告
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliDistribute {
+    /// SOL 或代币 mint 地址
+    pub asset: String,
+    pub ledger: String,
+    pub dry_run: bool,
+    pub recipients: usize,
+    pub total_amount: String,
+    pub confirmed: usize,
+    pub confirmed_amount: String,
+    /// 在之前的运行中已确认的行数
+    pub previously_confirmed: usize,
+    pub failed: usize,
+    pub pending: usize,
+    pub remaining_amount: String,
+    /// 本次运行的交易手续费 (dry-run 时为模拟估算)
+    pub fee: u64,
+    pub transactions: Vec<CliDistributeTransaction>,
+    pub unpaid: Vec<CliDistributeEntry>,
+}
+
+impl fmt::Display for CliDistribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Distribution Report ({}):", self.asset)?;
+        writeln!(f, "  Ledger: {}", self.ledger)?;
+        writeln!(
+            f,
+            "  Recipients: {} (total {})",
+            self.recipients, self.total_amount
+        )?;
+        writeln!(
+            f,
+            "  Confirmed: {} ({}), {} in earlier runs",
+            self.confirmed, self.confirmed_amount, self.previously_confirmed
+        )?;
+        writeln!(f, "  Failed: {}", self.failed)?;
+        writeln!(f, "  Pending: {}", self.pending)?;
+        writeln!(f, "  Remaining: {}", self.remaining_amount)?;
+        write!(f, "  Fee: {} ({} lamports)", Sol(self.fee), self.fee)?;
+        if !self.transactions.is_empty() {
+            write!(f, "\nTransactions:")?;
+            for transaction in &self.transactions {
+                write!(
+                    f,
+                    "\n  {} ({} recipients, {}): {}",
+                    transaction.signature.as_deref().unwrap_or("(not sent)"),
+                    transaction.recipients,
+                    transaction.amount,
+                    transaction.status
+                )?;
+                if let Some(error) = &transaction.error {
+                    write!(f, " - {}", error)?;
+                }
+            }
+        }
+        if !self.unpaid.is_empty() {
+            write!(f, "\nUnpaid:")?;
+            for entry in &self.unpaid {
+                write!(
+                    f,
+                    "\n  line {}: {} {} - {}",
+                    entry.line, entry.recipient, entry.amount, entry.status
+                )?;
+                if let Some(error) = &entry.error {
+                    write!(f, " ({})", error)?;
+                }
+            }
+        }
+        if self.dry_run {
+            write!(f, "\nDry run: 交易未发送")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliMintToken {
+    pub mint: String,
+    /// spl-token 或 spl-token-2022
+    pub program: String,
+    pub decimals: u8,
+    pub mint_authority: String,
+    pub freeze_authority: Option<String>,
+    /// 启用的 Token-2022 扩展
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
+    /// 本命令生成的 mint keypair 的保存路径 (私钥不会输出到终端)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keypair_file: Option<String>,
+    #[serde(flatten)]
+    pub result: CliTxResult,
+}
+
+impl fmt::Display for CliMintToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Mint: {}", self.mint)?;
+        writeln!(f, "Program: {}", self.program)?;
+        writeln!(f, "Decimals: {}", self.decimals)?;
+        writeln!(f, "Mint Authority: {}", self.mint_authority)?;
+        writeln!(
+            f,
+            "Freeze Authority: {}",
+            self.freeze_authority.as_deref().unwrap_or("(none)")
+        )?;
+        if !self.extensions.is_empty() {
+            writeln!(f, "Extensions: {}", self.extensions.join(", "))?;
+        }
+        if let Some(keypair_file) = &self.keypair_file {
+            writeln!(f, "Mint Keypair: 已保存到 {}", keypair_file)?;
+        }
+        write!(f, "{}", self.result)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTokenAccount {
+    pub account: String,
+    #[serde(flatten)]
+    pub result: CliTxResult,
+}
+
+impl fmt::Display for CliTokenAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Token Account: {}", self.account)?;
+        write!(f, "{}", self.result)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliNewStakeAccount {
+    pub stake_account: String,
+    #[serde(flatten)]
+    pub result: CliTxResult,
+}
+
+impl fmt::Display for CliNewStakeAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Stake Account: {}", self.stake_account)?;
+        write!(f, "{}", self.result)
+    }
+}
+
+/// 按 stake history 计算的激活状态
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStakeActivation {
+    pub epoch: u64,
+    /// activating、active、deactivating 或 inactive
+    pub state: String,
+    pub active_stake: u64,
+    pub activating_stake: u64,
+    pub deactivating_stake: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStakeReward {
+    pub epoch: u64,
+    pub effective_slot: u64,
+    pub amount: u64,
+    pub post_balance: u64,
+    pub percent_change: f64,
+    pub commission: Option<u8>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStakeAccount {
+    pub address: String,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub stake: CliParsedStake,
+    /// 未委托时为 None
+    pub activation: Option<CliStakeActivation>,
+    pub rewards: Vec<CliStakeReward>,
+}
+
+impl fmt::Display for CliStakeAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Address: {}", self.address)?;
+        writeln!(
+            f,
+            "Balance: {} ({} lamports)",
+            Sol(self.lamports),
+            self.lamports
+        )?;
+        write!(f, "{}", self.stake)?;
+        if let Some(activation) = &self.activation {
+            write!(
+                f,
+                "\n  Status (epoch {}): {}",
+                activation.epoch, activation.state
+            )?;
+            write!(f, "\n  Active Stake: {}", Sol(activation.active_stake))?;
+            if activation.activating_stake > 0 {
+                write!(
+                    f,
+                    "\n  Activating Stake: {}",
+                    Sol(activation.activating_stake)
+                )?;
+            }
+            if activation.deactivating_stake > 0 {
+                write!(
+                    f,
+                    "\n  Deactivating Stake: {}",
+                    Sol(activation.deactivating_stake)
+                )?;
+            }
+        }
+        if !self.rewards.is_empty() {
+            write!(
+                f,
+                "\nRewards:\n  {:<8}{:<14}{:<22}{:<22}{:<10}Commission",
+                "Epoch", "Reward Slot", "Amount", "New Balance", "Change"
+            )?;
+            for reward in &self.rewards {
+                write!(
+                    f,
+                    "\n  {:<8}{:<14}{:<22}{:<22}{:<10}{}",
+                    reward.epoch,
+                    reward.effective_slot,
+                    Sol(reward.amount).to_string(),
+                    Sol(reward.post_balance).to_string(),
+                    format!("{:.4}%", reward.percent_change),
+                    reward
+                        .commission
+                        .map_or_else(|| "-".to_string(), |commission| format!("{}%", commission))
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 按 owner 程序解码后的账户数据
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", tag = "type", content = "info")]
+pub enum CliParsedAccount {
+    Mint(CliParsedMint),
+    TokenAccount(CliParsedTokenAccount),
+    Nonce(CliParsedNonce),
+    Stake(CliParsedStake),
+    Vote(CliParsedVote),
+    Program(CliParsedProgram),
+    ProgramData(CliParsedProgramData),
+    Buffer(CliParsedBuffer),
+    LookupTable(CliParsedLookupTable),
+    /// 程序已分配但尚未初始化的账户 (如未初始化的 nonce / stake 账户)
+    Uninitialized,
+}
+
+impl fmt::Display for CliParsedAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliParsedAccount::Mint(mint) => write!(f, "{}", mint),
+            CliParsedAccount::TokenAccount(account) => write!(f, "{}", account),
+            CliParsedAccount::Nonce(nonce) => write!(f, "{}", nonce),
+            CliParsedAccount::Stake(stake) => write!(f, "{}", stake),
+            CliParsedAccount::Vote(vote) => write!(f, "{}", vote),
+            CliParsedAccount::Program(program) => write!(f, "{}", program),
+            CliParsedAccount::ProgramData(program_data) => write!(f, "{}", program_data),
+            CliParsedAccount::Buffer(buffer) => write!(f, "{}", buffer),
+            CliParsedAccount::LookupTable(table) => write!(f, "{}", table),
+            CliParsedAccount::Uninitialized => write!(f, "Uninitialized"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedNonce {
+    pub authority: String,
+    /// 存储的 durable nonce，可作为 --blockhash 使用
+    pub nonce: String,
+    pub lamports_per_signature: u64,
+}
+
+impl fmt::Display for CliParsedNonce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Nonce Account:")?;
+        writeln!(f, "  Nonce: {}", self.nonce)?;
+        writeln!(
+            f,
+            "  Fee: {} lamports per signature",
+            self.lamports_per_signature
+        )?;
+        write!(f, "  Authority: {}", self.authority)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStakeLockup {
+    pub unix_timestamp: i64,
+    pub epoch: u64,
+    pub custodian: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStakeDelegation {
+    pub voter: String,
+    pub stake: u64,
+    pub activation_epoch: u64,
+    /// 尚未取消委托时为 None
+    pub deactivation_epoch: Option<u64>,
+    pub credits_observed: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedStake {
+    /// initialized、delegated 或 rewardsPool
+    pub state: String,
+    pub rent_exempt_reserve: Option<u64>,
+    pub staker: Option<String>,
+    pub withdrawer: Option<String>,
+    pub lockup: Option<CliStakeLockup>,
+    pub delegation: Option<CliStakeDelegation>,
+}
+
+impl fmt::Display for CliParsedStake {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Stake Account ({}):", self.state)?;
+        if let Some(reserve) = self.rent_exempt_reserve {
+            write!(f, "\n  Rent Exempt Reserve: {}", Sol(reserve))?;
+        }
+        if let (Some(staker), Some(withdrawer)) = (&self.staker, &self.withdrawer) {
+            write!(f, "\n  Stake Authority: {}", staker)?;
+            write!(f, "\n  Withdraw Authority: {}", withdrawer)?;
+        }
+        if let Some(lockup) = &self.lockup
+            && (lockup.unix_timestamp != 0 || lockup.epoch != 0)
+        {
+            write!(
+                f,
+                "\n  Lockup: until epoch {} / unix timestamp {} (custodian {})",
+                lockup.epoch, lockup.unix_timestamp, lockup.custodian
+            )?;
+        }
+        if let Some(delegation) = &self.delegation {
+            write!(f, "\n  Delegated Vote Account: {}", delegation.voter)?;
+            write!(f, "\n  Delegated Stake: {}", Sol(delegation.stake))?;
+            write!(f, "\n  Activation Epoch: {}", delegation.activation_epoch)?;
+            if let Some(epoch) = delegation.deactivation_epoch {
+                write!(f, "\n  Deactivation Epoch: {}", epoch)?;
+            }
+            write!(f, "\n  Credits Observed: {}", delegation.credits_observed)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliEpochCredits {
+    pub epoch: u64,
+    pub credits: u64,
+    pub previous_credits: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedVote {
+    pub node_pubkey: String,
+    pub authorized_withdrawer: String,
+    /// 最新的投票授权者
+    pub authorized_voter: Option<String>,
+    pub commission: u8,
+    pub root_slot: Option<u64>,
+    pub last_vote_slot: Option<u64>,
+    pub last_timestamp_slot: u64,
+    pub last_timestamp: i64,
+    pub epoch_credits: Vec<CliEpochCredits>,
+}
+
+impl fmt::Display for CliParsedVote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Vote Account:")?;
+        writeln!(f, "  Validator Identity: {}", self.node_pubkey)?;
+        writeln!(
+            f,
+            "  Vote Authority: {}",
+            self.authorized_voter.as_deref().unwrap_or("(none)")
+        )?;
+        writeln!(f, "  Withdraw Authority: {}", self.authorized_withdrawer)?;
+        writeln!(f, "  Commission: {}%", self.commission)?;
+        let slot = |slot: Option<u64>| slot.map_or("(none)".to_string(), |slot| slot.to_string());
+        writeln!(f, "  Root Slot: {}", slot(self.root_slot))?;
+        write!(f, "  Last Vote Slot: {}", slot(self.last_vote_slot))?;
+        if !self.epoch_credits.is_empty() {
+            write!(
+                f,
+                "\n  Epoch Credits (latest {}):",
+                self.epoch_credits.len().min(5)
+            )?;
+            for credits in self.epoch_credits.iter().rev().take(5) {
+                write!(
+                    f,
+                    "\n    epoch {}: {} credits (+{})",
+                    credits.epoch,
+                    credits.credits,
+                    credits.credits.saturating_sub(credits.previous_credits)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedProgram {
+    pub program_data: String,
+}
+
+impl fmt::Display for CliParsedProgram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Upgradeable Program:")?;
+        write!(f, "  ProgramData Address: {}", self.program_data)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedProgramData {
+    /// 最近一次部署 / 升级所在的 slot
+    pub slot: u64,
+    /// None 表示程序已不可升级
+    pub authority: Option<String>,
+    pub data_len: usize,
+}
+
+impl fmt::Display for CliParsedProgramData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Program Data:")?;
+        writeln!(f, "  Last Deployed Slot: {}", self.slot)?;
+        writeln!(
+            f,
+            "  Upgrade Authority: {}",
+            self.authority.as_deref().unwrap_or("(none, immutable)")
+        )?;
+        write!(f, "  Program Length: {} bytes", self.data_len)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedBuffer {
+    pub authority: Option<String>,
+    pub data_len: usize,
+}
+
+impl fmt::Display for CliParsedBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Program Buffer:")?;
+        writeln!(
+            f,
+            "  Authority: {}",
+            self.authority.as_deref().unwrap_or("(none)")
+        )?;
+        write!(f, "  Data Length: {} bytes", self.data_len)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedLookupTable {
+    /// 尚未停用时为 None
+    pub deactivation_slot: Option<u64>,
+    pub last_extended_slot: u64,
+    pub last_extended_slot_start_index: u8,
+    /// None 表示该表已冻结
+    pub authority: Option<String>,
+    pub addresses: Vec<String>,
+}
+
+impl fmt::Display for CliParsedLookupTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Address Lookup Table:")?;
+        writeln!(
+            f,
+            "  Authority: {}",
+            self.authority.as_deref().unwrap_or("(none, frozen)")
+        )?;
+        if let Some(slot) = self.deactivation_slot {
+            writeln!(f, "  Deactivation Slot: {}", slot)?;
+        }
+        writeln!(f, "  Last Extended Slot: {}", self.last_extended_slot)?;
+        write!(f, "  Addresses ({}):", self.addresses.len())?;
+        for (index, address) in self.addresses.iter().enumerate() {
+            write!(f, "\n    {:>3}: {}", index, address)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedMint {
+    /// spl-token 或 spl-token-2022
+    pub program: String,
+    pub mint_authority: Option<String>,
+    /// 最小单位的总供应量
+    pub supply: String,
+    pub ui_supply: String,
+    pub decimals: u8,
+    pub is_initialized: bool,
+    pub freeze_authority: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<CliTokenExtension>,
+}
+
+impl fmt::Display for CliParsedMint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Token Mint ({}):", self.program)?;
+        writeln!(f, "  Supply: {} ({})", self.ui_supply, self.supply)?;
+        writeln!(f, "  Decimals: {}", self.decimals)?;
+        writeln!(
+            f,
+            "  Mint Authority: {}",
+            self.mint_authority.as_deref().unwrap_or("(none)")
+        )?;
+        write!(
+            f,
+            "  Freeze Authority: {}",
+            self.freeze_authority.as_deref().unwrap_or("(none)")
+        )?;
+        write_token_extensions(f, &self.extensions)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParsedTokenAccount {
+    /// spl-token 或 spl-token-2022
+    pub program: String,
+    pub mint: String,
+    pub owner: String,
+    /// 最小单位的余额
+    pub amount: String,
+    /// initialized 或 frozen
+    pub state: String,
+    /// wrapped SOL 账户预留的租金 (lamports)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rent_exempt_reserve: Option<u64>,
+    pub delegate: Option<String>,
+    pub delegated_amount: String,
+    pub close_authority: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<CliTokenExtension>,
+}
+
+impl fmt::Display for CliParsedTokenAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Token Account ({}):", self.program)?;
+        writeln!(f, "  Mint: {}", self.mint)?;
+        writeln!(f, "  Owner: {}", self.owner)?;
+        writeln!(f, "  Amount: {}", self.amount)?;
+        write!(f, "  State: {}", self.state)?;
+        if let Some(reserve) = self.rent_exempt_reserve {
+            write!(
+                f,
+                "\n  Native: true (rent-exempt reserve {} lamports)",
+                reserve
+            )?;
+        }
+        if let Some(delegate) = &self.delegate {
+            write!(
+                f,
+                "\n  Delegate: {} (delegated amount {})",
+                delegate, self.delegated_amount
+            )?;
+        }
+        if let Some(close_authority) = &self.close_authority {
+            write!(f, "\n  Close Authority: {}", close_authority)?;
+        }
+        write_token_extensions(f, &self.extensions)
+    }
+}
+
+fn write_token_extensions(f: &mut fmt::Formatter, extensions: &[CliTokenExtension]) -> fmt::Result {
+    if extensions.is_empty() {
+        return Ok(());
+    }
+    write!(f, "\n  Extensions:")?;
+    for extension in extensions {
+        write!(f, "\n    {}", extension)?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTransferFee {
+    pub epoch: u64,
+    /// 单笔转账手续费上限 (最小单位)
+    pub maximum_fee: u64,
+    pub transfer_fee_basis_points: u16,
+}
+
+impl fmt::Display for CliTransferFee {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}% ({} bps), maximum fee {}, from epoch {}",
+            self.transfer_fee_basis_points as f64 / 100.0,
+            self.transfer_fee_basis_points,
+            self.maximum_fee,
+            self.epoch
+        )
+    }
+}
+
+/// Token-2022 扩展的状态，未单独解码的扩展仅输出名称
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", tag = "extension", content = "state")]
+pub enum CliTokenExtension {
+    #[serde(rename_all = "camelCase")]
+    TransferFeeConfig {
+        transfer_fee_config_authority: Option<String>,
+        withdraw_withheld_authority: Option<String>,
+        withheld_amount: u64,
+        older_transfer_fee: CliTransferFee,
+        newer_transfer_fee: CliTransferFee,
+    },
+    #[serde(rename_all = "camelCase")]
+    TransferFeeAmount {
+        withheld_amount: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    InterestBearingConfig {
+        rate_authority: Option<String>,
+        initialization_timestamp: i64,
+        pre_update_average_rate: i16,
+        last_update_timestamp: i64,
+        current_rate: i16,
+    },
+    #[serde(rename_all = "camelCase")]
+    MetadataPointer {
+        authority: Option<String>,
+        metadata_address: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    TokenMetadata {
+        update_authority: Option<String>,
+        mint: String,
+        name: String,
+        symbol: String,
+        uri: String,
+        additional_metadata: Vec<(String, String)>,
+    },
+    NonTransferable,
+    NonTransferableAccount,
+    PermanentDelegate {
+        delegate: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    MintCloseAuthority {
+        close_authority: Option<String>,
+    },
+    ImmutableOwner,
+    Unparsed(String),
+}
+
+impl fmt::Display for CliTokenExtension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let none = || "(none)".to_string();
+        match self {
+            CliTokenExtension::TransferFeeConfig {
+                transfer_fee_config_authority,
+                withdraw_withheld_authority,
+                withheld_amount,
+                older_transfer_fee,
+                newer_transfer_fee,
+            } => {
+                write!(f, "Transfer Fee: {}", newer_transfer_fee)?;
+                if older_transfer_fee.epoch != newer_transfer_fee.epoch {
+                    write!(f, "\n      Older Fee: {}", older_transfer_fee)?;
+                }
+                write!(
+                    f,
+                    "\n      Config Authority: {}\n      Withdraw Withheld Authority: {}\n      Withheld Amount: {}",
+                    transfer_fee_config_authority.clone().unwrap_or_else(none),
+                    withdraw_withheld_authority.clone().unwrap_or_else(none),
+                    withheld_amount
+                )
+            }
+            CliTokenExtension::TransferFeeAmount { withheld_amount } => {
+                write!(f, "Transfer Fee Withheld: {}", withheld_amount)
+            }
+            CliTokenExtension::InterestBearingConfig {
+                rate_authority,
+                current_rate,
+                pre_update_average_rate,
+                ..
+            } => write!(
+                f,
+                "Interest Rate: {}% (current {} bps, average before last update {} bps)\n      Rate Authority: {}",
+                *current_rate as f64 / 100.0,
+                current_rate,
+                pre_update_average_rate,
+                rate_authority.clone().unwrap_or_else(none)
+            ),
+            CliTokenExtension::MetadataPointer {
+                authority,
+                metadata_address,
+            } => write!(
+                f,
+                "Metadata Pointer: {}\n      Authority: {}",
+                metadata_address.clone().unwrap_or_else(none),
+                authority.clone().unwrap_or_else(none)
+            ),
+            CliTokenExtension::TokenMetadata {
+                update_authority,
+                name,
+                symbol,
+                uri,
+                additional_metadata,
+                ..
+            } => {
+                write!(
+                    f,
+                    "Metadata: {} ({})\n      URI: {}\n      Update Authority: {}",
+                    name,
+                    symbol,
+                    uri,
+                    update_authority.clone().unwrap_or_else(none)
+                )?;
+                for (key, value) in additional_metadata {
+                    write!(f, "\n      {}: {}", key, value)?;
+                }
+                Ok(())
+            }
+            CliTokenExtension::NonTransferable => write!(f, "Non-Transferable"),
+            CliTokenExtension::NonTransferableAccount => write!(f, "Non-Transferable Account"),
+            CliTokenExtension::PermanentDelegate { delegate } => write!(
+                f,
+                "Permanent Delegate: {}",
+                delegate.clone().unwrap_or_else(none)
+            ),
+            CliTokenExtension::MintCloseAuthority { close_authority } => write!(
+                f,
+                "Mint Close Authority: {}",
+                close_authority.clone().unwrap_or_else(none)
+            ),
+            CliTokenExtension::ImmutableOwner => write!(f, "Immutable Owner"),
+            CliTokenExtension::Unparsed(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliNonceAccount {
+    pub address: String,
+    pub lamports: u64,
+    pub sol: String,
+    pub authority: String,
+    /// 存储的 durable nonce，可作为 --blockhash 使用
+    pub nonce: String,
+    pub lamports_per_signature: u64,
+}
+
+impl CliNonceAccount {
+    pub fn new(address: &Pubkey, lamports: u64, data: &NonceData) -> Self {
+        Self {
+            address: address.to_string(),
+            lamports,
+            sol: lamports_to_sol_string(lamports),
+            authority: data.authority.to_string(),
+            nonce: data.blockhash().to_string(),
+            lamports_per_signature: data.get_lamports_per_signature(),
+        }
+    }
+}
+
+impl fmt::Display for CliNonceAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Nonce Account: {}", self.address)?;
+        writeln!(
+            f,
+            "Balance: {} ({} lamports)",
+            Sol(self.lamports),
+            self.lamports
+        )?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        writeln!(
+            f,
+            "Fee: {} lamports per signature",
+            self.lamports_per_signature
+        )?;
+        write!(f, "Authority: {}", self.authority)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTokenHolder {
+    pub rank: usize,
+    pub owner: String,
+    /// 原始数量 (最小单位)，使用字符串避免 u128 精度丢失
+    pub total: String,
+    pub total_ui: f64,
+    pub accounts: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliOtherToken {
+    pub mint: String,
+    pub label: String,
+    pub amount_ui: f64,
+    pub decimals: u8,
+    pub price_usd: Option<f64>,
+    pub value_usd: Option<f64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliHolderOtherTokens {
+    pub owner: String,
+    pub tokens: Vec<CliOtherToken>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTokenAnalysis {
+    pub mint: String,
+    pub label: String,
+    /// spl-token 或 spl-token-2022
+    pub program: String,
+    pub decimals: u8,
+    pub supply_ui: Option<f64>,
+    pub holder_count: usize,
+    pub holders: Vec<CliTokenHolder>,
+    pub displayed_total_ui: f64,
+    /// Token-2022 mint 的扩展 (手续费、利率、元数据等)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<CliTokenExtension>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other_holdings: Option<Vec<CliHolderOtherTokens>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signatures: Option<Vec<String>>,
+    /// 无法生成结构化数据时的提示信息
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+impl fmt::Display for CliTokenAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.holders.is_empty() {
+            return write!(f, "{}", self.notes.join("\n"));
+        }
+
+        writeln!(
+            f,
+            "=== {} ({}) 的持有人清单 (前 {} 名 / 共 {} 名) ===",
+            self.label,
+            self.mint,
+            self.holders.len(),
+            self.holder_count
+        )?;
+        writeln!(f, "代币程序: {}", self.program)?;
+        writeln!(f, "代币精度: {} 位小数", self.decimals)?;
+        for extension in &self.extensions {
+            writeln!(f, "扩展: {}", extension)?;
+        }
+        if let Some(ui_amount) = self.supply_ui {
+            writeln!(f, "链上报告的总供应量: {:.6}", ui_amount)?;
+        }
+        for holder in &self.holders {
+            writeln!(
+                f,
+                "{:>3}. {} 持有 {:.6} 枚 (共 {} 个代币账户，示例: {})",
+                holder.rank,
+                holder.owner,
+                holder.total_ui,
+                holder.accounts.len(),
+                holder
+                    .accounts
+                    .first()
+                    .map(String::as_str)
+                    .unwrap_or("<unknown-token-account>")
+            )?;
+        }
+        write!(
+            f,
+            "小计 (前 {} 名): {:.6} 枚",
+            self.holders.len(),
+            self.displayed_total_ui
+        )?;
+
+        if let Some(other_holdings) = &self.other_holdings {
+            write!(f, "\n\n=== 持有人常见其它 SPL 代币持仓 (按余额排序) ===")?;
+            for holding in other_holdings {
+                if let Some(err) = &holding.error {
+                    write!(
+                        f,
+                        "\n- {} 的其它 SPL 代币未能成功获取 ({})",
+                        holding.owner, err
+                    )?;
+                    continue;
+                }
+                if holding.tokens.is_empty() {
+                    write!(
+                        f,
+                        "\n- {} 暂无其它 SPL 代币余额 (或不足筛选条件)",
+                        holding.owner
+                    )?;
+                    continue;
+                }
+                write!(f, "\n- {} 还持有:", holding.owner)?;
+                for token in &holding.tokens {
+                    let price_info = token
+                        .price_usd
+                        .map(|p| format!("价格: ${:.6}", p))
+                        .unwrap_or_else(|| "价格: 未知".to_string());
+                    let value_info = token
+                        .value_usd
+                        .map(|v| format!("≈ ${:.2}", v))
+                        .unwrap_or_else(|| "≈ $-".to_string());
+                    write!(
+                        f,
+                        "\n    - {} ({}) : {:.6} 枚 (精度: {}) [{} | {}]",
+                        token.label,
+                        token.mint,
+                        token.amount_ui,
+                        token.decimals,
+                        price_info,
+                        value_info
+                    )?;
+                }
+            }
+        }
+
+        if let Some(signatures) = &self.signatures {
+            write!(
+                f,
+                "\n\n=== 最近的 {} 条代币相关交易签名 (基于 DAS getSignaturesForAsset) ===",
+                signatures.len()
+            )?;
+            for signature in signatures {
+                write!(f, "\n- {}", signature)?;
+            }
+        }
+
+        for note in &self.notes {
+            write!(f, "\n\n{}", note)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_system_interface::instruction::SystemInstruction;
+use spl_token::solana_program::program_option::COption;
+use spl_token_2022::instruction::TokenInstruction;
+
+use crate::output::CliParsedInstruction;
+
+/// 指令解码函数：参数为指令引用的账户 (按顺序) 与指令数据
+type InstructionParser = fn(&[Pubkey], &[u8]) -> Option<CliParsedInstruction>;
+
+/// 以程序 ID 为键的指令解码器注册表，值中的名称用于展示
+static PARSERS: LazyLock<HashMap<Pubkey, (&'static str, InstructionParser)>> =
+    LazyLock::new(|| {
+        let parsers: [(Pubkey, (&'static str, InstructionParser)); 5] = [
+            (
+                solana_sdk_ids::system_program::ID,
+                ("System Program", parse_system_instruction),
+            ),
+            (
+                solana_sdk_ids::compute_budget::ID,
+                ("Compute Budget Program", parse_compute_budget_instruction),
+            ),
+            (
+                Pubkey::new_from_array(spl_token::ID.to_bytes()),
+                ("Token Program", parse_token_instruction),
+            ),
+            (
+                Pubkey::new_from_array(spl_token_2022::ID.to_bytes()),
+                ("Token-2022 Program", parse_token_instruction),
+            ),
+            (
+                Pubkey::new_from_array(spl_associated_token_account_client::program::ID.to_bytes()),
+                (
+                    "Associated Token Account Program",
+                    parse_associated_token_instruction,
+                ),
+            ),
+        ];
+        parsers.into_iter().collect()
+    });
+
+/// 程序的可读名称，未注册的程序返回 None
+pub fn program_name(program_id: &Pubkey) -> Option<&'static str> {
+    PARSERS.get(program_id).map(|(name, _)| *name)
+}
+
+/// 按程序 ID 解码指令，无法识别时返回 None
+pub fn parse_instruction(
+    program_id: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Option<CliParsedInstruction> {
+    PARSERS
+        .get(program_id)
+        .and_then(|(_, parser)| parser(accounts, data))
+}
+
+/// 依次为指令的各个账户命名，账户数量不足时跳过缺失的部分
+fn named_accounts(names: &[&str], accounts: &[Pubkey]) -> Vec<(String, String)> {
+    names
+        .iter()
+        .zip(accounts)
+        .map(|(name, account)| (name.to_string(), account.to_string()))
+        .collect()
+}
+
+fn instruction(
+    name: &str,
+    account_names: &[&str],
+    accounts: &[Pubkey],
+    fields: Vec<(&str, String)>,
+) -> CliParsedInstruction {
+    let mut info = named_accounts(account_names, accounts);
+    info.extend(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value)),
+    );
+    CliParsedInstruction {
+        name: name.to_string(),
+        info,
+    }
+}
+
+fn parse_system_instruction(accounts: &[Pubkey], data: &[u8]) -> Option<CliParsedInstruction> {
+    let system_instruction: SystemInstruction = bincode::deserialize(data).ok()?;
+    Some(match system_instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => instruction(
+            "createAccount",
+            &["source", "newAccount"],
+            accounts,
+            vec![
+                ("lamports", lamports.to_string()),
+                ("space", space.to_string()),
+                ("owner", owner.to_string()),
+            ],
+        ),
+        SystemInstruction::Assign { owner } => instruction(
+            "assign",
+            &["account"],
+            accounts,
+            vec![("owner", owner.to_string())],
+        ),
+        SystemInstruction::Transfer { lamports } => instruction(
+            "transfer",
+            &["source", "destination"],
+            accounts,
+            vec![("lamports", lamports.to_string())],
+        ),
+        SystemInstruction::CreateAccountWithSeed {
+            base,
+            seed,
+            lamports,
+            space,
+            owner,
+        } => instruction(
+            "createAccountWithSeed",
+            &["source", "newAccount"],
+            accounts,
+            vec![
+                ("base", base.to_string()),
+                ("seed", seed),
+                ("lamports", lamports.to_string()),
+                ("space", space.to_string()),
+                ("owner", owner.to_string()),
+            ],
+        ),
+        SystemInstruction::AdvanceNonceAccount => instruction(
+            "advanceNonce",
+            &["nonceAccount", "recentBlockhashesSysvar", "nonceAuthority"],
+            accounts,
+            vec![],
+        ),
+        SystemInstruction::WithdrawNonceAccount(lamports) => instruction(
+            "withdrawFromNonce",
+            &[
+                "nonceAccount",
+                "destination",
+                "recentBlockhashesSysvar",
+                "rentSysvar",
+                "nonceAuthority",
+            ],
+            accounts,
+            vec![("lamports", lamports.to_string())],
+        ),
+        SystemInstruction::InitializeNonceAccount(authority) => instruction(
+            "initializeNonce",
+            &["nonceAccount", "recentBlockhashesSysvar", "rentSysvar"],
+            accounts,
+            vec![("nonceAuthority", authority.to_string())],
+        ),
+        SystemInstruction::AuthorizeNonceAccount(new_authority) => instruction(
+            "authorizeNonce",
+            &["nonceAccount", "nonceAuthority"],
+            accounts,
+            vec![("newAuthorized", new_authority.to_string())],
+        ),
+        SystemInstruction::Allocate { space } => instruction(
+            "allocate",
+            &["account"],
+            accounts,
+            vec![("space", space.to_string())],
+        ),
+        SystemInstruction::AllocateWithSeed {
+            base,
+            seed,
+            space,
+            owner,
+        } => instruction(
+            "allocateWithSeed",
+            &["account"],
+            accounts,
+            vec![
+                ("base", base.to_string()),
+                ("seed", seed),
+                ("space", space.to_string()),
+                ("owner", owner.to_string()),
+            ],
+        ),
+        SystemInstruction::AssignWithSeed { base, seed, owner } => instruction(
+            "assignWithSeed",
+            &["account"],
+            accounts,
+            vec![
+                ("base", base.to_string()),
+                ("seed", seed),
+                ("owner", owner.to_string()),
+            ],
+        ),
+        SystemInstruction::TransferWithSeed {
+            lamports,
+            from_seed,
+            from_owner,
+        } => instruction(
+            "transferWithSeed",
+            &["source", "sourceBase", "destination"],
+            accounts,
+            vec![
+                ("lamports", lamports.to_string()),
+                ("sourceSeed", from_seed),
+                ("sourceOwner", from_owner.to_string()),
+            ],
+        ),
+        SystemInstruction::UpgradeNonceAccount => {
+            instruction("upgradeNonce", &["nonceAccount"], accounts, vec![])
+        }
+    })
+}
+
+/// 计算预算指令：第一个字节为类型，其后为小端序的参数
+fn parse_compute_budget_instruction(
+    _accounts: &[Pubkey],
+    data: &[u8],
+) -> Option<CliParsedInstruction> {
+    let (&tag, rest) = data.split_first()?;
+    let u32_arg = || {
+        rest.get(..4)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_le_bytes)
+    };
+    let (name, field, value) = match tag {
+        1 => ("requestHeapFrame", "bytes", u32_arg()?.to_string()),
+        2 => ("setComputeUnitLimit", "units", u32_arg()?.to_string()),
+        3 => (
+            "setComputeUnitPrice",
+            "microLamports",
+            u64::from_le_bytes(rest.get(..8)?.try_into().ok()?).to_string(),
+        ),
+        4 => (
+            "setLoadedAccountsDataSizeLimit",
+            "bytes",
+            u32_arg()?.to_string(),
+        ),
+        _ => return None,
+    };
+    Some(instruction(name, &[], &[], vec![(field, value)]))
+}
+
+fn parse_associated_token_instruction(
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Option<CliParsedInstruction> {
+    let account_names = [
+        "source",
+        "account",
+        "wallet",
+        "mint",
+        "systemProgram",
+        "tokenProgram",
+    ];
+    let name = match data.first() {
+        // 旧版本的指令没有数据，等同于 create
+        None | Some(0) => "create",
+        Some(1) => "createIdempotent",
+        Some(2) => {
+            return Some(instruction(
+                "recoverNested",
+                &[
+                    "nestedSource",
+                    "nestedMint",
+                    "destination",
+                    "nestedOwner",
+                    "ownerMint",
+                    "wallet",
+                    "tokenProgram",
+                ],
+                accounts,
+                vec![],
+            ));
+        }
+        Some(_) => return None,
+    };
+    Some(instruction(name, &account_names, accounts, vec![]))
+}
+
+/// 可选的权限地址，未设置时显示为 none
+fn optional<T: ToString>(pubkey: COption<T>) -> String {
+    match pubkey {
+        COption::Some(pubkey) => pubkey.to_string(),
+        COption::None => "none".to_string(),
+    }
+}
+
+/// spl-token 与 Token-2022 共用同一套基础指令编码，统一按 Token-2022 解码
+#[allow(deprecated)]
+fn parse_token_instruction(accounts: &[Pubkey], data: &[u8]) -> Option<CliParsedInstruction> {
+    let token_instruction = TokenInstruction::unpack(data).ok()?;
+    let amount = |amount: u64| ("amount", amount.to_string());
+    let decimals = |decimals: u8| ("decimals", decimals.to_string());
+    Some(match token_instruction {
+        TokenInstruction::InitializeMint {
+            decimals: mint_decimals,
+            mint_authority,
+            freeze_authority,
+        } => instruction(
+            "initializeMint",
+            &["mint", "rentSysvar"],
+            accounts,
+            vec![
+                decimals(mint_decimals),
+                ("mintAuthority", mint_authority.to_string()),
+                ("freezeAuthority", optional(freeze_authority)),
+            ],
+        ),
+        TokenInstruction::InitializeMint2 {
+            decimals: mint_decimals,
+            mint_authority,
+            freeze_authority,
+        } => instruction(
+            "initializeMint2",
+            &["mint"],
+            accounts,
+            vec![
+                decimals(mint_decimals),
+                ("mintAuthority", mint_authority.to_string()),
+                ("freezeAuthority", optional(freeze_authority)),
+            ],
+        ),
+        TokenInstruction::InitializeAccount => instruction(
+            "initializeAccount",
+            &["account", "mint", "owner", "rentSysvar"],
+            accounts,
+            vec![],
+        ),
+        TokenInstruction::InitializeAccount2 { owner } => instruction(
+            "initializeAccount2",
+            &["account", "mint", "rentSysvar"],
+            accounts,
+            vec![("owner", owner.to_string())],
+        ),
+        TokenInstruction::InitializeAccount3 { owner } => instruction(
+            "initializeAccount3",
+            &["account", "mint"],
+            accounts,
+            vec![("owner", owner.to_string())],
+        ),
+        TokenInstruction::Transfer { amount: value } => instruction(
+            "transfer",
+            &["source", "destination", "authority"],
+            accounts,
+            vec![amount(value)],
+        ),
+        TokenInstruction::TransferChecked {
+            amount: value,
+            decimals: mint_decimals,
+        } => instruction(
+            "transferChecked",
+            &["source", "mint", "destination", "authority"],
+            accounts,
+            vec![amount(value), decimals(mint_decimals)],
+        ),
+        TokenInstruction::Approve { amount: value } => instruction(
+            "approve",
+            &["source", "delegate", "owner"],
+            accounts,
+            vec![amount(value)],
+        ),
+        TokenInstruction::ApproveChecked {
+            amount: value,
+            decimals: mint_decimals,
+        } => instruction(
+            "approveChecked",
+            &["source", "mint", "delegate", "owner"],
+            accounts,
+            vec![amount(value), decimals(mint_decimals)],
+        ),
+        TokenInstruction::Revoke => instruction("revoke", &["source", "owner"], accounts, vec![]),
+        TokenInstruction::SetAuthority {
+            authority_type,
+            new_authority,
+        } => instruction(
+            "setAuthority",
+            &["account", "authority"],
+            accounts,
+            vec![
+                ("authorityType", format!("{:?}", authority_type)),
+                ("newAuthority", optional(new_authority)),
+            ],
+        ),
+        TokenInstruction::MintTo { amount: value } => instruction(
+            "mintTo",
+            &["mint", "account", "mintAuthority"],
+            accounts,
+            vec![amount(value)],
+        ),
+        TokenInstruction::MintToChecked {
+            amount: value,
+            decimals: mint_decimals,
+        } => instruction(
+            "mintToChecked",
+            &["mint", "account", "mintAuthority"],
+            accounts,
+            vec![amount(value), decimals(mint_decimals)],
+        ),
+        TokenInstruction::Burn { amount: value } => instruction(
+            "burn",
+            &["account", "mint", "authority"],
+            accounts,
+            vec![amount(value)],
+        ),
+        TokenInstruction::BurnChecked {
+            amount: value,
+            decimals: mint_decimals,
+        } => instruction(
+            "burnChecked",
+            &["account", "mint", "authority"],
+            accounts,
+            vec![amount(value), decimals(mint_decimals)],
+        ),
+        TokenInstruction::CloseAccount => instruction(
+            "closeAccount",
+            &["account", "destination", "owner"],
+            accounts,
+            vec![],
+        ),
+        TokenInstruction::FreezeAccount => instruction(
+            "freezeAccount",
+            &["account", "mint", "freezeAuthority"],
+            accounts,
+            vec![],
+        ),
+        TokenInstruction::ThawAccount => instruction(
+            "thawAccount",
+            &["account", "mint", "freezeAuthority"],
+            accounts,
+            vec![],
+        ),
+        TokenInstruction::SyncNative => instruction("syncNative", &["account"], accounts, vec![]),
+        TokenInstruction::InitializeImmutableOwner => {
+            instruction("initializeImmutableOwner", &["account"], accounts, vec![])
+        }
+        TokenInstruction::InitializeMintCloseAuthority { close_authority } => instruction(
+            "initializeMintCloseAuthority",
+            &["mint"],
+            accounts,
+            vec![("closeAuthority", optional(close_authority))],
+        ),
+        TokenInstruction::InitializePermanentDelegate { delegate } => instruction(
+            "initializePermanentDelegate",
+            &["mint"],
+            accounts,
+            vec![("delegate", delegate.to_string())],
+        ),
+        TokenInstruction::InitializeNonTransferableMint => {
+            instruction("initializeNonTransferableMint", &["mint"], accounts, vec![])
+        }
+        // 其余指令 (多签与各扩展的子指令) 只展示名称
+        other => {
+            let name = format!("{:?}", other);
+            let name = name
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .next()
+                .unwrap_or_default();
+            let mut chars = name.chars();
+            let name = match chars.next() {
+                Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                None => String::new(),
+            };
+            instruction(&name, &[], accounts, vec![])
+        }
+    })
+}
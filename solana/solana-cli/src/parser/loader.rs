@@ -0,0 +1,35 @@
+use solana_loader_v3_interface::state::UpgradeableLoaderState;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::output::{CliParsedAccount, CliParsedBuffer, CliParsedProgram, CliParsedProgramData};
+
+/// 解码 BPF upgradeable loader 拥有的 program / programdata / buffer 账户
+pub fn parse_upgradeable_loader(_owner: &Pubkey, data: &[u8]) -> Option<CliParsedAccount> {
+    let state: UpgradeableLoaderState = bincode::deserialize(data).ok()?;
+    Some(match state {
+        UpgradeableLoaderState::Uninitialized => CliParsedAccount::Uninitialized,
+        UpgradeableLoaderState::Buffer { authority_address } => {
+            CliParsedAccount::Buffer(CliParsedBuffer {
+                authority: authority_address.map(|authority| authority.to_string()),
+                data_len: data
+                    .len()
+                    .saturating_sub(UpgradeableLoaderState::size_of_buffer_metadata()),
+            })
+        }
+        UpgradeableLoaderState::Program {
+            programdata_address,
+        } => CliParsedAccount::Program(CliParsedProgram {
+            program_data: programdata_address.to_string(),
+        }),
+        UpgradeableLoaderState::ProgramData {
+            slot,
+            upgrade_authority_address,
+        } => CliParsedAccount::ProgramData(CliParsedProgramData {
+            slot,
+            authority: upgrade_authority_address.map(|authority| authority.to_string()),
+            data_len: data
+                .len()
+                .saturating_sub(UpgradeableLoaderState::size_of_programdata_metadata()),
+        }),
+    })
+}
@@ -0,0 +1,21 @@
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::output::{CliParsedAccount, CliParsedLookupTable};
+
+/// 解码地址查找表 (ALT) 账户
+pub fn parse_lookup_table(_owner: &Pubkey, data: &[u8]) -> Option<CliParsedAccount> {
+    let table = AddressLookupTable::deserialize(data).ok()?;
+    let meta = table.meta;
+    Some(CliParsedAccount::LookupTable(CliParsedLookupTable {
+        deactivation_slot: (meta.deactivation_slot != u64::MAX).then_some(meta.deactivation_slot),
+        last_extended_slot: meta.last_extended_slot,
+        last_extended_slot_start_index: meta.last_extended_slot_start_index,
+        authority: meta.authority.map(|authority| authority.to_string()),
+        addresses: table
+            .addresses
+            .iter()
+            .map(|address| address.to_string())
+            .collect(),
+    }))
+}
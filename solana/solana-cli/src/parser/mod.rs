@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::output::CliParsedAccount;
+
+pub mod instruction;
+pub mod loader;
+pub mod lookup_table;
+pub mod stake;
+pub mod system;
+pub mod token;
+pub mod vote;
+
+/// 账户解码函数：参数为账户的 owner 与原始数据
+type AccountParser = fn(&Pubkey, &[u8]) -> Option<CliParsedAccount>;
+
+/// 以 owner 程序为键的解码器注册表
+static PARSERS: LazyLock<HashMap<Pubkey, AccountParser>> = LazyLock::new(|| {
+    let parsers: [(Pubkey, AccountParser); 7] = [
+        (solana_sdk_ids::system_program::ID, system::parse_system),
+        (
+            Pubkey::new_from_array(spl_token::ID.to_bytes()),
+            token::parse_token,
+        ),
+        (
+            Pubkey::new_from_array(spl_token_2022::ID.to_bytes()),
+            token::parse_token,
+        ),
+        (solana_sdk_ids::stake::ID, stake::parse_stake),
+        (solana_sdk_ids::vote::ID, vote::parse_vote),
+        (
+            solana_sdk_ids::bpf_loader_upgradeable::ID,
+            loader::parse_upgradeable_loader,
+        ),
+        (
+            solana_sdk_ids::address_lookup_table::ID,
+            lookup_table::parse_lookup_table,
+        ),
+    ];
+    parsers.into_iter().collect()
+});
+
+/// 按账户的 owner 程序解码账户数据，无法识别时返回 None
+pub fn parse_account(owner: &Pubkey, data: &[u8]) -> Option<CliParsedAccount> {
+    if data.is_empty() {
+        return None;
+    }
+    PARSERS.get(owner).and_then(|parser| parser(owner, data))
+}
@@ -0,0 +1,52 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_stake_interface::state::{Meta, StakeStateV2};
+
+use crate::output::{CliParsedAccount, CliParsedStake, CliStakeDelegation, CliStakeLockup};
+
+/// 解码 stake 账户
+pub fn parse_stake(_owner: &Pubkey, data: &[u8]) -> Option<CliParsedAccount> {
+    let state: StakeStateV2 = bincode::deserialize(data).ok()?;
+    Some(match state {
+        StakeStateV2::Uninitialized => CliParsedAccount::Uninitialized,
+        StakeStateV2::Initialized(meta) => {
+            CliParsedAccount::Stake(stake_meta("initialized", &meta))
+        }
+        StakeStateV2::Stake(meta, stake, _) => {
+            let delegation = stake.delegation;
+            CliParsedAccount::Stake(CliParsedStake {
+                delegation: Some(CliStakeDelegation {
+                    voter: delegation.voter_pubkey.to_string(),
+                    stake: delegation.stake,
+                    activation_epoch: delegation.activation_epoch,
+                    deactivation_epoch: (delegation.deactivation_epoch != u64::MAX)
+                        .then_some(delegation.deactivation_epoch),
+                    credits_observed: stake.credits_observed,
+                }),
+                ..stake_meta("delegated", &meta)
+            })
+        }
+        StakeStateV2::RewardsPool => CliParsedAccount::Stake(CliParsedStake {
+            state: "rewardsPool".to_string(),
+            rent_exempt_reserve: None,
+            staker: None,
+            withdrawer: None,
+            lockup: None,
+            delegation: None,
+        }),
+    })
+}
+
+fn stake_meta(state: &str, meta: &Meta) -> CliParsedStake {
+    CliParsedStake {
+        state: state.to_string(),
+        rent_exempt_reserve: Some(meta.rent_exempt_reserve),
+        staker: Some(meta.authorized.staker.to_string()),
+        withdrawer: Some(meta.authorized.withdrawer.to_string()),
+        lockup: Some(CliStakeLockup {
+            unix_timestamp: meta.lockup.unix_timestamp,
+            epoch: meta.lockup.epoch,
+            custodian: meta.lockup.custodian.to_string(),
+        }),
+        delegation: None,
+    }
+}
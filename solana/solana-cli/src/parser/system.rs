@@ -0,0 +1,18 @@
+use solana_nonce::state::State as NonceState;
+use solana_nonce::versions::Versions;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::output::{CliParsedAccount, CliParsedNonce};
+
+/// 解码系统程序拥有的账户，目前只有 nonce 账户带有数据
+pub fn parse_system(_owner: &Pubkey, data: &[u8]) -> Option<CliParsedAccount> {
+    let versions: Versions = bincode::deserialize(data).ok()?;
+    Some(match versions.state() {
+        NonceState::Uninitialized => CliParsedAccount::Uninitialized,
+        NonceState::Initialized(data) => CliParsedAccount::Nonce(CliParsedNonce {
+            authority: data.authority.to_string(),
+            nonce: data.blockhash().to_string(),
+            lamports_per_signature: data.get_lamports_per_signature(),
+        }),
+    })
+}
@@ -0,0 +1,215 @@
+use solana_sdk::pubkey::Pubkey;
+use spl_token::solana_program::program_error::ProgramError;
+use spl_token::solana_program::program_pack::Pack;
+use spl_token::solana_program::pubkey::Pubkey as SplPubkey;
+use spl_token_2022::extension::interest_bearing_mint::InterestBearingConfig;
+use spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use spl_token_2022::extension::mint_close_authority::MintCloseAuthority;
+use spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use spl_token_2022::extension::transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig};
+use spl_token_2022::extension::{
+    BaseState, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use spl_token_2022::state::{Account, AccountState, Mint};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+use crate::output::{
+    CliParsedAccount, CliParsedMint, CliParsedTokenAccount, CliTokenExtension, CliTransferFee,
+};
+
+/// Metaplex Token Metadata 程序，legacy mint 的名称 / 符号存放在其 PDA 中
+pub const METAPLEX_METADATA_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// 代币程序的名称，owner 不是 spl-token / spl-token-2022 时返回 None
+pub fn token_program_name(owner: &Pubkey) -> Option<&'static str> {
+    let owner = owner.to_bytes();
+    if owner == spl_token::ID.to_bytes() {
+        Some("spl-token")
+    } else if owner == spl_token_2022::ID.to_bytes() {
+        Some("spl-token-2022")
+    } else {
+        None
+    }
+}
+
+/// 解码代币程序拥有的账户 (mint 或代币账户)
+pub fn parse_token(owner: &Pubkey, data: &[u8]) -> Option<CliParsedAccount> {
+    parse_token_account(owner, data)
+        .map(CliParsedAccount::TokenAccount)
+        .or_else(|| parse_token_mint(owner, data).map(CliParsedAccount::Mint))
+}
+
+/// 解码 mint 账户，Token-2022 的扩展一并解码
+pub fn parse_token_mint(owner: &Pubkey, data: &[u8]) -> Option<CliParsedMint> {
+    let program = token_program_name(owner)?;
+    let state = StateWithExtensions::<Mint>::unpack(data).ok()?;
+    let mint = state.base;
+    Some(CliParsedMint {
+        program: program.to_string(),
+        mint_authority: optional_pubkey(mint.mint_authority),
+        supply: mint.supply.to_string(),
+        ui_supply: spl_token_2022::amount_to_ui_amount_string_trimmed(mint.supply, mint.decimals),
+        decimals: mint.decimals,
+        is_initialized: mint.is_initialized,
+        freeze_authority: optional_pubkey(mint.freeze_authority),
+        extensions: parse_extensions(&state),
+    })
+}
+
+/// 解码代币账户，Token-2022 的扩展一并解码
+pub fn parse_token_account(owner: &Pubkey, data: &[u8]) -> Option<CliParsedTokenAccount> {
+    let program = token_program_name(owner)?;
+    let state = StateWithExtensions::<Account>::unpack(data).ok()?;
+    let account = state.base;
+    Some(CliParsedTokenAccount {
+        program: program.to_string(),
+        mint: account.mint.to_string(),
+        owner: account.owner.to_string(),
+        amount: account.amount.to_string(),
+        state: match account.state {
+            AccountState::Uninitialized => "uninitialized",
+            AccountState::Initialized => "initialized",
+            AccountState::Frozen => "frozen",
+        }
+        .to_string(),
+        rent_exempt_reserve: account.is_native.into(),
+        delegate: optional_pubkey(account.delegate),
+        delegated_amount: account.delegated_amount.to_string(),
+        close_authority: optional_pubkey(account.close_authority),
+        extensions: parse_extensions(&state),
+    })
+}
+
+/// Token-2022 mint 的 TokenMetadata 扩展中记录的代币符号
+pub fn mint_symbol(mint: &CliParsedMint) -> Option<String> {
+    mint.extensions
+        .iter()
+        .find_map(|extension| match extension {
+            CliTokenExtension::TokenMetadata { symbol, .. } if !symbol.is_empty() => {
+                Some(symbol.clone())
+            }
+            _ => None,
+        })
+}
+
+/// mint 对应的 Metaplex metadata 账户地址
+pub fn metaplex_metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            METAPLEX_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &METAPLEX_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// 从 Metaplex metadata 账户中读取代币符号
+///
+/// 布局: key (1) | update_authority (32) | mint (32) | name (borsh string) | symbol (borsh string) | ...
+pub fn parse_metaplex_symbol(data: &[u8]) -> Option<String> {
+    let read_string = |offset: usize| -> Option<(String, usize)> {
+        let len = u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize;
+        let bytes = data.get(offset + 4..offset + 4 + len)?;
+        let value = String::from_utf8_lossy(bytes)
+            .trim_end_matches('\0')
+            .trim()
+            .to_string();
+        Some((value, offset + 4 + len))
+    };
+    let (_name, offset) = read_string(1 + 32 + 32)?;
+    let (symbol, _) = read_string(offset)?;
+    (!symbol.is_empty()).then_some(symbol)
+}
+
+fn optional_pubkey(pubkey: impl Into<Option<SplPubkey>>) -> Option<String> {
+    pubkey.into().map(|pubkey| pubkey.to_string())
+}
+
+fn parse_extensions<S: BaseState + Pack>(state: &StateWithExtensions<S>) -> Vec<CliTokenExtension> {
+    state
+        .get_extension_types()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|extension_type| {
+            parse_extension(state, extension_type)
+                .unwrap_or_else(|_| CliTokenExtension::Unparsed(format!("{:?}", extension_type)))
+        })
+        .collect()
+}
+
+fn parse_extension<S: BaseState + Pack>(
+    state: &StateWithExtensions<S>,
+    extension_type: ExtensionType,
+) -> Result<CliTokenExtension, ProgramError> {
+    Ok(match extension_type {
+        ExtensionType::TransferFeeConfig => {
+            let config = state.get_extension::<TransferFeeConfig>()?;
+            CliTokenExtension::TransferFeeConfig {
+                transfer_fee_config_authority: optional_pubkey(
+                    config.transfer_fee_config_authority,
+                ),
+                withdraw_withheld_authority: optional_pubkey(config.withdraw_withheld_authority),
+                withheld_amount: config.withheld_amount.into(),
+                older_transfer_fee: transfer_fee(&config.older_transfer_fee),
+                newer_transfer_fee: transfer_fee(&config.newer_transfer_fee),
+            }
+        }
+        ExtensionType::TransferFeeAmount => CliTokenExtension::TransferFeeAmount {
+            withheld_amount: state
+                .get_extension::<TransferFeeAmount>()?
+                .withheld_amount
+                .into(),
+        },
+        ExtensionType::InterestBearingConfig => {
+            let config = state.get_extension::<InterestBearingConfig>()?;
+            CliTokenExtension::InterestBearingConfig {
+                rate_authority: optional_pubkey(config.rate_authority),
+                initialization_timestamp: config.initialization_timestamp.into(),
+                pre_update_average_rate: config.pre_update_average_rate.into(),
+                last_update_timestamp: config.last_update_timestamp.into(),
+                current_rate: config.current_rate.into(),
+            }
+        }
+        ExtensionType::MetadataPointer => {
+            let pointer = state.get_extension::<MetadataPointer>()?;
+            CliTokenExtension::MetadataPointer {
+                authority: optional_pubkey(pointer.authority),
+                metadata_address: optional_pubkey(pointer.metadata_address),
+            }
+        }
+        ExtensionType::TokenMetadata => {
+            let metadata = state.get_variable_len_extension::<TokenMetadata>()?;
+            CliTokenExtension::TokenMetadata {
+                update_authority: optional_pubkey(metadata.update_authority),
+                mint: metadata.mint.to_string(),
+                name: metadata.name,
+                symbol: metadata.symbol,
+                uri: metadata.uri,
+                additional_metadata: metadata.additional_metadata,
+            }
+        }
+        ExtensionType::NonTransferable => CliTokenExtension::NonTransferable,
+        ExtensionType::NonTransferableAccount => CliTokenExtension::NonTransferableAccount,
+        ExtensionType::PermanentDelegate => CliTokenExtension::PermanentDelegate {
+            delegate: optional_pubkey(state.get_extension::<PermanentDelegate>()?.delegate),
+        },
+        ExtensionType::MintCloseAuthority => CliTokenExtension::MintCloseAuthority {
+            close_authority: optional_pubkey(
+                state.get_extension::<MintCloseAuthority>()?.close_authority,
+            ),
+        },
+        ExtensionType::ImmutableOwner => CliTokenExtension::ImmutableOwner,
+        extension_type => CliTokenExtension::Unparsed(format!("{:?}", extension_type)),
+    })
+}
+
+fn transfer_fee(fee: &TransferFee) -> CliTransferFee {
+    CliTransferFee {
+        epoch: fee.epoch.into(),
+        maximum_fee: fee.maximum_fee.into(),
+        transfer_fee_basis_points: fee.transfer_fee_basis_points.into(),
+    }
+}
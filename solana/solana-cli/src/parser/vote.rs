@@ -0,0 +1,31 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_vote_interface::state::VoteStateV3;
+
+use crate::output::{CliEpochCredits, CliParsedAccount, CliParsedVote};
+
+/// 解码 vote 账户
+pub fn parse_vote(_owner: &Pubkey, data: &[u8]) -> Option<CliParsedAccount> {
+    let vote_state = VoteStateV3::deserialize(data).ok()?;
+    Some(CliParsedAccount::Vote(CliParsedVote {
+        node_pubkey: vote_state.node_pubkey.to_string(),
+        authorized_withdrawer: vote_state.authorized_withdrawer.to_string(),
+        authorized_voter: vote_state
+            .authorized_voters
+            .last()
+            .map(|(_, voter)| voter.to_string()),
+        commission: vote_state.commission,
+        root_slot: vote_state.root_slot,
+        last_vote_slot: vote_state.votes.back().map(|vote| vote.slot()),
+        last_timestamp_slot: vote_state.last_timestamp.slot,
+        last_timestamp: vote_state.last_timestamp.timestamp,
+        epoch_credits: vote_state
+            .epoch_credits
+            .iter()
+            .map(|&(epoch, credits, previous_credits)| CliEpochCredits {
+                epoch,
+                credits,
+                previous_credits,
+            })
+            .collect(),
+    }))
+}
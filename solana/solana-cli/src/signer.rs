@@ -0,0 +1,337 @@
+use std::io::{self, IsTerminal, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use bip39::{Language, Mnemonic, Seed};
+use solana_clap_utils::keypair::keypair_from_seed_phrase;
+use solana_derivation_path::DerivationPath;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use solana_sdk::signer::keypair::{
+    Keypair, keypair_from_seed_and_derivation_path, read_keypair, read_keypair_file,
+    write_keypair_file,
+};
+use solana_sdk::signer::null_signer::NullSigner;
+
+use crate::error::{CliError, CliResult};
+
+const SOURCE_PROMPT: &str = "prompt:";
+const SOURCE_FILE: &str = "file:";
+const SOURCE_STDIN: &str = "stdin";
+const SOURCE_USB: &str = "usb://";
+/// 兼容 solana-keygen 的 ASK 关键字 (不带派生路径的旧式助记词)
+const SOURCE_ASK: &str = "ASK";
+
+/// 签名者的来源，语法与官方 solana CLI 保持一致
+#[derive(Debug, PartialEq, Eq)]
+pub enum SignerSource {
+    /// keypair JSON 文件 (可带 `file:` 前缀)
+    Filepath(String),
+    /// 从标准输入读取 keypair JSON 或助记词
+    Stdin,
+    /// 交互式输入 BIP39 助记词，`prompt://?key=0/0` 或 `prompt://?full-path=m/44/501/0/0` 指定派生路径
+    Prompt {
+        derivation_path: Option<DerivationPath>,
+        legacy: bool,
+    },
+    /// 硬件钱包 (暂不支持)
+    Usb(String),
+    /// 仅有公钥，签名需通过 --signer 离线提供 (或在 --sign-only 时留空)
+    Pubkey(Pubkey),
+}
+
+impl SignerSource {
+    pub fn parse(source: &str) -> CliResult<Self> {
+        if source == SOURCE_STDIN || source == "-" {
+            return Ok(SignerSource::Stdin);
+        }
+        if source == SOURCE_ASK {
+            return Ok(SignerSource::Prompt {
+                derivation_path: None,
+                legacy: true,
+            });
+        }
+        if source.starts_with(SOURCE_USB) {
+            return Ok(SignerSource::Usb(source.to_string()));
+        }
+        if let Some(rest) = source.strip_prefix(SOURCE_PROMPT) {
+            return Ok(SignerSource::Prompt {
+                derivation_path: parse_prompt_query(rest)?,
+                legacy: false,
+            });
+        }
+        if let Some(path) = source.strip_prefix(SOURCE_FILE) {
+            return Ok(SignerSource::Filepath(path.to_string()));
+        }
+        if let Ok(pubkey) = source.parse::<Pubkey>() {
+            return Ok(SignerSource::Pubkey(pubkey));
+        }
+
+        Ok(SignerSource::Filepath(source.to_string()))
+    }
+}
+
+/// 解析 `prompt:` 之后的部分，支持 `//?key=<account>/<change>` 与 `//?full-path=<path>`
+fn parse_prompt_query(rest: &str) -> CliResult<Option<DerivationPath>> {
+    let rest = rest.trim_start_matches("//");
+    let Some(query) = rest.strip_prefix('?') else {
+        if rest.is_empty() {
+            return Ok(None);
+        }
+        return Err(CliError::Signer(format!(
+            "无法识别的 prompt 参数: {}",
+            rest
+        )));
+    };
+
+    let (key, value) = query
+        .split_once('=')
+        .ok_or_else(|| CliError::Signer(format!("无效的派生路径参数: {}", query)))?;
+    let derivation_path = match key {
+        "key" => DerivationPath::from_key_str(value),
+        "full-path" => DerivationPath::from_absolute_path_str(value),
+        _ => {
+            return Err(CliError::Signer(format!(
+                "未知的 prompt 参数 {}，仅支持 key 或 full-path",
+                key
+            )));
+        }
+    }
+    .map_err(|err| CliError::Signer(format!("无效的派生路径 {}: {}", value, err)))?;
+
+    Ok(Some(derivation_path))
+}
+
+/// 按来源加载签名者，`keypair_name` 用于交互式提示 (如 "from"、"fee-payer")
+pub fn signer_from_source(source: &str, keypair_name: &str) -> CliResult<Box<dyn Signer>> {
+    match SignerSource::parse(source)? {
+        SignerSource::Pubkey(pubkey) => Ok(Box::new(NullSigner::new(&pubkey))),
+        _ => Ok(Box::new(keypair_from_source(source, keypair_name)?)),
+    }
+}
+
+/// 按来源加载完整的 keypair (文件、stdin 或助记词)，只有公钥的来源会返回错误
+pub fn keypair_from_source(source: &str, keypair_name: &str) -> CliResult<Keypair> {
+    match SignerSource::parse(source)? {
+        SignerSource::Filepath(path) => {
+            let keypair = read_keypair_file(&path).map_err(|err| {
+                CliError::Signer(format!("Failed to read keypair file {}: {}", path, err))
+            })?;
+            Ok(keypair)
+        }
+        SignerSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|err| CliError::Signer(format!("从标准输入读取签名者失败: {}", err)))?;
+            let input = input.trim();
+            if input.starts_with('[') {
+                let keypair = read_keypair(&mut input.as_bytes()).map_err(|err| {
+                    CliError::Signer(format!("无法解析标准输入中的 keypair: {}", err))
+                })?;
+                return Ok(keypair);
+            }
+            let mnemonic = Mnemonic::from_phrase(&normalize_phrase(input), Language::English)
+                .map_err(|err| {
+                    CliError::Signer(format!("无效的助记词 ({}): {}", keypair_name, err))
+                })?;
+            let seed = Seed::new(&mnemonic, "");
+            keypair_from_seed_and_derivation_path(seed.as_bytes(), None)
+                .map_err(|err| CliError::Signer(format!("助记词派生 keypair 失败: {}", err)))
+        }
+        SignerSource::Prompt {
+            derivation_path,
+            legacy,
+        } => {
+            if !io::stdin().is_terminal() {
+                return Err(CliError::Signer(format!(
+                    "{} 使用 prompt: 来源时需要交互式终端",
+                    keypair_name
+                )));
+            }
+            keypair_from_seed_phrase(keypair_name, false, false, derivation_path, legacy).map_err(
+                |err| CliError::Signer(format!("无法从助记词恢复 {}: {}", keypair_name, err)),
+            )
+        }
+        SignerSource::Usb(locator) => Err(CliError::Signer(format!(
+            "暂不支持硬件钱包签名 ({})，请改用 keypair 文件、stdin 或 prompt:",
+            locator
+        ))),
+        SignerSource::Pubkey(pubkey) => Err(CliError::Signer(format!(
+            "{} 需要完整的 keypair，而不是公钥 {}",
+            keypair_name, pubkey
+        ))),
+    }
+}
+
+/// 读取可选的签名者参数，未指定时返回 None (由调用方退回到默认签名者)
+pub fn optional_signer_from_source(
+    source: Option<&str>,
+    keypair_name: &str,
+) -> CliResult<Option<Box<dyn Signer>>> {
+    source
+        .map(|source| signer_from_source(source, keypair_name))
+        .transpose()
+}
+
+/// 将新生成的 keypair 写入文件 (unix 下权限为 0600)，拒绝覆盖已有文件以免丢失旧私钥
+pub fn write_new_keypair_file(keypair: &Keypair, path: &str) -> CliResult<()> {
+    if Path::new(path).exists() {
+        return Err(CliError::InvalidInput(format!(
+            "{} 已存在，拒绝覆盖 (请换一个 --outfile)",
+            path
+        )));
+    }
+    save_keypair_file(keypair, path)
+}
+
+/// 将 keypair 写入文件，自动创建父目录；新建的文件在 unix 下权限为 0600
+pub fn save_keypair_file(keypair: &Keypair, path: &str) -> CliResult<()> {
+    write_keypair_file(keypair, path).map_err(|err| {
+        CliError::Other(anyhow::anyhow!("写入 keypair 文件 {} 失败: {}", path, err))
+    })?;
+    Ok(())
+}
+
+/// 靓号地址的匹配条件：公钥的 base58 以 `prefix` 开头且以 `suffix` 结尾
+#[derive(Clone, Debug)]
+pub struct GrindPattern {
+    pub prefix: String,
+    pub suffix: String,
+    /// 需要找到的 keypair 数量
+    pub count: usize,
+}
+
+impl GrindPattern {
+    /// 前缀与后缀只能包含 base58 字符，且不能同时为空
+    pub fn validate(&self) -> CliResult<()> {
+        if self.prefix.is_empty() && self.suffix.is_empty() {
+            return Err(CliError::InvalidInput("grind 前缀不能为空".to_string()));
+        }
+        for pattern in [&self.prefix, &self.suffix] {
+            if let Some(invalid) = pattern
+                .chars()
+                .find(|c| bs58::decode(c.to_string()).into_vec().is_err())
+            {
+                return Err(CliError::InvalidInput(format!(
+                    "{} 含有 base58 之外的字符 '{}' (不允许 0、O、I、l)",
+                    pattern, invalid
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn matches(&self, address: &str, ignore_case: bool) -> bool {
+        if ignore_case {
+            let address = address.to_ascii_lowercase();
+            address.starts_with(&self.prefix.to_ascii_lowercase())
+                && address.ends_with(&self.suffix.to_ascii_lowercase())
+        } else {
+            address.starts_with(&self.prefix) && address.ends_with(&self.suffix)
+        }
+    }
+}
+
+/// 多线程随机生成 keypair，直到公钥的 base58 以 `prefix` 开头
+pub fn grind_keypair(prefix: &str) -> CliResult<Keypair> {
+    let pattern = GrindPattern {
+        prefix: prefix.to_string(),
+        suffix: String::new(),
+        count: 1,
+    };
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    let mut found = None;
+    grind_keypairs(&[pattern], false, threads, |keypair, _| {
+        found = Some(keypair);
+        Ok(())
+    })?;
+    found.ok_or_else(|| CliError::Other(anyhow::anyhow!("grind 线程异常退出")))
+}
+
+/// 用 `threads` 个线程随机生成 keypair，每找到一个匹配的 keypair 就交给 `on_found`
+/// (附带匹配的条件序号)，直到每个条件都找到 `count` 个；返回尝试的 keypair 总数
+///
+/// `on_found` 在调用线程中执行，返回错误时停止搜索。
+pub fn grind_keypairs(
+    patterns: &[GrindPattern],
+    ignore_case: bool,
+    threads: usize,
+    mut on_found: impl FnMut(Keypair, usize) -> CliResult<()>,
+) -> CliResult<u64> {
+    for pattern in patterns {
+        pattern.validate()?;
+    }
+    let remaining: Vec<AtomicUsize> = patterns
+        .iter()
+        .map(|pattern| AtomicUsize::new(pattern.count))
+        .collect();
+    let attempts = AtomicU64::new(0);
+    let done = AtomicBool::new(patterns.iter().all(|pattern| pattern.count == 0));
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            let sender = sender.clone();
+            let (remaining, attempts, done) = (&remaining, &attempts, &done);
+            scope.spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    let keypair = Keypair::new();
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    let address = keypair.pubkey().to_string();
+                    let matched = patterns.iter().enumerate().position(|(index, pattern)| {
+                        pattern.matches(&address, ignore_case)
+                            && remaining[index]
+                                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| {
+                                    left.checked_sub(1)
+                                })
+                                .is_ok()
+                    });
+                    if let Some(index) = matched {
+                        if remaining
+                            .iter()
+                            .all(|left| left.load(Ordering::Relaxed) == 0)
+                        {
+                            done.store(true, Ordering::Relaxed);
+                        }
+                        if sender.send((keypair, index)).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        for (keypair, index) in receiver {
+            if let Err(err) = on_found(keypair, index) {
+                done.store(true, Ordering::Relaxed);
+                return Err(err);
+            }
+        }
+        Ok(attempts.load(Ordering::Relaxed))
+    })
+}
+
+fn normalize_phrase(phrase: &str) -> String {
+    phrase.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 按公钥去重，避免同一账户同时作为 from 与 fee payer 时重复签名
+pub fn unique_signers<'a>(signers: &[&'a dyn Signer]) -> Vec<&'a dyn Signer> {
+    let mut seen: Vec<Pubkey> = Vec::new();
+    signers
+        .iter()
+        .filter(|signer| {
+            let pubkey = signer.pubkey();
+            if seen.contains(&pubkey) {
+                false
+            } else {
+                seen.push(pubkey);
+                true
+            }
+        })
+        .copied()
+        .collect()
+}
@@ -0,0 +1,1024 @@
+use std::time::{Duration, Instant};
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use clap::Args;
+use solana_account::Account;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcSendTransactionConfig, RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_nonce::state::Data as NonceData;
+use solana_rpc_client_nonce_utils::nonblocking as nonce_utils;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::{AddressLookupTableAccount, Message, VersionedMessage, v0};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::presigner::Presigner;
+use solana_sdk::signer::{Signer, SignerError};
+use solana_sdk::transaction::VersionedTransaction;
+use solana_system_interface::instruction as system_instruction;
+use solana_transaction_status_client_types::TransactionConfirmationStatus;
+
+use crate::error::{CliError, CliResult};
+use crate::output::{
+    CliAccountMeta, CliBalanceChange, CliInstruction, CliSignOnly, CliSimulation, CliTransaction,
+    CliTxResult,
+};
+use crate::signer::{optional_signer_from_source, unique_signers};
+
+/// 所有会修改链上状态的命令共享的交易参数
+#[derive(Args, Clone, Debug, Default)]
+pub struct TransactionArgs {
+    /// 仅模拟交易并输出待签名的交易内容，不发送上链
+    #[arg(long = "dry-run", conflicts_with = "sign_only")]
+    pub dry_run: bool,
+    /// 离线签名：使用 --blockhash 构造并签名交易，输出签名与消息后退出，不访问 RPC
+    #[arg(long = "sign-only", requires = "blockhash")]
+    pub sign_only: bool,
+    /// 使用指定的区块哈希，而不是从 RPC 获取最新的区块哈希
+    #[arg(long, value_name = "BLOCKHASH")]
+    pub blockhash: Option<Hash>,
+    /// 附加离线生成的签名 (可重复)，用于在联网机器上组装并广播交易
+    #[arg(
+        long = "signer",
+        value_name = "PUBKEY=SIGNATURE",
+        value_parser = parse_signer_pair
+    )]
+    pub signers: Vec<(Pubkey, Signature)>,
+    /// 计算单元价格 (micro-lamports)，即优先费；网络拥堵时提高可加快上链
+    #[arg(long = "with-compute-unit-price", value_name = "MICRO_LAMPORTS")]
+    pub compute_unit_price: Option<u64>,
+    /// 交易的计算单元上限
+    #[arg(
+        long = "with-compute-unit-limit",
+        value_name = "UNITS",
+        value_parser = clap::value_parser!(u32).range(1..=MAX_COMPUTE_UNIT_LIMIT as i64)
+    )]
+    pub compute_unit_limit: Option<u32>,
+    /// 自动设置计算预算：按模拟消耗估算上限，并按可写账户的近期优先费选择价格；显式指定的值优先
+    #[arg(long = "auto-compute-budget", conflicts_with = "sign_only")]
+    pub auto_compute_budget: bool,
+    /// 自动模式下选择优先费的分位数 (0-100)
+    #[arg(
+        long = "priority-fee-percentile",
+        value_name = "PERCENTILE",
+        default_value_t = 50,
+        value_parser = clap::value_parser!(u8).range(0..=100),
+        requires = "auto_compute_budget"
+    )]
+    pub priority_fee_percentile: u8,
+    /// 等待交易确认的最长时间 (秒)，默认一直等到区块哈希过期 (durable nonce 交易默认 120 秒)
+    #[arg(
+        long = "confirm-timeout",
+        value_name = "SECONDS",
+        conflicts_with = "sign_only"
+    )]
+    pub confirm_timeout: Option<u64>,
+    /// 交易尚未被处理时重新广播的最大次数，默认持续重新广播直到区块哈希过期
+    #[arg(
+        long = "max-retries",
+        value_name = "COUNT",
+        conflicts_with = "sign_only"
+    )]
+    pub max_retries: Option<usize>,
+    /// 通过地址查找表 (可重复指定) 引用账户，以 v0 交易发送，单笔交易可容纳更多账户
+    #[arg(
+        long = "lookup-table",
+        value_name = "ADDRESS",
+        conflicts_with = "sign_only"
+    )]
+    pub lookup_tables: Vec<Pubkey>,
+}
+
+impl TransactionArgs {
+    /// 交易使用的区块哈希：durable nonce 账户中存储的 nonce、--blockhash 或 RPC 返回的最新区块哈希
+    ///
+    /// 联网时会校验 nonce 账户的授权者，以及 --blockhash 是否与当前存储的 nonce 一致。
+    pub async fn blockhash(
+        &self,
+        rpc_client: &RpcClient,
+        nonce: Option<&DurableNonce<'_>>,
+    ) -> CliResult<Hash> {
+        if let Some(nonce) = nonce
+            && !self.sign_only
+        {
+            let (_, data) = get_nonce_account(rpc_client, &nonce.account).await?;
+            if data.authority != nonce.authority.pubkey() {
+                return Err(CliError::Signer(format!(
+                    "nonce 账户 {} 的授权者为 {}，而不是 {}",
+                    nonce.account,
+                    data.authority,
+                    nonce.authority.pubkey()
+                )));
+            }
+            if let Some(blockhash) = self.blockhash
+                && blockhash != data.blockhash()
+            {
+                return Err(CliError::InvalidInput(format!(
+                    "--blockhash {} 与 nonce 账户当前存储的 nonce {} 不一致 (nonce 可能已被推进)",
+                    blockhash,
+                    data.blockhash()
+                )));
+            }
+            return Ok(data.blockhash());
+        }
+
+        match self.blockhash {
+            Some(blockhash) => Ok(blockhash),
+            None => Ok(rpc_client.get_latest_blockhash().await?),
+        }
+    }
+
+    /// 读取 --lookup-table 指定的地址查找表，未指定时不访问 RPC
+    pub async fn lookup_tables(
+        &self,
+        rpc_client: &RpcClient,
+    ) -> CliResult<Vec<AddressLookupTableAccount>> {
+        get_lookup_tables(rpc_client, &self.lookup_tables).await
+    }
+
+    /// 按 --with-compute-unit-limit / --with-compute-unit-price / --auto-compute-budget 确定计算预算
+    ///
+    /// 自动模式下模拟 `instructions` 得到实际消耗的计算单元并留出余量，
+    /// 价格取交易中可写账户近期优先费的 --priority-fee-percentile 分位数。
+    pub async fn compute_budget(
+        &self,
+        instructions: &[Instruction],
+        fee_payer: &Pubkey,
+        lookup_tables: &[AddressLookupTableAccount],
+        rpc_client: &RpcClient,
+    ) -> CliResult<ComputeBudget> {
+        let mut budget = ComputeBudget {
+            unit_limit: self.compute_unit_limit,
+            unit_price: self.compute_unit_price,
+        };
+        if !self.auto_compute_budget {
+            return Ok(budget);
+        }
+        if budget.unit_limit.is_none() {
+            budget.unit_limit = Some(
+                estimate_compute_unit_limit(instructions, fee_payer, lookup_tables, rpc_client)
+                    .await?,
+            );
+        }
+        if budget.unit_price.is_none() {
+            budget.unit_price = Some(
+                estimate_compute_unit_price(
+                    instructions,
+                    fee_payer,
+                    self.priority_fee_percentile,
+                    rpc_client,
+                )
+                .await?,
+            );
+        }
+        eprintln!(
+            "Compute budget: {} units at {} micro-lamports per unit",
+            budget.unit_limit.unwrap_or_default(),
+            budget.unit_price.unwrap_or_default()
+        );
+        Ok(budget)
+    }
+
+    fn presigners(&self) -> Vec<Presigner> {
+        self.signers
+            .iter()
+            .map(|(pubkey, signature)| Presigner::new(pubkey, signature))
+            .collect()
+    }
+}
+
+/// 交易单个计算单元上限的最大值
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// 单笔交易序列化后的大小上限，与 solana-packet 的 PACKET_DATA_SIZE 相同
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// 每条计算预算指令本身消耗的计算单元
+const COMPUTE_BUDGET_INSTRUCTION_UNITS: u64 = 150;
+
+/// getRecentPrioritizationFees 单次最多接受的账户数
+const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// 交易的计算预算：计算单元上限与单价 (优先费)，未设置的项不生成指令
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ComputeBudget {
+    pub unit_limit: Option<u32>,
+    pub unit_price: Option<u64>,
+}
+
+impl ComputeBudget {
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        if let Some(units) = self.unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(micro_lamports) = self.unit_price.filter(|price| *price > 0) {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                micro_lamports,
+            ));
+        }
+        instructions
+    }
+}
+
+/// 以最大上限模拟交易，按实际消耗加上价格指令的开销与 10% 余量得到计算单元上限
+async fn estimate_compute_unit_limit(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+    rpc_client: &RpcClient,
+) -> CliResult<u32> {
+    let mut probe = instructions.to_vec();
+    probe.push(ComputeBudgetInstruction::set_compute_unit_limit(
+        MAX_COMPUTE_UNIT_LIMIT,
+    ));
+    let transaction = unsigned_transaction(compile_message(
+        &probe,
+        fee_payer,
+        lookup_tables,
+        Hash::default(),
+    )?);
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(rpc_client.commitment()),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc_client
+        .simulate_transaction_with_config(&transaction, config)
+        .await?
+        .value;
+    if let Some(err) = result.err {
+        return Err(CliError::from_transaction_error(
+            err.into(),
+            result.logs.unwrap_or_default(),
+        ));
+    }
+    let units = result.units_consumed.unwrap_or_default() + COMPUTE_BUDGET_INSTRUCTION_UNITS;
+    Ok((units + units / 10).min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32)
+}
+
+/// 交易中可写账户在近期区块中的优先费，按 `percentile` 分位数取值
+async fn estimate_compute_unit_price(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    percentile: u8,
+    rpc_client: &RpcClient,
+) -> CliResult<u64> {
+    let message = Message::new(instructions, Some(fee_payer));
+    let writable_accounts: Vec<Pubkey> = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_maybe_writable(*index, None))
+        .map(|(_, pubkey)| *pubkey)
+        .take(MAX_PRIORITIZATION_FEE_ACCOUNTS)
+        .collect();
+    let mut fees: Vec<u64> = rpc_client
+        .get_recent_prioritization_fees(&writable_accounts)
+        .await?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    if fees.is_empty() {
+        return Ok(0);
+    }
+    fees.sort_unstable();
+    Ok(fees[((fees.len() - 1) * percentile as usize).div_ceil(100)])
+}
+
+/// 使用 durable nonce 代替区块哈希的参数，离线签名的交易不再受区块哈希过期时间的限制
+#[derive(Args, Clone, Debug, Default)]
+pub struct NonceArgs {
+    /// durable nonce 账户，交易以其存储的 nonce 作为区块哈希，并在第一条指令中推进 nonce
+    #[arg(long, value_name = "NONCE_ACCOUNT")]
+    pub nonce: Option<Pubkey>,
+    /// nonce 账户的授权签名者，语法同 --from，默认由手续费支付者担任
+    #[arg(long = "nonce-authority", value_name = "SIGNER", requires = "nonce")]
+    pub nonce_authority: Option<String>,
+}
+
+impl NonceArgs {
+    /// 读取 --nonce-authority 指定的签名者，未指定时返回 None (由调用方退回到手续费支付者)
+    pub fn authority_signer(&self) -> CliResult<Option<Box<dyn Signer>>> {
+        optional_signer_from_source(self.nonce_authority.as_deref(), "nonce-authority")
+    }
+
+    pub fn durable_nonce<'a>(&self, authority: &'a dyn Signer) -> Option<DurableNonce<'a>> {
+        self.nonce
+            .map(|account| DurableNonce { account, authority })
+    }
+}
+
+/// 已加载授权签名者的 durable nonce 账户
+pub struct DurableNonce<'a> {
+    pub account: Pubkey,
+    pub authority: &'a dyn Signer,
+}
+
+/// 使用 durable nonce 时在指令列表最前面插入 advance_nonce_account
+pub fn with_nonce(
+    mut instructions: Vec<Instruction>,
+    nonce: Option<&DurableNonce<'_>>,
+) -> Vec<Instruction> {
+    if let Some(nonce) = nonce {
+        instructions.insert(
+            0,
+            system_instruction::advance_nonce_account(&nonce.account, &nonce.authority.pubkey()),
+        );
+    }
+    instructions
+}
+
+/// 组装交易：durable nonce 的推进指令在最前，其后依次为计算预算指令与业务指令
+///
+/// 指定了地址查找表时编译为 v0 交易，否则为 legacy 交易；区块哈希在签名时填入。
+pub fn build_transaction(
+    instructions: Vec<Instruction>,
+    fee_payer: &Pubkey,
+    nonce: Option<&DurableNonce<'_>>,
+    budget: &ComputeBudget,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> CliResult<VersionedTransaction> {
+    let mut all_instructions = budget.instructions();
+    all_instructions.extend(instructions);
+    let message = compile_message(
+        &with_nonce(all_instructions, nonce),
+        fee_payer,
+        lookup_tables,
+        Hash::default(),
+    )?;
+    Ok(unsigned_transaction(message))
+}
+
+/// 按交易参数确定计算预算与地址查找表后组装交易，所有会发送交易的命令都通过这里构造交易
+pub async fn new_transaction(
+    instructions: Vec<Instruction>,
+    fee_payer: &Pubkey,
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<VersionedTransaction> {
+    let lookup_tables = tx_args.lookup_tables(rpc_client).await?;
+    let budget = tx_args
+        .compute_budget(
+            &with_nonce(instructions.clone(), nonce),
+            fee_payer,
+            &lookup_tables,
+            rpc_client,
+        )
+        .await?;
+    build_transaction(instructions, fee_payer, nonce, &budget, &lookup_tables)
+}
+
+/// 编译交易消息：没有地址查找表时为 legacy 消息，否则为 v0 消息
+fn compile_message(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> CliResult<VersionedMessage> {
+    if lookup_tables.is_empty() {
+        return Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+            instructions,
+            Some(fee_payer),
+            &blockhash,
+        )));
+    }
+    v0::Message::try_compile(fee_payer, instructions, lookup_tables, blockhash)
+        .map(VersionedMessage::V0)
+        .map_err(|err| CliError::InvalidInput(format!("无法编译 v0 交易: {}", err)))
+}
+
+/// 签名全部为空的交易
+fn unsigned_transaction(message: VersionedMessage) -> VersionedTransaction {
+    VersionedTransaction {
+        signatures: vec![Signature::default(); message.header().num_required_signatures as usize],
+        message,
+    }
+}
+
+/// 读取并解码地址查找表，账户不存在或不属于地址查找表程序时报错
+pub async fn get_lookup_tables(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> CliResult<Vec<AddressLookupTableAccount>> {
+    if addresses.is_empty() {
+        return Ok(Vec::new());
+    }
+    let accounts = rpc_client.get_multiple_accounts(addresses).await?;
+    addresses
+        .iter()
+        .zip(accounts)
+        .map(|(address, account)| {
+            let account = account
+                .ok_or_else(|| CliError::InvalidInput(format!("地址查找表 {} 不存在", address)))?;
+            if account.owner != solana_sdk_ids::address_lookup_table::ID {
+                return Err(CliError::InvalidInput(format!(
+                    "{} 不是地址查找表 (owner 为 {})",
+                    address, account.owner
+                )));
+            }
+            let table = AddressLookupTable::deserialize(&account.data).map_err(|err| {
+                CliError::InvalidInput(format!("无法解析地址查找表 {}: {}", address, err))
+            })?;
+            Ok(AddressLookupTableAccount {
+                key: *address,
+                addresses: table.addresses.to_vec(),
+            })
+        })
+        .collect()
+}
+
+/// 交易引用的全部账户，顺序与指令中的账户索引一致：
+/// 静态账户在前，其后依次为从地址查找表加载的可写账户与只读账户
+pub async fn transaction_account_keys(
+    rpc_client: &RpcClient,
+    transaction: &VersionedTransaction,
+) -> CliResult<Vec<Pubkey>> {
+    let message = &transaction.message;
+    let mut account_keys = message.static_account_keys().to_vec();
+    let lookups = message.address_table_lookups().unwrap_or_default();
+    if lookups.is_empty() {
+        return Ok(account_keys);
+    }
+    let table_addresses: Vec<Pubkey> = lookups.iter().map(|lookup| lookup.account_key).collect();
+    let tables = get_lookup_tables(rpc_client, &table_addresses).await?;
+    let mut writable = Vec::new();
+    let mut readonly = Vec::new();
+    for (lookup, table) in lookups.iter().zip(&tables) {
+        let resolve = |index: &u8| {
+            table
+                .addresses
+                .get(*index as usize)
+                .copied()
+                .ok_or_else(|| {
+                    CliError::InvalidInput(format!(
+                        "地址查找表 {} 中没有索引 {} 的地址",
+                        table.key, index
+                    ))
+                })
+        };
+        for index in &lookup.writable_indexes {
+            writable.push(resolve(index)?);
+        }
+        for index in &lookup.readonly_indexes {
+            readonly.push(resolve(index)?);
+        }
+    }
+    account_keys.extend(writable);
+    account_keys.extend(readonly);
+    Ok(account_keys)
+}
+
+/// 估算消息的手续费 (getFeeForMessage)，支持 legacy 与 v0 消息
+pub async fn fee_for_message(rpc_client: &RpcClient, message: &VersionedMessage) -> CliResult<u64> {
+    let fee = match message {
+        VersionedMessage::Legacy(message) => rpc_client.get_fee_for_message(message).await?,
+        VersionedMessage::V0(message) => rpc_client.get_fee_for_message(message).await?,
+    };
+    Ok(fee)
+}
+
+/// 读取 nonce 账户并解析其中存储的 nonce 与授权者
+pub async fn get_nonce_account(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> CliResult<(Account, NonceData)> {
+    let account =
+        nonce_utils::get_account_with_commitment(rpc_client, address, rpc_client.commitment())
+            .await
+            .map_err(|err| {
+                CliError::InvalidInput(format!("无法读取 nonce 账户 {}: {}", address, err))
+            })?;
+    let data = nonce_utils::data_from_account(&account).map_err(|err| {
+        CliError::InvalidInput(format!("{} 不是已初始化的 nonce 账户: {}", address, err))
+    })?;
+    Ok((account, data))
+}
+
+/// 解析 `PUBKEY=SIGNATURE` 形式的离线签名 (均为 base58)
+fn parse_signer_pair(value: &str) -> Result<(Pubkey, Signature), String> {
+    let (pubkey, signature) = value
+        .split_once('=')
+        .ok_or_else(|| format!("格式应为 PUBKEY=SIGNATURE: {}", value))?;
+    let pubkey = pubkey
+        .parse::<Pubkey>()
+        .map_err(|err| format!("无效的公钥 {}: {}", pubkey, err))?;
+    let signature = signature
+        .parse::<Signature>()
+        .map_err(|err| format!("无效的签名 {}: {}", signature, err))?;
+    Ok((pubkey, signature))
+}
+
+/// 由 `fee_payer` 支付手续费，将指令组装成交易、签名并交给 process_transaction 处理
+pub async fn send_instructions(
+    instructions: &[Instruction],
+    fee_payer: &dyn Signer,
+    signers: &[&dyn Signer],
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    send_instructions_with_nonce(instructions, fee_payer, signers, None, tx_args, rpc_client).await
+}
+
+/// 同 send_instructions，`nonce` 不为 None 时以 durable nonce 代替区块哈希 (nonce 授权者自动加入签名者)
+pub async fn send_instructions_with_nonce(
+    instructions: &[Instruction],
+    fee_payer: &dyn Signer,
+    signers: &[&dyn Signer],
+    nonce: Option<&DurableNonce<'_>>,
+    tx_args: &TransactionArgs,
+    rpc_client: &RpcClient,
+) -> CliResult<CliTxResult> {
+    let blockhash = tx_args.blockhash(rpc_client, nonce).await?;
+    let mut transaction = new_transaction(
+        instructions.to_vec(),
+        &fee_payer.pubkey(),
+        nonce,
+        tx_args,
+        rpc_client,
+    )
+    .await?;
+    let mut signers = signers.to_vec();
+    if let Some(nonce) = nonce {
+        signers.push(nonce.authority);
+    }
+    sign_transaction(&mut transaction, &signers, blockhash, tx_args)?;
+    process_transaction(rpc_client, &transaction, tx_args).await
+}
+
+/// 先模拟交易 (模拟失败即中止)，再根据参数决定是否发送并确认
+pub async fn process_transaction(
+    rpc_client: &RpcClient,
+    transaction: &VersionedTransaction,
+    args: &TransactionArgs,
+) -> CliResult<CliTxResult> {
+    if args.sign_only {
+        return Ok(CliTxResult {
+            sign_only: Some(describe_sign_only(transaction)),
+            ..CliTxResult::default()
+        });
+    }
+
+    let simulation = simulate_transaction(rpc_client, transaction).await?;
+
+    if args.dry_run {
+        return Ok(CliTxResult {
+            simulation: Some(simulation),
+            transaction: Some(describe_transaction(rpc_client, transaction).await?),
+            ..CliTxResult::default()
+        });
+    }
+
+    let signature = send_and_confirm(rpc_client, transaction, args).await?;
+
+    Ok(CliTxResult {
+        simulation: Some(simulation),
+        signature: Some(signature.to_string()),
+        ..CliTxResult::default()
+    })
+}
+
+/// getSignatureStatuses 单次最多查询的签名数
+pub const MAX_SIGNATURE_STATUSES: usize = 256;
+/// 轮询签名状态的间隔
+pub const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// 交易尚未被处理时重新广播 (并检查是否过期) 的间隔
+const REBROADCAST_INTERVAL: Duration = Duration::from_secs(2);
+/// durable nonce 交易不会随区块高度过期，未指定 --confirm-timeout 时最多等待的时间
+const DEFAULT_NONCE_CONFIRM_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 交易失效的判定依据
+enum Expiry {
+    /// 区块高度超过 last_valid_block_height 后交易不可能再上链
+    BlockHeight(u64),
+    /// nonce 账户中存储的 nonce 被推进后交易不可能再上链
+    Nonce { account: Pubkey, nonce: Hash },
+}
+
+impl Expiry {
+    async fn of(transaction: &VersionedTransaction, rpc_client: &RpcClient) -> CliResult<Self> {
+        let blockhash = *transaction.message.recent_blockhash();
+        if transaction.uses_durable_nonce()
+            && let Some(&index) = transaction.message.instructions()[0].accounts.first()
+        {
+            // nonce 账户可能是从地址查找表加载的
+            let account_keys = transaction_account_keys(rpc_client, transaction).await?;
+            if let Some(account) = account_keys.get(index as usize) {
+                return Ok(Expiry::Nonce {
+                    account: *account,
+                    nonce: blockhash,
+                });
+            }
+        }
+        // 交易的区块哈希不晚于最新区块哈希，因此最新区块哈希的 last_valid_block_height 是它的上界；
+        // 两者相同 (通常如此) 时即为精确值
+        let (_, last_valid_block_height) = rpc_client
+            .get_latest_blockhash_with_commitment(rpc_client.commitment())
+            .await?;
+        Ok(Expiry::BlockHeight(last_valid_block_height))
+    }
+
+    async fn is_expired(&self, rpc_client: &RpcClient) -> CliResult<bool> {
+        match self {
+            Expiry::BlockHeight(last_valid_block_height) => {
+                Ok(rpc_client.get_block_height().await? > *last_valid_block_height)
+            }
+            Expiry::Nonce { account, nonce } => {
+                let (_, data) = get_nonce_account(rpc_client, account).await?;
+                Ok(data.blockhash() != *nonce)
+            }
+        }
+    }
+}
+
+/// 发送交易并等待其达到 RPC 客户端的提交级别
+///
+/// 每隔 REBROADCAST_INTERVAL 在交易尚未被处理时重新广播 (至多 --max-retries 次)，
+/// 并在 stderr 报告 processed → confirmed → finalized 的进度。区块哈希过期 (或 nonce 被推进)
+/// 且交易仍未上链时返回 BlockhashExpired，此时可以安全地重新发送；超过 --confirm-timeout 时返回 ConfirmationTimeout。
+pub async fn send_and_confirm(
+    rpc_client: &RpcClient,
+    transaction: &VersionedTransaction,
+    args: &TransactionArgs,
+) -> CliResult<Signature> {
+    let signature = transaction.signatures[0];
+    let commitment = rpc_client.commitment();
+    let expiry = Expiry::of(transaction, rpc_client).await?;
+    let timeout = match (args.confirm_timeout, &expiry) {
+        (Some(seconds), _) => Some(Duration::from_secs(seconds)),
+        (None, Expiry::Nonce { .. }) => Some(DEFAULT_NONCE_CONFIRM_TIMEOUT),
+        (None, Expiry::BlockHeight(_)) => None,
+    };
+    // 发送前已经模拟过，跳过预检；由这里负责重新广播，RPC 节点不再自行重试
+    let config = RpcSendTransactionConfig {
+        skip_preflight: true,
+        preflight_commitment: Some(commitment.commitment),
+        max_retries: Some(0),
+        ..RpcSendTransactionConfig::default()
+    };
+
+    let started = Instant::now();
+    rpc_client
+        .send_transaction_with_config(transaction, config)
+        .await
+        .map_err(|err| CliError::from_send_error(err, signature))?;
+    let mut last_broadcast = Instant::now();
+    let mut retries = 0;
+    let mut reported: Option<TransactionConfirmationStatus> = None;
+
+    loop {
+        tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+        if timeout.is_some_and(|timeout| started.elapsed() >= timeout) {
+            return Err(CliError::ConfirmationTimeout(signature));
+        }
+
+        let status = rpc_client
+            .get_signature_statuses(&[signature])
+            .await?
+            .value
+            .pop()
+            .flatten();
+        if let Some(status) = status {
+            if let Some(error) = status.err.clone() {
+                return Err(CliError::from_executed_error(error, signature));
+            }
+            let confirmation_status = status.confirmation_status();
+            if reported.as_ref() != Some(&confirmation_status) {
+                eprintln!(
+                    "Transaction {}: {} (slot {})",
+                    signature,
+                    confirmation_status_str(&confirmation_status),
+                    status.slot
+                );
+                reported = Some(confirmation_status);
+            }
+            if status.satisfies_commitment(commitment) {
+                return Ok(signature);
+            }
+            continue;
+        }
+
+        if last_broadcast.elapsed() < REBROADCAST_INTERVAL {
+            continue;
+        }
+        if expiry.is_expired(rpc_client).await? {
+            // 过期检查与上一次状态查询之间交易可能刚好上链，再确认一次
+            let status = rpc_client
+                .get_signature_statuses(&[signature])
+                .await?
+                .value
+                .pop()
+                .flatten();
+            if status.is_none() {
+                return Err(CliError::BlockhashExpired(signature));
+            }
+            continue;
+        }
+        if args
+            .max_retries
+            .is_none_or(|max_retries| retries < max_retries)
+        {
+            retries += 1;
+            eprintln!(
+                "Rebroadcasting transaction {} (retry {})",
+                signature, retries
+            );
+            // 重新广播失败不影响已发送的交易，继续等待确认
+            if let Err(err) = rpc_client
+                .send_transaction_with_config(transaction, config)
+                .await
+            {
+                eprintln!("Rebroadcast failed: {}", err);
+            }
+        }
+        last_broadcast = Instant::now();
+    }
+}
+
+/// 提交级别的小写名称，与 RPC 返回的 confirmationStatus 一致
+pub fn confirmation_status_str(status: &TransactionConfirmationStatus) -> &'static str {
+    match status {
+        TransactionConfirmationStatus::Processed => "processed",
+        TransactionConfirmationStatus::Confirmed => "confirmed",
+        TransactionConfirmationStatus::Finalized => "finalized",
+    }
+}
+
+/// 调用 simulateTransaction，汇总消耗的计算单元、程序日志、手续费与各账户余额变化
+pub async fn simulate_transaction(
+    rpc_client: &RpcClient,
+    transaction: &VersionedTransaction,
+) -> CliResult<CliSimulation> {
+    let account_keys = transaction_account_keys(rpc_client, transaction).await?;
+    let pre_accounts = rpc_client.get_multiple_accounts(&account_keys).await?;
+    let fee = fee_for_message(rpc_client, &transaction.message).await?;
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        commitment: Some(rpc_client.commitment()),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: account_keys.iter().map(ToString::to_string).collect(),
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc_client
+        .simulate_transaction_with_config(transaction, config)
+        .await?
+        .value;
+
+    let logs = result.logs.unwrap_or_default();
+    if let Some(err) = result.err {
+        return Err(CliError::from_transaction_error(err.into(), logs));
+    }
+
+    let post_accounts = result.accounts.unwrap_or_default();
+    let balance_changes = account_keys
+        .iter()
+        .enumerate()
+        .filter_map(|(index, address)| {
+            let pre = pre_accounts
+                .get(index)
+                .and_then(|account| account.as_ref())
+                .map_or(0, |account| account.lamports);
+            let post = post_accounts
+                .get(index)
+                .and_then(|account| account.as_ref())
+                .map_or(0, |account| account.lamports);
+            (pre != post).then(|| CliBalanceChange {
+                address: address.to_string(),
+                pre,
+                post,
+                delta: post as i128 - pre as i128,
+            })
+        })
+        .collect();
+
+    Ok(CliSimulation {
+        units_consumed: result.units_consumed,
+        fee,
+        logs,
+        balance_changes,
+    })
+}
+
+/// 将交易展开为可审阅的结构 (指令、账户权限) 并附带 base64 编码的完整交易
+///
+/// v0 交易中从地址查找表加载的账户会先解析为实际地址。
+pub async fn describe_transaction(
+    rpc_client: &RpcClient,
+    transaction: &VersionedTransaction,
+) -> CliResult<CliTransaction> {
+    let message = &transaction.message;
+    let account_keys = transaction_account_keys(rpc_client, transaction).await?;
+    let instructions = message
+        .instructions()
+        .iter()
+        .map(|instruction| CliInstruction {
+            program_id: account_keys[instruction.program_id_index as usize].to_string(),
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|index| {
+                    let index = *index as usize;
+                    CliAccountMeta {
+                        pubkey: account_keys[index].to_string(),
+                        is_signer: message.is_signer(index),
+                        is_writable: message.is_maybe_writable(index, None),
+                    }
+                })
+                .collect(),
+            data: bs58::encode(&instruction.data).into_string(),
+        })
+        .collect();
+    let encoded = bincode::serialize(transaction)
+        .map_err(|err| CliError::Other(anyhow::anyhow!("序列化交易失败: {}", err)))?;
+
+    let version = match message {
+        VersionedMessage::Legacy(_) => "legacy".to_string(),
+        VersionedMessage::V0(_) => "0".to_string(),
+    };
+
+    Ok(CliTransaction {
+        version,
+        fee_payer: account_keys[0].to_string(),
+        recent_blockhash: message.recent_blockhash().to_string(),
+        signers: required_signers(message)
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        lookup_tables: message
+            .address_table_lookups()
+            .unwrap_or_default()
+            .iter()
+            .map(|lookup| lookup.account_key.to_string())
+            .collect(),
+        instructions,
+        encoded: BASE64_STANDARD.encode(encoded),
+    })
+}
+
+/// 离线签名结果：已有的签名、尚缺的签名者以及序列化后的消息
+fn describe_sign_only(transaction: &VersionedTransaction) -> CliSignOnly {
+    let message = &transaction.message;
+    let verified = transaction.verify_with_results();
+    let mut signers = Vec::new();
+    let mut absent = Vec::new();
+    let mut bad_signatures = Vec::new();
+    for (index, signature) in transaction.signatures.iter().enumerate() {
+        let pubkey = message.static_account_keys()[index];
+        if *signature == Signature::default() {
+            absent.push(pubkey.to_string());
+        } else if verified[index] {
+            signers.push(format!("{}={}", pubkey, signature));
+        } else {
+            bad_signatures.push(pubkey.to_string());
+        }
+    }
+
+    CliSignOnly {
+        blockhash: message.recent_blockhash().to_string(),
+        signers,
+        absent,
+        bad_signatures,
+        message: BASE64_STANDARD.encode(message.serialize()),
+    }
+}
+
+/// --signer 的签名无法验证时的提示：通常是重新构造的交易与离线签名时的内容不同
+const PRESIGNER_MISMATCH: &str =
+    "--signer 提供的签名与交易内容不匹配，请确认命令参数及 --blockhash 与离线签名时一致";
+
+/// 用本地签名者与 --signer 提供的离线签名共同签名交易
+///
+/// 同一账户担任多个角色时只签一次；已有离线签名的账户不再使用本地签名者 (如仅有公钥的 NullSigner)。
+/// 非 --sign-only 模式下交易必须收齐所有签名。
+pub fn sign_transaction(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn Signer],
+    blockhash: Hash,
+    args: &TransactionArgs,
+) -> CliResult<()> {
+    let required = required_signers(&transaction.message);
+    if let Some((pubkey, _)) = args
+        .signers
+        .iter()
+        .find(|(pubkey, _)| !required.contains(pubkey))
+    {
+        return Err(CliError::Signer(format!(
+            "--signer {} 不是该交易的签名者",
+            pubkey
+        )));
+    }
+
+    let presigners = args.presigners();
+    let mut all_signers: Vec<&dyn Signer> = presigners
+        .iter()
+        .map(|presigner| presigner as &dyn Signer)
+        .collect();
+    all_signers.extend(signers.iter().filter(|signer| {
+        !args
+            .signers
+            .iter()
+            .any(|(pubkey, _)| *pubkey == signer.pubkey())
+    }));
+    partial_sign(transaction, &unique_signers(&all_signers), blockhash).map_err(
+        |err| match err {
+            SignerError::PresignerError(_) => CliError::Signer(PRESIGNER_MISMATCH.to_string()),
+            err => CliError::from(err),
+        },
+    )?;
+
+    if !args.sign_only {
+        let missing: Vec<String> = transaction
+            .signatures
+            .iter()
+            .zip(transaction.message.static_account_keys())
+            .filter(|(signature, _)| **signature == Signature::default())
+            .map(|(_, pubkey)| pubkey.to_string())
+            .collect();
+        if !missing.is_empty() {
+            return Err(CliError::Signer(format!(
+                "缺少 {} 的签名，请提供对应的 keypair 或通过 --signer PUBKEY=SIGNATURE 传入离线签名",
+                missing.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 交易中需要签名的账户 (静态账户的前 num_required_signatures 个)
+fn required_signers(message: &VersionedMessage) -> &[Pubkey] {
+    &message.static_account_keys()[..message.header().num_required_signatures as usize]
+}
+
+/// 填入区块哈希并用 `signers` 签名；区块哈希变化时清空已有签名，签名者不在交易的签名账户中时报错
+fn partial_sign(
+    transaction: &mut VersionedTransaction,
+    signers: &[&dyn Signer],
+    blockhash: Hash,
+) -> Result<(), SignerError> {
+    if *transaction.message.recent_blockhash() != blockhash {
+        transaction.message.set_recent_blockhash(blockhash);
+        transaction.signatures.fill(Signature::default());
+    }
+    let message_data = transaction.message.serialize();
+    for signer in signers {
+        let pubkey = signer.try_pubkey()?;
+        let index = required_signers(&transaction.message)
+            .iter()
+            .position(|key| *key == pubkey)
+            .ok_or(SignerError::KeypairPubkeyMismatch)?;
+        transaction.signatures[index] = signer.try_sign_message(&message_data)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_signer_pair_valid() {
+        let pubkey = Pubkey::new_unique();
+        let signature = Signature::new_unique();
+        assert_eq!(
+            parse_signer_pair(&format!("{}={}", pubkey, signature)),
+            Ok((pubkey, signature))
+        );
+    }
+
+    #[test]
+    fn parse_signer_pair_missing_separator() {
+        let value = format!("{}{}", Pubkey::new_unique(), Signature::new_unique());
+        let err = parse_signer_pair(&value).unwrap_err();
+        assert!(err.starts_with("格式应为 PUBKEY=SIGNATURE"), "{}", err);
+    }
+
+    #[test]
+    fn parse_signer_pair_invalid_pubkey() {
+        let signature = Signature::new_unique();
+        for pubkey in ["", "not-a-pubkey", "11111"] {
+            let err = parse_signer_pair(&format!("{}={}", pubkey, signature)).unwrap_err();
+            assert!(err.starts_with("无效的公钥"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn parse_signer_pair_invalid_signature() {
+        let pubkey = Pubkey::new_unique();
+        // 第二个 `=` 属于签名部分
+        let extra = format!("{}={}", Signature::new_unique(), Signature::new_unique());
+        for signature in ["", "not-a-signature", "1111", extra.as_str()] {
+            let err = parse_signer_pair(&format!("{}={}", pubkey, signature)).unwrap_err();
+            assert!(err.starts_with("无效的签名"), "{}", err);
+        }
+    }
+}